@@ -0,0 +1,402 @@
+//! Absolute sector addressing of a disc.
+//!
+//! Logical block address (LBA) 0 is the start of the first file, absolute MSF addresses include
+//! the 150 sectors (2 seconds) lead-in.
+
+use crate::{
+  core::{
+    timestamp::CueTimeStamp,
+    track::{DataType, TrackNo},
+  },
+  discid::toc::{ENHANCED_CD_GAP_FRAMES, LEAD_IN_FRAMES},
+  document::{CueSheet, Track},
+};
+use alloc::vec::Vec;
+
+/// Absolute address in minutes, seconds and frames.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Msf {
+  pub minute: u8,
+  pub second: u8,
+  pub frame: u8,
+}
+
+/// Size of a source file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum FileSize {
+  /// Byte size of a raw sector image, such as `BINARY` and `MOTOROLA` files.
+  Bytes(u64),
+
+  /// Length of a decoded audio file.
+  Length(CueTimeStamp),
+}
+
+/// Recordable media capacities.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum MediaSize {
+  /// 74 minutes, 650 MB media.
+  Cd74,
+
+  /// 80 minutes, 700 MB media.
+  Cd80,
+}
+
+/// Track position on the disc.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TrackLayout {
+  pub track_no: TrackNo,
+  pub data_type: DataType,
+
+  /// Session number, data tracks after the audio tracks of a CD-Extra are in session 2.
+  pub session: u8,
+
+  /// Start of the pregap, including `PREGAP` silence.
+  pub pregap_lba: u32,
+
+  /// Start of the track (INDEX 01).
+  pub start_lba: u32,
+
+  /// Sectors from INDEX 01 to the next track's pregap, including `POSTGAP` silence.
+  pub length: u32,
+
+  /// Position of the file in [CueSheet::files].
+  pub file: Option<usize>,
+
+  /// Byte offset of INDEX 01 in the file, only known for [FileSize::Bytes] files.
+  pub file_offset: Option<u64>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DiscLayout {
+  pub tracks: Vec<TrackLayout>,
+
+  /// Lead-out LBA.
+  pub lead_out: u32,
+}
+
+/// Disc usage for a media size.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Capacity {
+  /// Used sectors, including the lead-in.
+  pub used: u32,
+  pub media: MediaSize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct AddressingError {
+  kind: AddressingErrorKind,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum AddressingErrorKind {
+  /// Size of the file at the given position is missing.
+  MissingFileSize(usize),
+
+  /// Byte size of the file at the given position is smaller than its last track start.
+  InvalidFileSize(usize),
+
+  /// Index has no `FILE` command.
+  MissingFile,
+}
+
+impl Msf {
+  /// Converts the LBA to an absolute address with the 150 sectors lead-in.
+  #[inline]
+  pub const fn from_lba(lba: u32) -> Self {
+    let sectors = lba + LEAD_IN_FRAMES;
+
+    Self {
+      minute: (sectors / 4500) as u8,
+      second: ((sectors / 75) % 60) as u8,
+      frame: (sectors % 75) as u8,
+    }
+  }
+
+  #[inline]
+  pub const fn to_lba(&self) -> Option<u32> {
+    let sectors = self.minute as u32 * 4500 + self.second as u32 * 75 + self.frame as u32;
+    sectors.checked_sub(LEAD_IN_FRAMES)
+  }
+}
+
+impl core::fmt::Display for Msf {
+  #[inline]
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    f.write_fmt(format_args!(
+      "{:02}:{:02}:{:02}",
+      self.minute, self.second, self.frame
+    ))
+  }
+}
+
+impl MediaSize {
+  /// Returns the media capacity in sectors, including the lead-in.
+  #[inline]
+  pub const fn sectors(&self) -> u32 {
+    match self {
+      MediaSize::Cd74 => 74 * 4500,
+      MediaSize::Cd80 => 80 * 4500,
+    }
+  }
+}
+
+impl Capacity {
+  #[inline]
+  pub const fn fits(&self) -> bool {
+    self.used <= self.media.sectors()
+  }
+
+  /// Returns the remaining sectors, `None` when the disc does not fit.
+  #[inline]
+  pub const fn free_sectors(&self) -> Option<u32> {
+    self.media.sectors().checked_sub(self.used)
+  }
+
+  /// Returns the usage in percent.
+  #[inline]
+  pub fn usage(&self) -> f32 {
+    self.used as f32 * 100.0 / self.media.sectors() as f32
+  }
+}
+
+impl TrackLayout {
+  #[inline]
+  pub const fn start_msf(&self) -> Msf {
+    Msf::from_lba(self.start_lba)
+  }
+
+  /// Returns the track length in bytes with its sector size.
+  #[inline]
+  pub const fn byte_size(&self) -> u64 {
+    self.length as u64 * self.data_type.sector_size() as u64
+  }
+}
+
+impl DiscLayout {
+  /// Calculates the disc layout. `file_sizes` are the sizes of each file in [CueSheet::files]
+  /// order.
+  pub fn new(cuesheet: &CueSheet, file_sizes: &[FileSize]) -> Result<Self, AddressingError> {
+    let file_sectors = file_sectors(cuesheet, file_sizes)?;
+    let mut tracks = Vec::with_capacity(cuesheet.tracks.len());
+    let mut file_starts = Vec::with_capacity(file_sectors.len());
+    let mut silence: u32 = 0;
+    let mut session_gap = 0;
+    let mut has_audio = false;
+
+    for (position, _) in file_sectors.iter().enumerate() {
+      file_starts.push(file_sectors[..position].iter().sum::<u32>());
+    }
+
+    let absolute = |file: Option<usize>, timestamp: CueTimeStamp| -> Result<u32, AddressingError> {
+      let file = file.ok_or(AddressingError::new(AddressingErrorKind::MissingFile))?;
+      Ok(file_starts[file] + timestamp.as_frames() as u32)
+    };
+
+    for track in &cuesheet.tracks {
+      let start = track
+        .start_index()
+        .ok_or(AddressingError::new(AddressingErrorKind::MissingFile))?;
+
+      // Data tracks after audio tracks are written in the second session of a CD-Extra.
+      let is_audio = track.data_type.is_audio();
+      let session = if !is_audio && has_audio {
+        if session_gap == 0 {
+          session_gap = ENHANCED_CD_GAP_FRAMES;
+        }
+
+        2
+      } else {
+        has_audio |= is_audio;
+        1
+      };
+
+      let pregap_silence = track.pregap.map_or(0, |v| v.as_frames() as u32);
+      let pregap_start = match track.pregap_index() {
+        Some(index) => absolute(index.file, index.timestamp)?,
+        None => absolute(start.file, start.timestamp)?,
+      };
+
+      let shift = silence + session_gap;
+      silence += pregap_silence;
+
+      tracks.push(TrackLayout {
+        track_no: track.track_no,
+        data_type: track.data_type,
+        session,
+        pregap_lba: pregap_start + shift,
+        start_lba: absolute(start.file, start.timestamp)? + shift + pregap_silence,
+        length: 0,
+        file: start.file,
+        file_offset: file_offset(cuesheet, file_sizes, track)?,
+      });
+
+      silence += track.postgap.map_or(0, |v| v.as_frames() as u32);
+    }
+
+    let lead_out = file_sectors.iter().sum::<u32>() + silence + session_gap;
+    let mut next_start = lead_out;
+    let mut next_session = tracks.last().map_or(1, |v| v.session);
+
+    for track in tracks.iter_mut().rev() {
+      // Session gap is not part of the last track of the first session.
+      if track.session != next_session {
+        next_start -= session_gap;
+      }
+
+      track.length = next_start.saturating_sub(track.start_lba);
+      next_start = track.pregap_lba;
+      next_session = track.session;
+    }
+
+    Ok(Self { tracks, lead_out })
+  }
+
+  /// Returns the disc usage for the media, lead-in included.
+  #[inline]
+  pub const fn capacity(&self, media: MediaSize) -> Capacity {
+    Capacity {
+      used: self.lead_out + LEAD_IN_FRAMES,
+      media,
+    }
+  }
+
+  #[inline]
+  pub const fn lead_out_msf(&self) -> Msf {
+    Msf::from_lba(self.lead_out)
+  }
+}
+
+impl AddressingError {
+  #[inline]
+  pub const fn new(kind: AddressingErrorKind) -> Self {
+    Self { kind }
+  }
+
+  #[inline]
+  pub const fn kind(&self) -> AddressingErrorKind {
+    self.kind
+  }
+}
+
+impl core::fmt::Display for AddressingError {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    match self.kind {
+      AddressingErrorKind::MissingFileSize(position) => f.write_fmt(format_args!(
+        "disc layout failed: missing size of file #{position}"
+      )),
+      AddressingErrorKind::InvalidFileSize(position) => f.write_fmt(format_args!(
+        "disc layout failed: file #{position} is smaller than its tracks"
+      )),
+      AddressingErrorKind::MissingFile => {
+        f.write_str("disc layout failed: index does not belong to a file")
+      }
+    }
+  }
+}
+
+impl core::error::Error for AddressingError {}
+
+/// Returns the length of each file in sectors. Byte sizes are split by the sector sizes of the
+/// tracks stored in the file.
+pub(crate) fn file_sectors(
+  cuesheet: &CueSheet,
+  file_sizes: &[FileSize],
+) -> Result<Vec<u32>, AddressingError> {
+  let mut sectors = Vec::with_capacity(cuesheet.files.len());
+
+  for position in 0..cuesheet.files.len() {
+    let size = file_sizes.get(position).ok_or(AddressingError::new(
+      AddressingErrorKind::MissingFileSize(position),
+    ))?;
+
+    let length = match size {
+      FileSize::Length(length) => length.as_frames() as u32,
+      FileSize::Bytes(bytes) => {
+        let (last_sector, last_offset, sector_size) = sector_offsets(cuesheet, position)
+          .last()
+          .unwrap_or((0, 0, DataType::Mode1_2352.sector_size() as u64));
+
+        let remaining = bytes.checked_sub(last_offset).ok_or(AddressingError::new(
+          AddressingErrorKind::InvalidFileSize(position),
+        ))?;
+
+        last_sector + (remaining / sector_size) as u32
+      }
+    };
+
+    sectors.push(length);
+  }
+
+  Ok(sectors)
+}
+
+/// Returns the byte offset of the track's INDEX 01 in a raw sector image.
+fn file_offset(
+  cuesheet: &CueSheet,
+  file_sizes: &[FileSize],
+  track: &Track,
+) -> Result<Option<u64>, AddressingError> {
+  let Some(start) = track.start_index() else {
+    return Ok(None);
+  };
+
+  let file = start
+    .file
+    .ok_or(AddressingError::new(AddressingErrorKind::MissingFile))?;
+
+  if !matches!(file_sizes.get(file), Some(FileSize::Bytes(_))) {
+    return Ok(None);
+  }
+
+  Ok(Some(byte_offset(
+    cuesheet,
+    file,
+    start.timestamp.as_frames() as u32,
+  )))
+}
+
+/// Returns the byte offset of the sector in a raw sector image. Sectors before the file's first
+/// index use the first index's sector size.
+pub(crate) fn byte_offset(cuesheet: &CueSheet, file: usize, sector: u32) -> u64 {
+  let mut offsets = sector_offsets(cuesheet, file).peekable();
+  let first_size = offsets
+    .peek()
+    .map_or(DataType::Mode1_2352.sector_size() as u64, |v| v.2);
+
+  offsets
+    .take_while(|(start, _, _)| *start <= sector)
+    .last()
+    .map_or(
+      sector as u64 * first_size,
+      |(start, offset, sector_size)| offset + (sector - start) as u64 * sector_size,
+    )
+}
+
+/// Iterates the index positions in the file as `(sector, byte offset, sector size)`. Each index
+/// uses its track's sector size until the next index.
+fn sector_offsets(cuesheet: &CueSheet, file: usize) -> impl Iterator<Item = (u32, u64, u64)> + '_ {
+  let mut prev: Option<(u32, u64, u64)> = None;
+
+  cuesheet
+    .tracks
+    .iter()
+    .flat_map(|track| {
+      track
+        .indexes
+        .iter()
+        .map(move |index| (index, track.data_type.sector_size() as u64))
+    })
+    .filter(move |(index, _)| index.file == Some(file))
+    .map(move |(index, sector_size)| {
+      let sector = index.timestamp.as_frames() as u32;
+      let offset = match prev {
+        Some((prev_sector, prev_offset, prev_size)) => {
+          prev_offset + sector.saturating_sub(prev_sector) as u64 * prev_size
+        }
+        None => sector as u64 * sector_size,
+      };
+
+      prev = Some((sector, offset, sector_size));
+      (sector, offset, sector_size)
+    })
+}
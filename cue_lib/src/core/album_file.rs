@@ -24,7 +24,7 @@ impl_enum_str!(
   ]
 );
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct AlbumFile<'a> {
   pub file_type: KnownFileType,
   pub name: CueStr<'a>,
@@ -5,27 +5,46 @@ use super::{
   timestamp::CueTimeStamp,
   track::{Track, TrackIndex},
 };
-use crate::discid::isrc::Isrc;
+use crate::discid::{isrc::Isrc, mcn::Mcn};
 
+#[cfg(feature = "alloc")]
+use alloc::string::{String, ToString};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum Command<'a> {
+  /// Track/Album arranger (CD-TEXT)
+  Arranger { value: CueStr<'a> },
+
   /// Disc's media catalog number (MCN)
-  Catalog { value: CueStr<'a> },
+  Catalog { value: Mcn },
 
   /// Specifies the name of the file that contains the encoded CD-Text information for the disc
   CdTextFile { value: CueStr<'a> },
 
+  /// Track/Album composer (CD-TEXT)
+  Composer { value: CueStr<'a> },
+
+  /// Disc identification information (CD-TEXT)
+  DiscId { value: CueStr<'a> },
+
   /// The data or audio filename
   File { value: AlbumFile<'a> },
 
   /// Special subcode flags
   Flags { value: TrackFlag },
 
+  /// Album genre (CD-TEXT)
+  Genre { value: CueStr<'a> },
+
   /// Track or subtrack index
   Index { value: TrackIndex },
 
   /// International Standard Recording Code
   ISRC { value: Isrc },
 
+  /// Track/Album message from the content provider or artist (CD-TEXT)
+  Message { value: CueStr<'a> },
+
   /// Track/Album performer
   Performer { value: CueStr<'a> },
 
@@ -46,4 +65,92 @@ pub enum Command<'a> {
 
   /// Starts new track
   Track { value: Track },
+
+  /// Album UPC/EAN code (CD-TEXT)
+  UpcEan { value: CueStr<'a> },
+
+  /// Command which is not part of the cuesheet format, such as vendor extensions. Arguments are
+  /// kept as raw text.
+  Unknown { name: &'a str, args: &'a str },
+}
+
+/// Owned version of [Command] with un-escaped and un-quoted text values.
+#[cfg(feature = "alloc")]
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum OwnedCommand {
+  Arranger { value: String },
+  Catalog { value: Mcn },
+  CdTextFile { value: String },
+  Composer { value: String },
+  DiscId { value: String },
+  File { value: crate::document::AlbumFile },
+  Flags { value: TrackFlag },
+  Genre { value: String },
+  Index { value: TrackIndex },
+  ISRC { value: Isrc },
+  Message { value: String },
+  Performer { value: String },
+  Postgap { value: CueTimeStamp },
+  Pregap { value: CueTimeStamp },
+  Remark { value: String },
+  SongWriter { value: String },
+  Title { value: String },
+  Track { value: Track },
+  UpcEan { value: String },
+  Unknown { name: String, args: String },
+}
+
+#[cfg(feature = "alloc")]
+impl From<Command<'_>> for OwnedCommand {
+  fn from(value: Command<'_>) -> Self {
+    match value {
+      Command::Arranger { value } => Self::Arranger {
+        value: value.to_string(),
+      },
+      Command::Catalog { value } => Self::Catalog { value },
+      Command::CdTextFile { value } => Self::CdTextFile {
+        value: value.to_string(),
+      },
+      Command::Composer { value } => Self::Composer {
+        value: value.to_string(),
+      },
+      Command::DiscId { value } => Self::DiscId {
+        value: value.to_string(),
+      },
+      Command::File { value } => Self::File {
+        value: value.into(),
+      },
+      Command::Flags { value } => Self::Flags { value },
+      Command::Genre { value } => Self::Genre {
+        value: value.to_string(),
+      },
+      Command::Index { value } => Self::Index { value },
+      Command::ISRC { value } => Self::ISRC { value },
+      Command::Message { value } => Self::Message {
+        value: value.to_string(),
+      },
+      Command::Performer { value } => Self::Performer {
+        value: value.to_string(),
+      },
+      Command::Postgap { value } => Self::Postgap { value },
+      Command::Pregap { value } => Self::Pregap { value },
+      Command::Remark { value } => Self::Remark {
+        value: value.to_string(),
+      },
+      Command::SongWriter { value } => Self::SongWriter {
+        value: value.to_string(),
+      },
+      Command::Title { value } => Self::Title {
+        value: value.to_string(),
+      },
+      Command::Track { value } => Self::Track { value },
+      Command::UpcEan { value } => Self::UpcEan {
+        value: value.to_string(),
+      },
+      Command::Unknown { name, args } => Self::Unknown {
+        name: name.to_string(),
+        args: args.to_string(),
+      },
+    }
+  }
 }
@@ -52,7 +52,7 @@ impl<'a> CueStr<'a> {
 
   #[inline]
   /// Returns un-escaped/quoted raw str reference.
-  pub const fn as_raw_str(&self) -> &str {
+  pub const fn as_raw_str(&self) -> &'a str {
     match self {
       Self::QuotedText(v) => v,
       Self::QuotedTextWithEscape(v) => v,
@@ -134,12 +134,12 @@ impl core::fmt::Display for CueStr<'_> {
       Self::QuotedTextWithEscape(quoted_text) => {
         let text = &quoted_text[1..(quoted_text.len() - 1)];
         let mut slice_start = 0;
-        let mut iter = text.chars().enumerate();
+        let mut iter = text.char_indices();
 
         while let Some((idx, ch)) = iter.next() {
           if ch == '\\' {
             f.write_str(&text[slice_start..idx])?;
-            slice_start = idx + 1;
+            slice_start = idx + ch.len_utf8();
             _ = iter.next();
           }
         }
@@ -170,7 +170,7 @@ mod alloc {
     fn into(self) -> Cow<'a, str> {
       match self {
         CueStr::QuotedText(v) => Cow::Borrowed(&v[1..(v.len() - 1)]),
-        CueStr::QuotedTextWithEscape(v) => Cow::Owned(v.to_string()),
+        CueStr::QuotedTextWithEscape(_) => Cow::Owned(self.to_string()),
         CueStr::Text(v) => Cow::Borrowed(v),
       }
     }
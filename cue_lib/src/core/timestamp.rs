@@ -2,9 +2,9 @@ use super::error::{TimeStampParseError, TimeStampParseErrorKind};
 use crate::internal::range::impl_numeric_range_type;
 use core::time::Duration;
 
-const SECONDS: u128 = 1000;
-const MINUTE: u128 = 60 * 1000;
-const FRAME: u128 = 1000 / 75;
+const FRAMES_PER_SECOND: u64 = 75;
+const FRAMES_PER_MINUTE: u64 = 60 * FRAMES_PER_SECOND;
+const NANOS_PER_SECOND: u128 = 1_000_000_000;
 
 /// Value between 0 and 59
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
@@ -18,81 +18,209 @@ pub struct Frame(u8);
 impl_numeric_range_type!(Second, u8, max = 59, len = 2, display_leading_zeros = 2);
 impl_numeric_range_type!(Frame, u8, max = 74, len = 2, display_leading_zeros = 2);
 
-#[derive(Clone, Copy, Debug, Eq, Hash, PartialEq)]
+/// Audio sample rates with a whole number of samples per frame.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum SampleRate {
+  Hz44100,
+  Hz48000,
+  Hz88200,
+  Hz96000,
+  Hz192000,
+}
+
+impl SampleRate {
+  #[inline]
+  pub const fn as_hz(&self) -> u32 {
+    match self {
+      SampleRate::Hz44100 => 44_100,
+      SampleRate::Hz48000 => 48_000,
+      SampleRate::Hz88200 => 88_200,
+      SampleRate::Hz96000 => 96_000,
+      SampleRate::Hz192000 => 192_000,
+    }
+  }
+
+  #[inline]
+  pub const fn samples_per_frame(&self) -> u64 {
+    self.as_hz() as u64 / FRAMES_PER_SECOND
+  }
+
+  #[inline]
+  pub const fn from_hz(value: u32) -> Option<Self> {
+    match value {
+      44_100 => Some(SampleRate::Hz44100),
+      48_000 => Some(SampleRate::Hz48000),
+      88_200 => Some(SampleRate::Hz88200),
+      96_000 => Some(SampleRate::Hz96000),
+      192_000 => Some(SampleRate::Hz192000),
+      _ => None,
+    }
+  }
+}
+
+/// Cuesheet timestamp in `mm:ss:ff` format, stored as total number of frames.
+#[derive(Clone, Copy, Debug, Default, Eq, Hash, PartialEq, PartialOrd, Ord)]
 pub struct CueTimeStamp {
-  minute: u64,
-  second: u8,
-  frame: u8,
+  frames: u64,
 }
 
 impl CueTimeStamp {
+  pub const ZERO: Self = Self { frames: 0 };
+  pub const MAX: Self = Self { frames: u64::MAX };
+
+  /// Creates the timestamp, saturating at [CueTimeStamp::MAX] for large minutes.
   pub const fn new(minute: u64, second: Second, frame: Frame) -> Self {
+    let frames = second.into_inner() as u64 * FRAMES_PER_SECOND + frame.into_inner() as u64;
+
     Self {
-      frame: frame.into_inner(),
-      minute,
-      second: second.into_inner(),
+      frames: minute
+        .saturating_mul(FRAMES_PER_MINUTE)
+        .saturating_add(frames),
     }
   }
 
+  #[inline]
+  pub const fn from_frames(frames: u64) -> Self {
+    Self { frames }
+  }
+
+  #[inline]
+  pub const fn minute(&self) -> u64 {
+    self.frames / FRAMES_PER_MINUTE
+  }
+
+  #[inline]
+  pub const fn second(&self) -> Second {
+    Second(((self.frames / FRAMES_PER_SECOND) % 60) as u8)
+  }
+
+  #[inline]
+  pub const fn frame(&self) -> Frame {
+    Frame((self.frames % FRAMES_PER_SECOND) as u8)
+  }
+
+  /// Returns the total number of frames.
+  #[inline]
+  pub const fn as_frames(&self) -> u64 {
+    self.frames
+  }
+
+  /// Returns the milliseconds, rounded down.
   #[inline]
   pub const fn as_millis(&self) -> u128 {
-    (self.second as u128) * SECONDS + (self.minute as u128) * MINUTE + (self.frame as u128) * FRAME
+    self.frames as u128 * 1000 / FRAMES_PER_SECOND as u128
+  }
+
+  /// Returns the duration, sub-nanosecond part is rounded down.
+  #[inline]
+  pub const fn as_duration(&self) -> Duration {
+    let nanos = (self.frames % FRAMES_PER_SECOND) as u128 * NANOS_PER_SECOND;
+
+    Duration::new(
+      self.frames / FRAMES_PER_SECOND,
+      (nanos / FRAMES_PER_SECOND as u128) as u32,
+    )
   }
 
+  /// Returns the sample offset of the frame, saturating at [u64::MAX].
   #[inline]
-  pub fn as_duration(&self) -> Duration {
-    Duration::from_mins(self.minute)
-      + Duration::from_secs(self.second as u64)
-      + Duration::from_millis(self.frame as u64 * FRAME as u64)
+  pub const fn as_samples(&self, sample_rate: SampleRate) -> u64 {
+    self.frames.saturating_mul(sample_rate.samples_per_frame())
   }
 
+  /// Converts milliseconds to the nearest frame.
   #[inline]
   pub const fn from_millis(value: u128) -> Self {
-    let mut remaining_ms = value;
+    Self {
+      frames: ((value * FRAMES_PER_SECOND as u128 + 500) / 1000) as u64,
+    }
+  }
 
-    let minute = remaining_ms.saturating_div(MINUTE);
-    remaining_ms = minute * MINUTE;
+  /// Returns the frame containing the sample.
+  #[inline]
+  pub const fn from_samples(samples: u64, sample_rate: SampleRate) -> Self {
+    Self {
+      frames: samples / sample_rate.samples_per_frame(),
+    }
+  }
 
-    let second = remaining_ms.saturating_div(SECONDS);
-    remaining_ms = second * SECONDS;
+  #[inline]
+  pub const fn checked_add(self, rhs: Self) -> Option<Self> {
+    match self.frames.checked_add(rhs.frames) {
+      Some(frames) => Some(Self { frames }),
+      None => None,
+    }
+  }
+
+  #[inline]
+  pub const fn checked_sub(self, rhs: Self) -> Option<Self> {
+    match self.frames.checked_sub(rhs.frames) {
+      Some(frames) => Some(Self { frames }),
+      None => None,
+    }
+  }
 
-    let frame = remaining_ms / FRAME;
+  #[inline]
+  pub const fn saturating_add(self, rhs: Self) -> Self {
+    Self {
+      frames: self.frames.saturating_add(rhs.frames),
+    }
+  }
 
+  #[inline]
+  pub const fn saturating_sub(self, rhs: Self) -> Self {
     Self {
-      minute: minute as u64,
-      second: second as u8,
-      frame: frame as u8,
+      frames: self.frames.saturating_sub(rhs.frames),
     }
   }
 }
 
-impl Ord for CueTimeStamp {
-  fn cmp(&self, other: &Self) -> core::cmp::Ordering {
-    match self.minute.cmp(&other.minute) {
-      core::cmp::Ordering::Equal => {}
-      order => return order,
-    };
+impl core::ops::Add for CueTimeStamp {
+  type Output = Self;
 
-    match self.second.cmp(&other.second) {
-      core::cmp::Ordering::Equal => {}
-      order => return order,
-    };
+  #[inline]
+  fn add(self, rhs: Self) -> Self::Output {
+    self
+      .checked_add(rhs)
+      .expect("overflow when adding timestamps")
+  }
+}
+
+impl core::ops::Sub for CueTimeStamp {
+  type Output = Self;
 
-    self.frame.cmp(&other.frame)
+  #[inline]
+  fn sub(self, rhs: Self) -> Self::Output {
+    self
+      .checked_sub(rhs)
+      .expect("overflow when subtracting timestamps")
+  }
+}
+
+impl core::ops::AddAssign for CueTimeStamp {
+  #[inline]
+  fn add_assign(&mut self, rhs: Self) {
+    *self = *self + rhs;
   }
 }
 
-impl PartialOrd for CueTimeStamp {
+impl core::ops::SubAssign for CueTimeStamp {
   #[inline]
-  fn partial_cmp(&self, other: &Self) -> Option<core::cmp::Ordering> {
-    Some(self.cmp(other))
+  fn sub_assign(&mut self, rhs: Self) {
+    *self = *self - rhs;
   }
 }
 
 impl From<Duration> for CueTimeStamp {
+  /// Converts the duration to the nearest frame.
   #[inline]
   fn from(value: Duration) -> Self {
-    Self::from_millis(value.as_millis())
+    let frames =
+      (value.as_nanos() * FRAMES_PER_SECOND as u128 + NANOS_PER_SECOND / 2) / NANOS_PER_SECOND;
+
+    Self {
+      frames: frames as u64,
+    }
   }
 }
 
@@ -101,9 +229,9 @@ impl core::fmt::Display for CueTimeStamp {
   fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
     f.write_fmt(format_args!(
       "{mm:0>2}:{ss:0>2}:{ff:0>2}",
-      mm = self.minute,
-      ss = self.second,
-      ff = self.frame
+      mm = self.minute(),
+      ss = self.second().into_inner(),
+      ff = self.frame().into_inner()
     ))
   }
 }
@@ -152,10 +280,12 @@ impl core::str::FromStr for CueTimeStamp {
     let minute = u64::from_str_radix(&s[..second_start], 10)
       .map_err(|_| TimeStampParseError::new(TimeStampParseErrorKind::InvalidMinute))?;
 
-    Ok(Self {
-      second: second.into_inner(),
-      minute,
-      frame: frame.into_inner(),
-    })
+    minute
+      .checked_mul(FRAMES_PER_MINUTE)
+      .and_then(|v| v.checked_add(Self::new(0, second, frame).frames))
+      .map(Self::from_frames)
+      .ok_or(TimeStampParseError::new(
+        TimeStampParseErrorKind::InvalidMinute,
+      ))
   }
 }
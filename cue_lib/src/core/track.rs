@@ -23,6 +23,29 @@ impl_enum_str!(
   ]
 );
 
+impl DataType {
+  /// Returns the number of bytes per sector stored in the file.
+  pub const fn sector_size(&self) -> u16 {
+    match self {
+      DataType::Audio => 2352,
+      // Audio sector with 96 bytes of subchannel data.
+      DataType::CDG => 2448,
+      DataType::Mode1_2048 => 2048,
+      DataType::Mode1_2352 => 2352,
+      DataType::Mode2_2336 => 2336,
+      DataType::Mode2_2352 => 2352,
+      DataType::CDI_2336 => 2336,
+      DataType::CDI_2352 => 2352,
+    }
+  }
+
+  /// Returns `true` for audio tracks, including CD+G.
+  #[inline]
+  pub const fn is_audio(&self) -> bool {
+    matches!(self, DataType::Audio | DataType::CDG)
+  }
+}
+
 #[derive(Copy, Clone, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, Default)]
 pub struct TrackNo(u8);
 
@@ -32,13 +55,13 @@ pub struct IndexNo(u8);
 impl_numeric_range_type!(IndexNo, u8, max = 255, len = 3, display_leading_zeros = 2);
 impl_numeric_range_type!(TrackNo, u8, max = 255, len = 3, display_leading_zeros = 2);
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct TrackIndex {
   pub index_no: IndexNo,
   pub timestamp: CueTimeStamp,
 }
 
-#[derive(Clone, Copy, Debug)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct Track {
   pub track_no: TrackNo,
   pub data_type: DataType,
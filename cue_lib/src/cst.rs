@@ -0,0 +1,528 @@
+//! Lossless concrete syntax tree.
+//!
+//! The tree keeps every token together with the whitespace, byte order mark and line endings
+//! around it, so printing an unmodified tree gives back the exact input. Edited tokens are the
+//! only parts re-printed, everything else is copied from the source buffer as is.
+
+use crate::{
+  core::{
+    cue_str::CueStr,
+    timestamp::CueTimeStamp,
+    track::{IndexNo, TrackNo},
+  },
+  internal::tokenizer::{Token, Tokenizer},
+  writer::{LineEnding, Quoted},
+};
+use alloc::{
+  string::{String, ToString},
+  vec::Vec,
+};
+use core::{fmt::Write as _, ops::Range, str::FromStr};
+
+/// Zero Width No-break Space aka Byte Order Mark
+const ZWNBP: char = '\u{feff}';
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TokenKind {
+  /// Command name, e.g. `TITLE`
+  Keyword,
+  /// Command argument, quoted or unquoted
+  Argument,
+  /// Remaining text of a `REM` line
+  Remark,
+  /// Text that could not be tokenized, kept as is
+  Error,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SyntaxToken<'a> {
+  kind: TokenKind,
+  span: Range<usize>,
+  text: &'a str,
+  replacement: Option<String>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SyntaxLine<'a> {
+  /// Line content without the line ending.
+  span: Range<usize>,
+  content: &'a str,
+  line_ending: Option<LineEnding>,
+  tokens: Vec<SyntaxToken<'a>>,
+  inserted: Option<String>,
+}
+
+/// Lines of a single track, starting with its `TRACK` command.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct TrackNode {
+  pub track_no: Option<TrackNo>,
+  pub lines: Range<usize>,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SyntaxTree<'a> {
+  source: &'a str,
+  has_bom: bool,
+  lines: Vec<SyntaxLine<'a>>,
+  tracks: Vec<TrackNode>,
+}
+
+impl<'a> SyntaxToken<'a> {
+  #[inline]
+  pub const fn kind(&self) -> TokenKind {
+    self.kind
+  }
+
+  /// Byte range of the token in the source buffer.
+  #[inline]
+  pub fn span(&self) -> Range<usize> {
+    self.span.clone()
+  }
+
+  /// Returns the current token text, including quotes and escape sequences.
+  #[inline]
+  pub fn text(&self) -> &str {
+    self.replacement.as_deref().unwrap_or(self.text)
+  }
+
+  /// Returns the token text from the source buffer, ignoring edits.
+  #[inline]
+  pub const fn source_text(&self) -> &'a str {
+    self.text
+  }
+
+  #[inline]
+  pub const fn is_edited(&self) -> bool {
+    self.replacement.is_some()
+  }
+
+  /// Replaces the token text. The value is written as is, quoting is up to the caller.
+  #[inline]
+  pub fn set_text(&mut self, value: String) {
+    self.replacement = Some(value);
+  }
+}
+
+impl<'a> SyntaxLine<'a> {
+  /// Byte range of the line content in the source buffer, excluding the line ending.
+  #[inline]
+  pub fn span(&self) -> Range<usize> {
+    self.span.clone()
+  }
+
+  #[inline]
+  pub const fn line_ending(&self) -> Option<LineEnding> {
+    self.line_ending
+  }
+
+  #[inline]
+  pub fn tokens(&self) -> &[SyntaxToken<'a>] {
+    &self.tokens
+  }
+
+  #[inline]
+  pub fn tokens_mut(&mut self) -> &mut [SyntaxToken<'a>] {
+    &mut self.tokens
+  }
+
+  /// Returns the command name if the line has one.
+  pub fn keyword(&self) -> Option<&str> {
+    match self.tokens.first() {
+      Some(token) if token.kind == TokenKind::Keyword => Some(token.text()),
+      _ => None,
+    }
+  }
+
+  /// Returns the tokens after the command name.
+  #[inline]
+  pub fn arguments(&self) -> &[SyntaxToken<'a>] {
+    match self.keyword() {
+      Some(_) => &self.tokens[1..],
+      None => &[],
+    }
+  }
+
+  /// Whitespace before the first token.
+  pub fn indent(&self) -> &'a str {
+    match self.tokens.first() {
+      Some(token) => &self.content[..(token.span.start - self.span.start)],
+      None => self.content,
+    }
+  }
+
+  /// Whitespace after the last token.
+  pub fn trailing_trivia(&self) -> &'a str {
+    match self.tokens.last() {
+      Some(token) => &self.content[(token.span.end - self.span.start)..],
+      None => "",
+    }
+  }
+
+  /// Replaces the argument at the given position, returns `false` if it does not exist.
+  pub fn set_argument(&mut self, position: usize, value: String) -> bool {
+    if self.keyword().is_none() {
+      return false;
+    }
+
+    match self.tokens.get_mut(position + 1) {
+      Some(token) if token.kind == TokenKind::Argument => {
+        token.set_text(value);
+        true
+      }
+      _ => false,
+    }
+  }
+
+  #[inline]
+  pub const fn is_inserted(&self) -> bool {
+    self.inserted.is_some()
+  }
+
+  #[inline]
+  pub fn is_edited(&self) -> bool {
+    self.inserted.is_some() || self.tokens.iter().any(|v| v.is_edited())
+  }
+
+  fn is_command(&self, name: &str) -> bool {
+    self.keyword().is_some_and(|v| v.eq_ignore_ascii_case(name))
+  }
+
+  fn parse(source: &'a str, span: Range<usize>, line_ending: Option<LineEnding>) -> Self {
+    let content = &source[span.clone()];
+    let mut tokens = Vec::new();
+    let mut tokenizer = Tokenizer::new(content);
+
+    macro_rules! token {
+      ($kind:expr, $text:expr) => {{
+        let text: &'a str = $text;
+        let start = span.start + (text.as_ptr() as usize - content.as_ptr() as usize);
+
+        SyntaxToken {
+          kind: $kind,
+          span: start..(start + text.len()),
+          text,
+          replacement: None,
+        }
+      }};
+    }
+
+    loop {
+      let cursor = tokenizer.cursor_position();
+
+      match tokenizer.next_token() {
+        Ok(Some(Token::Text { value })) => {
+          let raw = value.as_raw_str();
+
+          match value {
+            CueStr::Text(keyword) if tokens.is_empty() => {
+              tokens.push(token!(TokenKind::Keyword, raw));
+
+              if keyword.eq_ignore_ascii_case("REM") {
+                let remark = content[tokenizer.cursor_position()..].trim_end();
+
+                if !remark.is_empty() {
+                  tokens.push(token!(TokenKind::Remark, remark));
+                }
+
+                break;
+              }
+            }
+            _ => tokens.push(token!(TokenKind::Argument, raw)),
+          }
+        }
+        Ok(Some(Token::LF)) | Ok(None) => break,
+        Err(_) => {
+          let rest = content[cursor..].trim_matches(|v: char| v.is_whitespace() || v == ZWNBP);
+
+          if !rest.is_empty() {
+            tokens.push(token!(TokenKind::Error, rest));
+          }
+
+          break;
+        }
+      }
+    }
+
+    Self {
+      span,
+      content,
+      line_ending,
+      tokens,
+      inserted: None,
+    }
+  }
+
+  fn write<W>(&self, source: &str, out: &mut W) -> core::fmt::Result
+  where
+    W: core::fmt::Write,
+  {
+    match self.inserted.as_deref() {
+      Some(text) => out.write_str(text)?,
+      None => {
+        let mut cursor = self.span.start;
+
+        for token in self.tokens.iter() {
+          out.write_str(&source[cursor..token.span.start])?;
+          out.write_str(token.text())?;
+          cursor = token.span.end;
+        }
+
+        out.write_str(&source[cursor..self.span.end])?;
+      }
+    }
+
+    match self.line_ending {
+      Some(LineEnding::LF) => out.write_str("\n"),
+      Some(LineEnding::CRLF) => out.write_str("\r\n"),
+      None => Ok(()),
+    }
+  }
+}
+
+impl<'a> SyntaxTree<'a> {
+  pub fn parse(source: &'a str) -> Self {
+    let has_bom = source.starts_with(ZWNBP);
+    let mut cursor = if has_bom { ZWNBP.len_utf8() } else { 0 };
+    let mut lines = Vec::new();
+
+    while cursor < source.len() {
+      let (span, line_ending, next) = match source[cursor..].find('\n') {
+        Some(offset) => {
+          let lf = cursor + offset;
+
+          if lf > cursor && source.as_bytes()[lf - 1] == b'\r' {
+            (cursor..(lf - 1), Some(LineEnding::CRLF), lf + 1)
+          } else {
+            (cursor..lf, Some(LineEnding::LF), lf + 1)
+          }
+        }
+        None => (cursor..source.len(), None, source.len()),
+      };
+
+      lines.push(SyntaxLine::parse(source, span, line_ending));
+      cursor = next;
+    }
+
+    let mut tree = Self {
+      source,
+      has_bom,
+      lines,
+      tracks: Vec::new(),
+    };
+
+    tree.update_tracks();
+    tree
+  }
+
+  #[inline]
+  pub const fn source(&self) -> &'a str {
+    self.source
+  }
+
+  #[inline]
+  pub const fn has_bom(&self) -> bool {
+    self.has_bom
+  }
+
+  #[inline]
+  pub fn lines(&self) -> &[SyntaxLine<'a>] {
+    &self.lines
+  }
+
+  #[inline]
+  pub fn lines_mut(&mut self) -> &mut [SyntaxLine<'a>] {
+    &mut self.lines
+  }
+
+  /// Lines before the first `TRACK` command.
+  #[inline]
+  pub fn album_lines(&self) -> Range<usize> {
+    0..self
+      .tracks
+      .first()
+      .map_or(self.lines.len(), |v| v.lines.start)
+  }
+
+  #[inline]
+  pub fn tracks(&self) -> &[TrackNode] {
+    &self.tracks
+  }
+
+  /// Finds the line range of the track, or the album section when `track_no` is `None`.
+  pub fn section(&self, track_no: Option<TrackNo>) -> Option<Range<usize>> {
+    match track_no {
+      Some(track_no) => self
+        .tracks
+        .iter()
+        .find(|v| v.track_no == Some(track_no))
+        .map(|v| v.lines.clone()),
+      None => Some(self.album_lines()),
+    }
+  }
+
+  /// Finds the first command line with the given name in the section.
+  pub fn find_command(&self, track_no: Option<TrackNo>, name: &str) -> Option<usize> {
+    self
+      .section(track_no)?
+      .find(|idx| self.lines[*idx].is_command(name))
+  }
+
+  /// Sets the album (`None`) or track title. A new `TITLE` line is inserted when the section
+  /// does not have one.
+  pub fn set_title(&mut self, track_no: Option<TrackNo>, title: &str) -> bool {
+    self.set_text_command(track_no, "TITLE", title)
+  }
+
+  /// Sets the album (`None`) or track performer. A new `PERFORMER` line is inserted when the
+  /// section does not have one.
+  pub fn set_performer(&mut self, track_no: Option<TrackNo>, performer: &str) -> bool {
+    self.set_text_command(track_no, "PERFORMER", performer)
+  }
+
+  /// Changes the timestamp of an existing `INDEX` command.
+  pub fn set_index_timestamp(
+    &mut self,
+    track_no: TrackNo,
+    index_no: IndexNo,
+    timestamp: CueTimeStamp,
+  ) -> bool {
+    let Some(section) = self.section(Some(track_no)) else {
+      return false;
+    };
+
+    for idx in section {
+      let line = &mut self.lines[idx];
+
+      if line.is_command("INDEX")
+        && line
+          .arguments()
+          .first()
+          .is_some_and(|v| IndexNo::from_str(v.text()).is_ok_and(|v| v == index_no))
+      {
+        return line.set_argument(1, timestamp.to_string());
+      }
+    }
+
+    false
+  }
+
+  /// Inserts a new line after the given line. Indentation and line ending are copied from it.
+  pub fn insert_line_after(&mut self, line_idx: usize, text: &str) -> bool {
+    match self.lines.get(line_idx) {
+      Some(line) => {
+        let indent = line.indent();
+        self.insert_line(line_idx + 1, indent, text);
+        true
+      }
+      None => false,
+    }
+  }
+
+  fn insert_line(&mut self, position: usize, indent: &str, text: &str) {
+    let line_ending = self
+      .lines
+      .iter()
+      .find_map(|v| v.line_ending)
+      .unwrap_or(LineEnding::LF);
+
+    // The previous last line has no ending, it needs one before the inserted line.
+    let is_last = position >= self.lines.len();
+    if is_last && let Some(prev) = self.lines.last_mut() {
+      prev.line_ending.get_or_insert(line_ending);
+    }
+
+    let mut inserted = String::with_capacity(indent.len() + text.len());
+    inserted.push_str(indent);
+    inserted.push_str(text);
+
+    let offset = match self.lines.get(position) {
+      Some(line) => line.span.start,
+      None => self.source.len(),
+    };
+
+    let new_line = SyntaxLine {
+      span: offset..offset,
+      content: "",
+      line_ending: if is_last { None } else { Some(line_ending) },
+      tokens: Vec::new(),
+      inserted: Some(inserted),
+    };
+
+    self.lines.insert(position.min(self.lines.len()), new_line);
+    self.update_tracks();
+  }
+
+  fn set_text_command(&mut self, track_no: Option<TrackNo>, name: &str, value: &str) -> bool {
+    let quoted = Quoted(value).to_string();
+
+    if let Some(idx) = self.find_command(track_no, name) {
+      return self.lines[idx].set_argument(0, quoted);
+    }
+
+    let Some(section) = self.section(track_no) else {
+      return false;
+    };
+
+    let text = alloc::format!("{name} {quoted}");
+
+    match track_no {
+      // Track commands go right after the TRACK line, using the indentation of the next line.
+      Some(_) => {
+        let track_line = &self.lines[section.start];
+        let indent: String = match self.lines.get(section.start + 1) {
+          Some(next) if section.start + 1 < section.end => next.indent().into(),
+          _ => alloc::format!("{}  ", track_line.indent()),
+        };
+
+        self.insert_line(section.start + 1, &indent, &text);
+      }
+      // Album commands go before the first FILE command, or to the end of album section.
+      None => {
+        let position = section
+          .clone()
+          .find(|idx| self.lines[*idx].is_command("FILE"))
+          .unwrap_or(section.end);
+
+        self.insert_line(position, "", &text);
+      }
+    }
+
+    true
+  }
+
+  fn update_tracks(&mut self) {
+    self.tracks.clear();
+
+    for (idx, line) in self.lines.iter().enumerate() {
+      if line.is_command("TRACK") {
+        if let Some(prev) = self.tracks.last_mut() {
+          prev.lines.end = idx;
+        }
+
+        let track_no = line
+          .arguments()
+          .first()
+          .and_then(|v| TrackNo::from_str(v.text()).ok());
+
+        self.tracks.push(TrackNode {
+          track_no,
+          lines: idx..self.lines.len(),
+        });
+      }
+    }
+  }
+}
+
+impl core::fmt::Display for SyntaxTree<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    if self.has_bom {
+      f.write_char(ZWNBP)?;
+    }
+
+    for line in self.lines.iter() {
+      line.write(self.source, f)?;
+    }
+
+    Ok(())
+  }
+}
@@ -1,6 +1,12 @@
 mod checksum;
 
+pub mod accuraterip;
+pub mod cddb;
+pub mod ctdb;
 pub mod ean;
 pub mod error;
 pub mod isrc;
+pub mod mcn;
+pub mod musicbrainz;
+pub mod toc;
 pub mod upc;
@@ -0,0 +1,55 @@
+use super::cddb::CddbDiscId;
+
+/// AccurateRip disc id triple.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct AccurateRipId {
+  audio_track_count: u8,
+  id1: u32,
+  id2: u32,
+  cddb_id: CddbDiscId,
+}
+
+impl AccurateRipId {
+  #[inline]
+  pub const fn new(audio_track_count: u8, id1: u32, id2: u32, cddb_id: CddbDiscId) -> Self {
+    Self {
+      audio_track_count,
+      id1,
+      id2,
+      cddb_id,
+    }
+  }
+
+  #[inline]
+  pub const fn audio_track_count(&self) -> u8 {
+    self.audio_track_count
+  }
+
+  /// Sum of the audio track offsets and the lead-out.
+  #[inline]
+  pub const fn id1(&self) -> u32 {
+    self.id1
+  }
+
+  /// Sum of the audio track offsets multiplied by their position, including the lead-out.
+  #[inline]
+  pub const fn id2(&self) -> u32 {
+    self.id2
+  }
+
+  #[inline]
+  pub const fn cddb_id(&self) -> CddbDiscId {
+    self.cddb_id
+  }
+}
+
+impl core::fmt::Display for AccurateRipId {
+  /// Writes the id in `dBAR` file name format, `NNN-xxxxxxxx-xxxxxxxx-xxxxxxxx`.
+  #[inline]
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    f.write_fmt(format_args!(
+      "{:03}-{:08x}-{:08x}-{}",
+      self.audio_track_count, self.id1, self.id2, self.cddb_id
+    ))
+  }
+}
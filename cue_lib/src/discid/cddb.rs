@@ -0,0 +1,40 @@
+/// FreeDB/CDDB disc id.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CddbDiscId(u32);
+
+impl CddbDiscId {
+  #[inline]
+  pub const fn new(value: u32) -> Self {
+    Self(value)
+  }
+
+  #[inline]
+  pub const fn into_inner(self) -> u32 {
+    self.0
+  }
+
+  /// Returns the digit sum checksum of the track offsets.
+  #[inline]
+  pub const fn checksum(&self) -> u8 {
+    (self.0 >> 24) as u8
+  }
+
+  /// Returns the disc length in seconds.
+  #[inline]
+  pub const fn length(&self) -> u16 {
+    (self.0 >> 8) as u16
+  }
+
+  #[inline]
+  pub const fn track_count(&self) -> u8 {
+    self.0 as u8
+  }
+}
+
+impl core::fmt::Display for CddbDiscId {
+  /// Writes the id as eight lowercase hex digits.
+  #[inline]
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    f.write_fmt(format_args!("{:08x}", self.0))
+  }
+}
@@ -0,0 +1,51 @@
+use super::musicbrainz::{HexBuffer, encode_base64};
+use crate::internal::sha1::Sha1;
+use core::fmt::Write;
+
+/// CUETools DB TOC id, 28 characters of base64 encoded SHA-1 digest.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct CtdbTocId([u8; 28]);
+
+impl CtdbTocId {
+  /// Computes the id from the audio track offsets relative to the first audio track and the
+  /// audio session length.
+  pub fn new(relative_offsets: &[u32], length: u32) -> Self {
+    let mut sha1 = Sha1::new();
+    let mut buffer = HexBuffer::default();
+
+    // Offsets of the second and later tracks, then the length and zero padding up to 100 entries.
+    let values = relative_offsets
+      .iter()
+      .skip(1)
+      .copied()
+      .chain(core::iter::once(length))
+      .chain(core::iter::repeat(0));
+
+    for value in values.take(100) {
+      buffer.clear();
+      _ = write!(buffer, "{value:08X}");
+      sha1.update(buffer.as_bytes());
+    }
+
+    Self(encode_base64(&sha1.finalize()))
+  }
+
+  #[inline]
+  pub fn as_str(&self) -> &str {
+    // SAFETY: Base64 alphabet is always ASCII.
+    unsafe { core::str::from_utf8_unchecked(&self.0) }
+  }
+}
+
+impl core::fmt::Display for CtdbTocId {
+  #[inline]
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    f.write_str(self.as_str())
+  }
+}
+
+impl core::fmt::Debug for CtdbTocId {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    f.debug_tuple("CtdbTocId").field(&self.as_str()).finish()
+  }
+}
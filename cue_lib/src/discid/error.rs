@@ -1,3 +1,5 @@
+use crate::error::CueLibError;
+
 /// Represents an error when parsing an ISRC string.
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub struct IsrcParseError {
@@ -144,3 +146,142 @@ impl core::fmt::Display for UpcParseError {
 }
 
 impl core::error::Error for UpcParseError {}
+
+/// Represents an error when parsing a media catalog number.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct McnParseError {
+  kind: McnParseErrorKind,
+}
+
+impl McnParseError {
+  #[inline]
+  pub const fn new(kind: McnParseErrorKind) -> Self {
+    Self { kind }
+  }
+
+  #[inline]
+  pub const fn kind(&self) -> McnParseErrorKind {
+    self.kind
+  }
+}
+
+/// Kinds of errors that can occur while parsing a media catalog number.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum McnParseErrorKind {
+  /// The MCN string contains an invalid character.
+  InvalidCharacter,
+  /// The MCN string is neither 13 digit EAN nor 12 digit UPC-A.
+  InvalidLength,
+  /// The MCN string failed checksum validation.
+  ChecksumFail,
+}
+
+impl core::fmt::Display for McnParseErrorKind {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    match self {
+      McnParseErrorKind::InvalidCharacter => f.write_str("MCN string contains invalid character"),
+      McnParseErrorKind::InvalidLength => f.write_str("MCN string has invalid length"),
+      McnParseErrorKind::ChecksumFail => f.write_str("MCN string failed checksum validation"),
+    }
+  }
+}
+
+impl core::fmt::Display for McnParseError {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    f.write_fmt(format_args!("invalid media catalog number: {}", self.kind))
+  }
+}
+
+impl core::error::Error for McnParseError {}
+
+impl From<EanParseError> for McnParseError {
+  #[inline]
+  fn from(value: EanParseError) -> Self {
+    let kind = match value.kind() {
+      EanParseErrorKind::InvalidCharacter => McnParseErrorKind::InvalidCharacter,
+      EanParseErrorKind::InvalidLength => McnParseErrorKind::InvalidLength,
+      EanParseErrorKind::ChecksumFail => McnParseErrorKind::ChecksumFail,
+    };
+
+    Self::new(kind)
+  }
+}
+
+impl From<UpcParseError> for McnParseError {
+  #[inline]
+  fn from(value: UpcParseError) -> Self {
+    let kind = match value.kind() {
+      UpcParseErrorKind::InvalidCharacter => McnParseErrorKind::InvalidCharacter,
+      UpcParseErrorKind::InvalidLength => McnParseErrorKind::InvalidLength,
+      UpcParseErrorKind::ChecksumFail => McnParseErrorKind::ChecksumFail,
+    };
+
+    Self::new(kind)
+  }
+}
+
+/// Represents an error when building a disc table of contents.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TocError {
+  kind: TocErrorKind,
+}
+
+impl TocError {
+  #[inline]
+  pub const fn new(kind: TocErrorKind) -> Self {
+    Self { kind }
+  }
+
+  #[inline]
+  pub const fn kind(&self) -> TocErrorKind {
+    self.kind
+  }
+}
+
+/// Kinds of errors that can occur while building a disc table of contents.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum TocErrorKind {
+  /// Cuesheet could not be parsed.
+  CueLibError(CueLibError),
+  /// Disc has no tracks or more than 99 tracks.
+  InvalidTrackCount,
+  /// Track numbers are outside of 1 to 99.
+  InvalidTrackNo,
+  /// Track offsets are not in ascending order.
+  InvalidOffset,
+  /// Lead-out is not after the last track.
+  InvalidLeadOut,
+  /// Tracks are in multiple files, file lengths are needed for the offsets.
+  MultipleFiles,
+  /// Number of file lengths does not match the `FILE` commands.
+  InvalidFileCount,
+}
+
+impl core::fmt::Display for TocErrorKind {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    match self {
+      TocErrorKind::CueLibError(err) => err.fmt(f),
+      TocErrorKind::InvalidTrackCount => f.write_str("disc must have between 1 and 99 tracks"),
+      TocErrorKind::InvalidTrackNo => f.write_str("track numbers must be between 1 and 99"),
+      TocErrorKind::InvalidOffset => f.write_str("track offsets must be in ascending order"),
+      TocErrorKind::InvalidLeadOut => f.write_str("lead-out must be after the last track"),
+      TocErrorKind::MultipleFiles => f.write_str("tracks must be in a single file"),
+      TocErrorKind::InvalidFileCount => f.write_str("file lengths do not match the files"),
+    }
+  }
+}
+
+impl core::fmt::Display for TocError {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    f.write_fmt(format_args!("invalid table of contents: {}", self.kind))
+  }
+}
+
+impl core::error::Error for TocError {}
+
+impl From<CueLibError> for TocError {
+  #[inline]
+  fn from(value: CueLibError) -> Self {
+    Self::new(TocErrorKind::CueLibError(value))
+  }
+}
@@ -0,0 +1,92 @@
+use super::{
+  ean::Ean13,
+  error::{McnParseError, McnParseErrorKind},
+  upc::UpcA,
+};
+use crate::core::digit::Digits;
+use core::str::FromStr;
+
+/// Media catalog number (CATALOG command).
+///
+/// Accepts 13 digit EAN and 12 digit UPC-A codes. UPC-A codes are stored in their zero-padded
+/// EAN-13 form, so both `724384960650` and `0724384960650` are the same MCN.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Mcn {
+  code: Ean13,
+}
+
+impl Mcn {
+  #[inline]
+  pub const fn new(code: Ean13) -> Self {
+    Self { code }
+  }
+
+  /// Returns the normalized 13 digit EAN code.
+  #[inline]
+  pub const fn as_ean_13(&self) -> Ean13 {
+    self.code
+  }
+
+  /// Returns the UPC-A code, if the EAN code is a zero-padded UPC-A.
+  pub fn to_upc_a(&self) -> Option<UpcA> {
+    let bytes = self.code.as_bytes();
+
+    if bytes[0] == 0 {
+      Digits::<11>::new(bytes[1..12].try_into().expect("UPC-A part never panics")).map(UpcA::new)
+    } else {
+      None
+    }
+  }
+
+  #[inline]
+  pub fn as_ascii_bytes(&self) -> [u8; 13] {
+    self.code.as_ascii_bytes()
+  }
+}
+
+impl FromStr for Mcn {
+  type Err = McnParseError;
+
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    match s.len() {
+      13 => Ok(Self::new(Ean13::from_str(s)?)),
+      12 => Ok(Self::from(UpcA::from_str(s)?)),
+      _ => Err(McnParseError::new(McnParseErrorKind::InvalidLength)),
+    }
+  }
+}
+
+impl From<Ean13> for Mcn {
+  #[inline]
+  fn from(value: Ean13) -> Self {
+    Self::new(value)
+  }
+}
+
+impl From<UpcA> for Mcn {
+  fn from(value: UpcA) -> Self {
+    let upc = value.as_bytes();
+    let mut code = [0u8; 12];
+    code[1..].copy_from_slice(&upc[..11]);
+
+    // EAN-13 checksum of the zero-padded code is the same as the UPC-A checksum.
+    Self::new(Ean13::new(
+      Digits::new(&code).expect("UPC-A digits are always valid"),
+    ))
+  }
+}
+
+impl core::fmt::Display for Mcn {
+  #[inline]
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    self.code.fmt(f)
+  }
+}
+
+impl core::fmt::Debug for Mcn {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    f.debug_tuple("Mcn")
+      .field(&format_args!("{}", self.code))
+      .finish()
+  }
+}
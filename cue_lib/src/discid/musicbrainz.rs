@@ -0,0 +1,175 @@
+use crate::internal::sha1::Sha1;
+use core::fmt::Write;
+
+/// Base64 alphabet used by MusicBrainz and CTDB, `+`, `/` and `=` are replaced with URL safe characters.
+const ALPHABET: &[u8; 64] = b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789._";
+const PADDING: u8 = b'-';
+
+/// MusicBrainz disc id, 28 characters of base64 encoded SHA-1 digest.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct MusicBrainzDiscId([u8; 28]);
+
+/// Disc TOC in MusicBrainz format, offsets include the 150 frames lead-in.
+///
+/// Displayed as space separated `first last lead-out offset...` values, which is the `toc`
+/// lookup parameter after replacing spaces with `+`.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct MusicBrainzToc {
+  first_track: u8,
+  last_track: u8,
+  lead_out: u32,
+  offsets: [u32; 99],
+}
+
+impl MusicBrainzToc {
+  #[inline]
+  pub(super) const fn new(
+    first_track: u8,
+    last_track: u8,
+    lead_out: u32,
+    offsets: [u32; 99],
+  ) -> Self {
+    Self {
+      first_track,
+      last_track,
+      lead_out,
+      offsets,
+    }
+  }
+
+  #[inline]
+  pub const fn first_track(&self) -> u8 {
+    self.first_track
+  }
+
+  #[inline]
+  pub const fn last_track(&self) -> u8 {
+    self.last_track
+  }
+
+  #[inline]
+  pub const fn lead_out(&self) -> u32 {
+    self.lead_out
+  }
+
+  #[inline]
+  pub fn offsets(&self) -> &[u32] {
+    &self.offsets[..(self.last_track - self.first_track + 1) as usize]
+  }
+}
+
+impl MusicBrainzDiscId {
+  pub fn new(toc: &MusicBrainzToc) -> Self {
+    let mut sha1 = Sha1::new();
+    let mut buffer = HexBuffer::default();
+
+    _ = write!(
+      buffer,
+      "{:02X}{:02X}{:08X}",
+      toc.first_track, toc.last_track, toc.lead_out
+    );
+    sha1.update(buffer.as_bytes());
+
+    // Offsets are always hashed as 99 entries, missing tracks are zero.
+    for track_no in 1..=99u8 {
+      let offset = track_no
+        .checked_sub(toc.first_track)
+        .and_then(|idx| toc.offsets().get(idx as usize))
+        .copied()
+        .unwrap_or(0);
+
+      buffer.clear();
+      _ = write!(buffer, "{offset:08X}");
+      sha1.update(buffer.as_bytes());
+    }
+
+    Self(encode_base64(&sha1.finalize()))
+  }
+
+  #[inline]
+  pub fn as_str(&self) -> &str {
+    // SAFETY: Base64 alphabet is always ASCII.
+    unsafe { core::str::from_utf8_unchecked(&self.0) }
+  }
+}
+
+impl core::fmt::Display for MusicBrainzDiscId {
+  #[inline]
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    f.write_str(self.as_str())
+  }
+}
+
+impl core::fmt::Debug for MusicBrainzDiscId {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    f.debug_tuple("MusicBrainzDiscId")
+      .field(&self.as_str())
+      .finish()
+  }
+}
+
+impl core::fmt::Display for MusicBrainzToc {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    f.write_fmt(format_args!(
+      "{} {} {}",
+      self.first_track, self.last_track, self.lead_out
+    ))?;
+
+    for offset in self.offsets() {
+      f.write_fmt(format_args!(" {offset}"))?;
+    }
+
+    Ok(())
+  }
+}
+
+/// Fixed buffer for the hex encoded TOC values.
+#[derive(Default)]
+pub(super) struct HexBuffer {
+  bytes: [u8; 12],
+  len: usize,
+}
+
+impl HexBuffer {
+  #[inline]
+  pub(super) fn as_bytes(&self) -> &[u8] {
+    &self.bytes[..self.len]
+  }
+
+  #[inline]
+  pub(super) fn clear(&mut self) {
+    self.len = 0;
+  }
+}
+
+impl Write for HexBuffer {
+  fn write_str(&mut self, s: &str) -> core::fmt::Result {
+    let end = self.len + s.len();
+
+    match self.bytes.get_mut(self.len..end) {
+      Some(target) => {
+        target.copy_from_slice(s.as_bytes());
+        self.len = end;
+        Ok(())
+      }
+      None => Err(core::fmt::Error),
+    }
+  }
+}
+
+pub(super) fn encode_base64(digest: &[u8; 20]) -> [u8; 28] {
+  let mut output = [PADDING; 28];
+
+  for (chunk, target) in digest.chunks(3).zip(output.chunks_mut(4)) {
+    let value = (chunk[0] as u32) << 16
+      | (*chunk.get(1).unwrap_or(&0) as u32) << 8
+      | *chunk.get(2).unwrap_or(&0) as u32;
+
+    // A partial chunk of N bytes gives N + 1 characters, the rest is padding.
+    for (idx, ch) in target.iter_mut().take(chunk.len() + 1).enumerate() {
+      *ch = ALPHABET[((value >> (18 - idx * 6)) & 0x3F) as usize];
+    }
+  }
+
+  output
+}
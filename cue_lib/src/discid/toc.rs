@@ -0,0 +1,296 @@
+use super::{
+  accuraterip::AccurateRipId,
+  cddb::CddbDiscId,
+  ctdb::CtdbTocId,
+  error::{TocError, TocErrorKind},
+  musicbrainz::{MusicBrainzDiscId, MusicBrainzToc},
+};
+use crate::probe::CueSheetProbe;
+
+/// Number of frames before the first track's LBA 0 (2 seconds).
+pub const LEAD_IN_FRAMES: u32 = 150;
+
+/// Gap between the audio session's lead-out and the data track of an Enhanced CD
+/// (lead-out 6750 + lead-in 4500 + pregap 150 frames).
+pub const ENHANCED_CD_GAP_FRAMES: u32 = 11400;
+
+/// Samples per frame for 44.1kHz audio.
+pub const SAMPLES_PER_FRAME: u64 = 588;
+
+/// End of the disc's last track.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LeadOut {
+  /// Length in CD frames (1/75 second).
+  Frames(u32),
+
+  /// Length in 44.1kHz samples, partial frames are rounded up.
+  Samples(u64),
+}
+
+/// Track entry of a [Toc].
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct TocTrack {
+  /// Start of the track (INDEX 01) as LBA, without the 150 frames lead-in.
+  pub offset: u32,
+  pub is_data: bool,
+}
+
+/// Disc table of contents.
+///
+/// Offsets are stored as LBA, the 150 frames lead-in is added when computing disc ids.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Toc {
+  first_track: u8,
+  track_count: u8,
+  tracks: [TocTrack; 99],
+  lead_out: u32,
+}
+
+impl LeadOut {
+  #[inline]
+  pub const fn as_frames(&self) -> u32 {
+    match self {
+      LeadOut::Frames(frames) => *frames,
+      LeadOut::Samples(samples) => samples.div_ceil(SAMPLES_PER_FRAME) as u32,
+    }
+  }
+}
+
+impl Toc {
+  /// Creates a TOC from the tracks in order, `lead_out` is the LBA after the last track.
+  pub fn new(first_track: u8, tracks: &[TocTrack], lead_out: u32) -> Result<Self, TocError> {
+    if tracks.is_empty() || tracks.len() > 99 {
+      return Err(TocError::new(TocErrorKind::InvalidTrackCount));
+    }
+
+    if first_track == 0 || (first_track as usize + tracks.len() - 1) > 99 {
+      return Err(TocError::new(TocErrorKind::InvalidTrackNo));
+    }
+
+    if tracks.windows(2).any(|v| v[0].offset >= v[1].offset) {
+      return Err(TocError::new(TocErrorKind::InvalidOffset));
+    }
+
+    if tracks.last().is_some_and(|v| v.offset >= lead_out) {
+      return Err(TocError::new(TocErrorKind::InvalidLeadOut));
+    }
+
+    let mut toc = Self {
+      first_track,
+      track_count: tracks.len() as u8,
+      tracks: [TocTrack::default(); 99],
+      lead_out,
+    };
+
+    toc.tracks[..tracks.len()].copy_from_slice(tracks);
+    Ok(toc)
+  }
+
+  /// Reads the track offsets from a single file cuesheet.
+  ///
+  /// `lead_out` is the length of the file. `PREGAP` lengths are added to the offsets since they
+  /// are not part of the file.
+  pub fn from_probe(probe: &CueSheetProbe<'_>, lead_out: LeadOut) -> Result<Self, TocError> {
+    Self::from_probe_files(probe, &[lead_out]).map_err(|err| match err.kind() {
+      TocErrorKind::InvalidFileCount => TocError::new(TocErrorKind::MultipleFiles),
+      _ => err,
+    })
+  }
+
+  /// Reads the track offsets from a cuesheet, `file_lengths` are the lengths of each `FILE` in
+  /// order. Each file starts where the previous one ends.
+  pub fn from_probe_files(
+    probe: &CueSheetProbe<'_>,
+    file_lengths: &[LeadOut],
+  ) -> Result<Self, TocError> {
+    let mut tracks = [TocTrack::default(); 99];
+    let mut track_count = 0;
+    let mut first_track = None;
+    let mut current_file = None;
+    let mut file_idx = 0;
+    let mut file_start = 0;
+    let mut pregap_total = 0;
+    let mut iter = probe.tracks();
+
+    while let Some(track) = iter.next_track()? {
+      if track_count == tracks.len() {
+        return Err(TocError::new(TocErrorKind::InvalidTrackCount));
+      }
+
+      match current_file {
+        None => current_file = Some(track.file()),
+        Some(file) if file != track.file() => {
+          file_start += file_lengths
+            .get(file_idx)
+            .ok_or(TocError::new(TocErrorKind::InvalidFileCount))?
+            .as_frames();
+          file_idx += 1;
+          current_file = Some(track.file());
+        }
+        Some(_) => {}
+      }
+
+      first_track.get_or_insert(track.track_no().into_inner());
+      pregap_total += track.pregap().map_or(0, |v| v.as_frames() as u32);
+
+      tracks[track_count] = TocTrack {
+        offset: file_start + track.start_index().as_frames() as u32 + pregap_total,
+        is_data: !track.track_data_type().is_audio(),
+      };
+      track_count += 1;
+    }
+
+    let first_track = first_track.ok_or(TocError::new(TocErrorKind::InvalidTrackCount))?;
+    let last_length = match file_lengths.get(file_idx) {
+      Some(length) if file_idx + 1 == file_lengths.len() => length.as_frames(),
+      _ => return Err(TocError::new(TocErrorKind::InvalidFileCount)),
+    };
+
+    Self::new(
+      first_track,
+      &tracks[..track_count],
+      file_start + last_length + pregap_total,
+    )
+  }
+
+  #[inline]
+  pub const fn first_track(&self) -> u8 {
+    self.first_track
+  }
+
+  #[inline]
+  pub const fn last_track(&self) -> u8 {
+    self.first_track + self.track_count - 1
+  }
+
+  #[inline]
+  pub const fn track_count(&self) -> u8 {
+    self.track_count
+  }
+
+  #[inline]
+  pub fn tracks(&self) -> &[TocTrack] {
+    &self.tracks[..self.track_count as usize]
+  }
+
+  /// Returns the track entry by its track number.
+  #[inline]
+  pub fn track(&self, track_no: u8) -> Option<&TocTrack> {
+    self
+      .tracks()
+      .get(track_no.checked_sub(self.first_track)? as usize)
+  }
+
+  /// Lead-out LBA.
+  #[inline]
+  pub const fn lead_out(&self) -> u32 {
+    self.lead_out
+  }
+
+  /// Computes the FreeDB/CDDB disc id. Every track is included, data tracks as well.
+  pub fn cddb_id(&self) -> CddbDiscId {
+    let seconds = |lba: u32| (lba + LEAD_IN_FRAMES) / 75;
+    let tracks = self.tracks();
+    let checksum: u32 = tracks
+      .iter()
+      .map(|v| {
+        let mut value = seconds(v.offset);
+        let mut sum = 0;
+
+        while value > 0 {
+          sum += value % 10;
+          value /= 10;
+        }
+
+        sum
+      })
+      .sum();
+
+    let length = seconds(self.lead_out) - seconds(tracks[0].offset);
+
+    CddbDiscId::new(((checksum % 0xFF) << 24) | (length << 8) | self.track_count as u32)
+  }
+
+  /// Computes the MusicBrainz disc id.
+  ///
+  /// Data tracks at the end of the disc are treated as an Enhanced CD session, they're excluded
+  /// and the lead-out is moved 11400 frames before the first data track.
+  #[inline]
+  pub fn musicbrainz_id(&self) -> MusicBrainzDiscId {
+    MusicBrainzDiscId::new(&self.musicbrainz_toc())
+  }
+
+  /// Returns the TOC used for MusicBrainz lookups, see [MusicBrainzToc].
+  pub fn musicbrainz_toc(&self) -> MusicBrainzToc {
+    let (audio_count, lead_out) = self.audio_session();
+    let mut offsets = [0u32; 99];
+
+    for (offset, track) in offsets.iter_mut().zip(&self.tracks()[..audio_count]) {
+      *offset = track.offset + LEAD_IN_FRAMES;
+    }
+
+    MusicBrainzToc::new(
+      self.first_track,
+      self.first_track + audio_count as u8 - 1,
+      lead_out + LEAD_IN_FRAMES,
+      offsets,
+    )
+  }
+
+  /// Computes the AccurateRip disc id. Data tracks are skipped but the lead-out stays at the end
+  /// of the disc.
+  pub fn accuraterip_id(&self) -> AccurateRipId {
+    let mut id1: u32 = 0;
+    let mut id2: u32 = 0;
+    let mut audio_count: u32 = 0;
+
+    for track in self.tracks().iter().filter(|v| !v.is_data) {
+      audio_count += 1;
+      id1 = id1.wrapping_add(track.offset);
+      id2 = id2.wrapping_add(track.offset.max(1).wrapping_mul(audio_count));
+    }
+
+    id1 = id1.wrapping_add(self.lead_out);
+    id2 = id2.wrapping_add(self.lead_out.max(1).wrapping_mul(audio_count + 1));
+
+    AccurateRipId::new(audio_count as u8, id1, id2, self.cddb_id())
+  }
+
+  /// Computes the CUETools DB TOC id over the audio tracks.
+  pub fn ctdb_toc_id(&self) -> CtdbTocId {
+    let (audio_end, lead_out) = self.audio_session();
+    let mut offsets = [0u32; 99];
+    let mut audio_count = 0;
+    let mut first_offset = None;
+
+    for track in self.tracks()[..audio_end].iter().filter(|v| !v.is_data) {
+      let first_offset = *first_offset.get_or_insert(track.offset);
+      offsets[audio_count] = track.offset - first_offset;
+      audio_count += 1;
+    }
+
+    CtdbTocId::new(
+      &offsets[..audio_count],
+      lead_out - first_offset.unwrap_or(0),
+    )
+  }
+
+  /// Returns the track count and the lead-out of the first session. Data tracks at the end of the
+  /// disc are treated as an Enhanced CD session, the lead-out is 11400 frames before the first
+  /// data track.
+  fn audio_session(&self) -> (usize, u32) {
+    let tracks = self.tracks();
+    let audio_count = tracks
+      .iter()
+      .rposition(|v| !v.is_data)
+      .map_or(tracks.len(), |v| v + 1);
+
+    match tracks.get(audio_count) {
+      Some(data_track) => (
+        audio_count,
+        data_track.offset.saturating_sub(ENHANCED_CD_GAP_FRAMES),
+      ),
+      None => (audio_count, self.lead_out),
+    }
+  }
+}
@@ -0,0 +1,309 @@
+//! Owned cuesheet model.
+//!
+//! Unlike the probes, the types in this module do not borrow from the input buffer. Text values
+//! are stored un-escaped and un-quoted, so they can be freely cached, cloned and sent across
+//! threads.
+
+use crate::{
+  core::{
+    album_file::{self, KnownFileType},
+    flags::TrackFlag,
+    timestamp::CueTimeStamp,
+    track::{DataType, IndexNo, TrackNo},
+  },
+  discid::{isrc::Isrc, mcn::Mcn},
+  error::CueLibError,
+  probe::{CueSheetProbe, track::TrackProbe},
+  timing::{self, TrackTiming},
+};
+use alloc::{
+  string::{String, ToString},
+  vec::Vec,
+};
+
+mod recovery;
+
+/// Owned cuesheet document.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+pub struct CueSheet {
+  /// Disc's media catalog number (CATALOG command)
+  pub catalog: Option<Mcn>,
+
+  /// CD-TEXT file name (CDTEXTFILE command)
+  pub cdtextfile: Option<String>,
+
+  /// Performer name for the entire album (PERFORMER command)
+  pub performer: Option<String>,
+
+  /// Songwriter name for the entire album (SONGWRITER command)
+  pub songwriter: Option<String>,
+
+  /// Album title (TITLE command)
+  pub title: Option<String>,
+
+  /// Arranger name for the entire album (ARRANGER command)
+  pub arranger: Option<String>,
+
+  /// Composer name for the entire album (COMPOSER command)
+  pub composer: Option<String>,
+
+  /// Album message (MESSAGE command)
+  pub message: Option<String>,
+
+  /// Album genre (GENRE command)
+  pub genre: Option<String>,
+
+  /// Disc identification information (DISC_ID command)
+  pub disc_id: Option<String>,
+
+  /// Album UPC/EAN code (UPC_EAN command)
+  pub upc_ean: Option<String>,
+
+  /// Files referenced by the track indexes, in order of appearance.
+  pub files: Vec<AlbumFile>,
+
+  /// Remarks in the album portion of the cuesheet (REM command)
+  pub remarks: Vec<String>,
+
+  pub tracks: Vec<Track>,
+}
+
+/// Owned version of [AlbumFile](crate::core::album_file::AlbumFile).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct AlbumFile {
+  pub file_type: KnownFileType,
+  pub name: String,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Track {
+  pub track_no: TrackNo,
+  pub data_type: DataType,
+
+  /// Track-specific flags (FLAGS command)
+  pub flags: Option<TrackFlag>,
+
+  /// International Standard Recording Code for the track (ISRC command)
+  pub isrc: Option<Isrc>,
+
+  /// Performer name for this specific track (PERFORMER command)
+  pub performer: Option<String>,
+
+  /// Songwriter name for this specific track (SONGWRITER command)
+  pub songwriter: Option<String>,
+
+  /// Track title (TITLE command)
+  pub title: Option<String>,
+
+  /// Arranger name for this specific track (ARRANGER command)
+  pub arranger: Option<String>,
+
+  /// Composer name for this specific track (COMPOSER command)
+  pub composer: Option<String>,
+
+  /// Track message (MESSAGE command)
+  pub message: Option<String>,
+
+  /// Length of the pre-gap silence (PREGAP command)
+  pub pregap: Option<CueTimeStamp>,
+
+  /// Length of the post-gap silence (POSTGAP command)
+  pub postgap: Option<CueTimeStamp>,
+
+  /// Every index of the track in ascending order, including INDEX 00 and INDEX 01.
+  pub indexes: Vec<Index>,
+
+  /// Remarks in the track portion of the cuesheet (REM command)
+  pub remarks: Vec<String>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Index {
+  pub index_no: IndexNo,
+
+  /// Timestamp relative to the start of the index file.
+  pub timestamp: CueTimeStamp,
+
+  /// Position of the index file in [CueSheet::files].
+  pub file: Option<usize>,
+}
+
+impl CueSheet {
+  /// Reads every command from the probe and builds an owned cuesheet.
+  pub fn from_probe(probe: &CueSheetProbe<'_>) -> Result<Self, CueLibError> {
+    let mut cuesheet = Self {
+      catalog: probe.catalog(),
+      cdtextfile: probe.cdtextfile().map(|v| v.to_string()),
+      performer: probe.performer().map(|v| v.to_string()),
+      songwriter: probe.songwriter().map(|v| v.to_string()),
+      title: probe.album_title().map(|v| v.to_string()),
+      arranger: probe.arranger().map(|v| v.to_string()),
+      composer: probe.composer().map(|v| v.to_string()),
+      message: probe.message().map(|v| v.to_string()),
+      genre: probe.genre().map(|v| v.to_string()),
+      disc_id: probe.disc_id().map(|v| v.to_string()),
+      upc_ean: probe.upc_ean().map(|v| v.to_string()),
+      files: Vec::new(),
+      remarks: probe.remarks().map(|v| v.to_string()).collect(),
+      tracks: Vec::new(),
+    };
+
+    _ = cuesheet.file_position(probe.file_info());
+
+    let mut tracks = probe.tracks();
+
+    while let Some(track) = tracks.next_track()? {
+      let track = cuesheet.read_track(&track)?;
+      cuesheet.tracks.push(track);
+    }
+
+    Ok(cuesheet)
+  }
+
+  /// Returns the file of the given index.
+  #[inline]
+  pub fn index_file(&self, index: &Index) -> Option<&AlbumFile> {
+    index.file.and_then(|position| self.files.get(position))
+  }
+
+  /// Returns an iterator over the vorbis metadata remarks in the album portion of the cuesheet.
+  #[cfg(feature = "metadata")]
+  #[inline]
+  pub fn vorbis_comments(&self) -> impl Iterator<Item = crate::metadata::VorbisComment<'_>> {
+    vorbis_comments(&self.remarks)
+  }
+
+  /// Calculates track timings, see [timing::track_timings].
+  #[inline]
+  pub fn track_timings(&self, file_lengths: &[CueTimeStamp]) -> Vec<TrackTiming> {
+    timing::track_timings(self, file_lengths)
+  }
+
+  /// Calculates track timings with the hidden track as track 0, see
+  /// [timing::track_timings_with_htoa].
+  #[inline]
+  pub fn track_timings_with_htoa(&self, file_lengths: &[CueTimeStamp]) -> Vec<TrackTiming> {
+    timing::track_timings_with_htoa(self, file_lengths)
+  }
+
+  /// Returns the hidden track one audio (HTOA), see [timing::hidden_track].
+  #[inline]
+  pub fn hidden_track(&self) -> Option<TrackTiming> {
+    timing::hidden_track(self)
+  }
+
+  fn read_track(&mut self, probe: &TrackProbe<'_>) -> Result<Track, CueLibError> {
+    let mut track = Track {
+      track_no: probe.track_no(),
+      data_type: probe.track_data_type(),
+      flags: probe.flags(),
+      isrc: probe.isrc(),
+      performer: probe.performer().map(|v| v.to_string()),
+      songwriter: probe.songwriter().map(|v| v.to_string()),
+      title: probe.title().map(|v| v.to_string()),
+      arranger: probe.arranger().map(|v| v.to_string()),
+      composer: probe.composer().map(|v| v.to_string()),
+      message: probe.message().map(|v| v.to_string()),
+      pregap: probe.pregap(),
+      postgap: probe.postgap(),
+      indexes: Vec::new(),
+      remarks: probe.remarks().map(|v| v.to_string()).collect(),
+    };
+
+    if let Some(timestamp) = probe.pregap_index() {
+      track.indexes.push(Index {
+        index_no: IndexNo::MIN,
+        timestamp,
+        file: self.file_position(probe.pregap_file()),
+      });
+    }
+
+    track.indexes.push(Index {
+      // SAFETY: 1 is always in IndexNo range.
+      index_no: unsafe { IndexNo::new_unchecked(1) },
+      timestamp: probe.start_index(),
+      file: self.file_position(probe.file()),
+    });
+
+    let mut sub_indexes = probe.sub_indexes();
+
+    while let Some(index) = sub_indexes.next_index()? {
+      track.indexes.push(Index {
+        index_no: index.index_no,
+        timestamp: index.timestamp,
+        file: self.file_position(sub_indexes.file()),
+      });
+    }
+
+    Ok(track)
+  }
+
+  fn file_position(&mut self, file: Option<album_file::AlbumFile<'_>>) -> Option<usize> {
+    let file = AlbumFile::from(file?);
+
+    match self.files.iter().position(|v| *v == file) {
+      Some(position) => Some(position),
+      None => {
+        self.files.push(file);
+        Some(self.files.len() - 1)
+      }
+    }
+  }
+}
+
+impl Track {
+  /// Returns the pregap index (INDEX 00) if present.
+  #[inline]
+  pub fn pregap_index(&self) -> Option<&Index> {
+    self.indexes.iter().find(|v| v.index_no.into_inner() == 0)
+  }
+
+  /// Returns the track start index (INDEX 01).
+  #[inline]
+  pub fn start_index(&self) -> Option<&Index> {
+    self.indexes.iter().find(|v| v.index_no.into_inner() == 1)
+  }
+
+  /// Returns an iterator over the vorbis metadata remarks in the track portion of the cuesheet.
+  #[cfg(feature = "metadata")]
+  #[inline]
+  pub fn vorbis_comments(&self) -> impl Iterator<Item = crate::metadata::VorbisComment<'_>> {
+    vorbis_comments(&self.remarks)
+  }
+}
+
+#[cfg(feature = "metadata")]
+#[inline]
+fn vorbis_comments(remarks: &[String]) -> impl Iterator<Item = crate::metadata::VorbisComment<'_>> {
+  remarks
+    .iter()
+    .filter_map(|v| crate::metadata::VorbisComment::try_from_line(v).ok())
+}
+
+impl From<album_file::AlbumFile<'_>> for AlbumFile {
+  #[inline]
+  fn from(value: album_file::AlbumFile<'_>) -> Self {
+    Self {
+      file_type: value.file_type,
+      name: value.name.to_string(),
+    }
+  }
+}
+
+impl TryFrom<&CueSheetProbe<'_>> for CueSheet {
+  type Error = CueLibError;
+
+  #[inline]
+  fn try_from(value: &CueSheetProbe<'_>) -> Result<Self, Self::Error> {
+    Self::from_probe(value)
+  }
+}
+
+impl core::str::FromStr for CueSheet {
+  type Err = CueLibError;
+
+  #[inline]
+  fn from_str(s: &str) -> Result<Self, Self::Err> {
+    Self::from_probe(&CueSheetProbe::new(s)?)
+  }
+}
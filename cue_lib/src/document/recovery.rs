@@ -0,0 +1,185 @@
+use super::{CueSheet, Index, Track};
+use crate::{
+  core::{album_file::AlbumFile, command::Command},
+  error::{Diagnostic, ParseError, ParseErrorKind},
+  internal::{lexer::CueLexer, tokenizer::Tokenizer},
+  probe::{
+    builder::{CueProbeBuilder, TrackProbeBuilder},
+    track::{IndexOrder, TrackIndexProbe, check_track_no},
+  },
+};
+use alloc::{string::ToString, vec::Vec};
+
+impl CueSheet {
+  /// Parses the cuesheet without stopping at the first error.
+  ///
+  /// Lines with invalid commands are skipped and parsing continues from the next line, so the
+  /// returned document contains everything that could be read. Diagnostics are empty for valid
+  /// cuesheets.
+  pub fn parse_recovering(cuesheet: &str) -> (Self, Vec<Diagnostic>) {
+    let mut parser = RecoveringParser {
+      cuesheet: CueSheet::default(),
+      diagnostics: Vec::new(),
+      album: CueProbeBuilder::new(),
+      file: None,
+      track: None,
+    };
+
+    let mut lexer = CueLexer::new(Tokenizer::new(cuesheet));
+
+    loop {
+      match lexer.next_command() {
+        Ok(Some(command)) => {
+          if let Err(kind) = parser.apply(command, &lexer) {
+            parser.report(ParseError::new_with_line(kind, lexer.position().line));
+          }
+        }
+        Ok(None) => break,
+        Err(err) => {
+          parser.report(err);
+          lexer.skip_line();
+        }
+      }
+    }
+
+    parser.finish_track();
+
+    if parser.cuesheet.tracks.is_empty() {
+      parser.report(ParseError::new_with_position(
+        ParseErrorKind::MissingTrackCommand,
+        lexer.position(),
+      ));
+    }
+
+    // Missing index errors are reported after the track is complete
+    parser.diagnostics.sort_by_key(|v| v.line());
+
+    (parser.cuesheet, parser.diagnostics)
+  }
+}
+
+struct RecoveringParser<'a> {
+  cuesheet: CueSheet,
+  diagnostics: Vec<Diagnostic>,
+
+  /// Album section rules are checked by the probe builder.
+  album: CueProbeBuilder<'a>,
+
+  /// Last declared file, index timestamps are relative to it.
+  file: Option<AlbumFile<'a>>,
+  track: Option<TrackState<'a>>,
+}
+
+struct TrackState<'a> {
+  track: Track,
+  builder: TrackProbeBuilder<'a>,
+  index_order: IndexOrder<'a>,
+  line: usize,
+}
+
+impl<'a> RecoveringParser<'a> {
+  #[inline]
+  fn report(&mut self, error: ParseError) {
+    self.diagnostics.push(error.into());
+  }
+
+  /// Applies the command with the same rules as [CueSheetProbe](crate::probe::CueSheetProbe).
+  fn apply(&mut self, command: Command<'a>, lexer: &CueLexer<'a>) -> Result<(), ParseErrorKind> {
+    if let Command::Track { value } = command {
+      let prev_track_no = self.track.as_ref().map(|v| v.track.track_no);
+      self.finish_track();
+      self.track = Some(TrackState {
+        track: Track {
+          track_no: value.track_no,
+          data_type: value.data_type,
+          flags: None,
+          isrc: None,
+          performer: None,
+          songwriter: None,
+          title: None,
+          arranger: None,
+          composer: None,
+          message: None,
+          pregap: None,
+          postgap: None,
+          indexes: Vec::new(),
+          remarks: Vec::new(),
+        },
+        builder: TrackProbeBuilder::new(TrackIndexProbe::new(lexer.snapshot(), self.file), value),
+        index_order: IndexOrder::new(self.file),
+        line: lexer.position().line,
+      });
+
+      return match prev_track_no {
+        Some(prev) => check_track_no(prev, value.track_no),
+        None => Ok(()),
+      };
+    }
+
+    let Some(state) = self.track.as_mut() else {
+      if let Command::File { value } = command {
+        // Later indexes use the new file even if the command is reported
+        self.file = Some(value);
+      }
+
+      self.album.apply_command(command)?;
+
+      match command {
+        Command::Catalog { value } => self.cuesheet.catalog = Some(value),
+        Command::CdTextFile { value } => self.cuesheet.cdtextfile = Some(value.to_string()),
+        Command::Performer { value } => self.cuesheet.performer = Some(value.to_string()),
+        Command::SongWriter { value } => self.cuesheet.songwriter = Some(value.to_string()),
+        Command::Title { value } => self.cuesheet.title = Some(value.to_string()),
+        Command::Arranger { value } => self.cuesheet.arranger = Some(value.to_string()),
+        Command::Composer { value } => self.cuesheet.composer = Some(value.to_string()),
+        Command::Message { value } => self.cuesheet.message = Some(value.to_string()),
+        Command::Genre { value } => self.cuesheet.genre = Some(value.to_string()),
+        Command::DiscId { value } => self.cuesheet.disc_id = Some(value.to_string()),
+        Command::UpcEan { value } => self.cuesheet.upc_ean = Some(value.to_string()),
+        Command::Remark { value } => self.cuesheet.remarks.push(value.to_string()),
+        Command::File { value } => _ = self.cuesheet.file_position(Some(value)),
+        _ => {}
+      }
+
+      return Ok(());
+    };
+
+    state.builder.apply_command(command, &mut self.file)?;
+
+    match command {
+      Command::Flags { value } => state.track.flags = Some(value),
+      Command::ISRC { value } => state.track.isrc = Some(value),
+      Command::Performer { value } => state.track.performer = Some(value.to_string()),
+      Command::Postgap { value } => state.track.postgap = Some(value),
+      Command::Pregap { value } => state.track.pregap = Some(value),
+      Command::SongWriter { value } => state.track.songwriter = Some(value.to_string()),
+      Command::Title { value } => state.track.title = Some(value.to_string()),
+      Command::Arranger { value } => state.track.arranger = Some(value.to_string()),
+      Command::Composer { value } => state.track.composer = Some(value.to_string()),
+      Command::Message { value } => state.track.message = Some(value.to_string()),
+      Command::Remark { value } => state.track.remarks.push(value.to_string()),
+      Command::Index { value } => {
+        state.index_order.push(value, self.file)?;
+        state.track.indexes.push(Index {
+          index_no: value.index_no,
+          timestamp: value.timestamp,
+          file: self.cuesheet.file_position(self.file),
+        });
+      }
+      _ => {}
+    }
+
+    Ok(())
+  }
+
+  fn finish_track(&mut self) {
+    if let Some(state) = self.track.take() {
+      // The builder reports tracks without INDEX 01
+      if let Err(kind) = state.builder.build("") {
+        self.report(ParseError::new_with_line(kind, state.line));
+      }
+
+      self.cuesheet.tracks.push(state.track);
+    }
+  }
+}
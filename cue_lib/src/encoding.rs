@@ -0,0 +1,150 @@
+//! Decoding cuesheets with non UTF-8 encodings.
+//!
+//! Byte order marks are always respected. Without a BOM, valid UTF-8 input is used as-is and
+//! other inputs are guessed with charset heuristics, which covers the common Shift-JIS and
+//! Windows-1252 cuesheets.
+
+use crate::{error::CueLibError, probe::CueSheetProbe};
+use alloc::{borrow::Cow, string::String};
+use chardetng::EncodingDetector;
+
+pub use encoding_rs::Encoding;
+
+/// How the encoding was picked.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum EncodingSource {
+  /// Byte order mark at the beginning of the input.
+  Bom,
+
+  /// Encoding given by the caller.
+  Explicit,
+
+  /// Input is valid UTF-8.
+  Utf8,
+
+  /// Guessed by charset heuristics.
+  Heuristic,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnknownEncoding;
+
+impl core::fmt::Display for UnknownEncoding {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    f.write_str("unknown encoding label")
+  }
+}
+
+impl core::error::Error for UnknownEncoding {}
+
+/// Cuesheet text decoded from bytes.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DecodedCueSheet<'a> {
+  text: Cow<'a, str>,
+  encoding: &'static Encoding,
+  source: EncodingSource,
+  had_errors: bool,
+}
+
+impl<'a> DecodedCueSheet<'a> {
+  /// Detects the encoding and decodes the cuesheet.
+  pub fn decode(bytes: &'a [u8]) -> Self {
+    if let Some((encoding, bom_len)) = Encoding::for_bom(bytes) {
+      return Self::new(bytes, bom_len, encoding, EncodingSource::Bom);
+    }
+
+    if core::str::from_utf8(bytes).is_ok() {
+      return Self::new(bytes, 0, encoding_rs::UTF_8, EncodingSource::Utf8);
+    }
+
+    let mut detector = EncodingDetector::new();
+    detector.feed(bytes, true);
+    let encoding = detector.guess(None, true);
+
+    Self::new(bytes, 0, encoding, EncodingSource::Heuristic)
+  }
+
+  /// Decodes the cuesheet with the given encoding. A BOM is only removed when it matches the
+  /// encoding.
+  pub fn decode_with(bytes: &'a [u8], encoding: &'static Encoding) -> Self {
+    let bom_len = match Encoding::for_bom(bytes) {
+      Some((bom_encoding, bom_len)) if bom_encoding == encoding => bom_len,
+      _ => 0,
+    };
+
+    Self::new(bytes, bom_len, encoding, EncodingSource::Explicit)
+  }
+
+  /// Decodes the cuesheet with the encoding label, such as `shift_jis`, `windows-1252` or
+  /// `utf-16le`.
+  pub fn decode_with_label(bytes: &'a [u8], label: &str) -> Result<Self, UnknownEncoding> {
+    match Encoding::for_label(label.as_bytes()) {
+      Some(encoding) => Ok(Self::decode_with(bytes, encoding)),
+      None => Err(UnknownEncoding),
+    }
+  }
+
+  fn new(
+    bytes: &'a [u8],
+    bom_len: usize,
+    encoding: &'static Encoding,
+    source: EncodingSource,
+  ) -> Self {
+    let (text, had_errors) = encoding.decode_without_bom_handling(&bytes[bom_len..]);
+
+    Self {
+      text,
+      encoding,
+      source,
+      had_errors,
+    }
+  }
+
+  #[inline]
+  pub fn as_str(&self) -> &str {
+    &self.text
+  }
+
+  #[inline]
+  pub fn into_string(self) -> String {
+    self.text.into_owned()
+  }
+
+  #[inline]
+  pub const fn encoding(&self) -> &'static Encoding {
+    self.encoding
+  }
+
+  /// Returns the canonical name of the encoding, e.g. `Shift_JIS`.
+  #[inline]
+  pub fn encoding_name(&self) -> &'static str {
+    self.encoding.name()
+  }
+
+  #[inline]
+  pub const fn source(&self) -> EncodingSource {
+    self.source
+  }
+
+  /// Returns `true` if malformed sequences were replaced with U+FFFD.
+  #[inline]
+  pub const fn had_errors(&self) -> bool {
+    self.had_errors
+  }
+
+  #[inline]
+  pub fn probe(&self) -> Result<CueSheetProbe<'_>, CueLibError> {
+    CueSheetProbe::new(&self.text)
+  }
+}
+
+impl core::fmt::Display for EncodingSource {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    match self {
+      EncodingSource::Bom => f.write_str("byte order mark"),
+      EncodingSource::Explicit => f.write_str("explicit"),
+      EncodingSource::Utf8 => f.write_str("valid utf-8"),
+      EncodingSource::Heuristic => f.write_str("detected"),
+    }
+  }
+}
@@ -3,7 +3,7 @@ use crate::{
     CueStrError, DataTypeParseError, DigitsParseError, FlagParseError, InvalidNumericRange,
     TimeStampParseError, UnknownFileType,
   },
-  discid::error::IsrcParseError,
+  discid::error::{IsrcParseError, McnParseError},
   internal::tokenizer::Position,
 };
 
@@ -79,6 +79,63 @@ impl ParseError {
   }
 }
 
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+  Warning,
+  Error,
+}
+
+/// Problem found while parsing a cuesheet in recovery mode.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Diagnostic {
+  error: ParseError,
+  severity: Severity,
+}
+
+impl Diagnostic {
+  #[inline]
+  pub const fn new(error: ParseError, severity: Severity) -> Self {
+    Self { error, severity }
+  }
+
+  #[inline]
+  pub const fn error(&self) -> ParseError {
+    self.error
+  }
+
+  #[inline]
+  pub const fn kind(&self) -> ParseErrorKind {
+    self.error.kind
+  }
+
+  /// Zero-based line number.
+  #[inline]
+  pub const fn line(&self) -> usize {
+    self.error.line
+  }
+
+  /// Zero-based column number.
+  #[inline]
+  pub const fn column(&self) -> usize {
+    self.error.col
+  }
+
+  #[inline]
+  pub const fn severity(&self) -> Severity {
+    self.severity
+  }
+}
+
+impl From<ParseError> for Diagnostic {
+  #[inline]
+  fn from(error: ParseError) -> Self {
+    Self {
+      error,
+      severity: Severity::Error,
+    }
+  }
+}
+
 #[derive(Clone, Copy, Debug, PartialEq, Eq)]
 pub enum ParseErrorKind {
   CueStrError(CueStrError),
@@ -88,6 +145,7 @@ pub enum ParseErrorKind {
   InvalidCommandFormat,
   InvalidNumericRange(InvalidNumericRange),
   IsrcParseError(IsrcParseError),
+  McnParseError(McnParseError),
   TimeStampParseError(TimeStampParseError),
   UnknownCommand,
   UnknownFileType(UnknownFileType),
@@ -99,6 +157,7 @@ pub enum ParseErrorKind {
   InvalidTrackIndex,
   MissingTrackIndex,
   MissingTrackCommand,
+  InvalidUtf8,
 }
 
 impl From<UnknownFileType> for ParseErrorKind {
@@ -157,6 +216,13 @@ impl From<IsrcParseError> for ParseErrorKind {
   }
 }
 
+impl From<McnParseError> for ParseErrorKind {
+  #[inline]
+  fn from(error: McnParseError) -> Self {
+    ParseErrorKind::McnParseError(error)
+  }
+}
+
 impl From<ParseError> for CueLibError {
   #[inline]
   fn from(error: ParseError) -> Self {
@@ -175,6 +241,7 @@ impl core::fmt::Display for ParseErrorKind {
       ParseErrorKind::EmptyCueSheet => f.write_str("empty cuesheet input"),
       ParseErrorKind::FlagParseError(err) => err.fmt(f),
       ParseErrorKind::IsrcParseError(err) => err.fmt(f),
+      ParseErrorKind::McnParseError(err) => err.fmt(f),
       ParseErrorKind::InvalidCommandFormat => f.write_str("invalid cuesheet command format"),
       ParseErrorKind::InvalidCommandUsage => f.write_str("invalid cuesheet command usage"),
       ParseErrorKind::InvalidCueSheetFormat => f.write_str("invalid cuesheet format"),
@@ -186,6 +253,7 @@ impl core::fmt::Display for ParseErrorKind {
       ParseErrorKind::InvalidTrackNo => f.write_str("invalid track number"),
       ParseErrorKind::InvalidTrackIndex => f.write_str("invalid track index"),
       ParseErrorKind::MissingTrackCommand => f.write_str("at least one track must be specified"),
+      ParseErrorKind::InvalidUtf8 => f.write_str("invalid UTF-8 sequence"),
       ParseErrorKind::MissingTrackIndex => {
         f.write_str("at least one track index must be specified")
       }
@@ -193,6 +261,27 @@ impl core::fmt::Display for ParseErrorKind {
   }
 }
 
+impl core::fmt::Display for Severity {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    match self {
+      Severity::Warning => f.write_str("warning"),
+      Severity::Error => f.write_str("error"),
+    }
+  }
+}
+
+impl core::fmt::Display for Diagnostic {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    f.write_fmt(format_args!(
+      "{severity} at {line}:{column}, {kind}",
+      severity = self.severity,
+      line = self.error.line,
+      column = self.error.col,
+      kind = self.error.kind,
+    ))
+  }
+}
+
 impl core::fmt::Display for CueLibErrorKind {
   fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
     match self {
@@ -0,0 +1,456 @@
+//! Raw sector (`BINARY` and `MOTOROLA`) disc image tooling.
+//!
+//! Plans only contain byte ranges of the source files, reading and writing the files is left to
+//! the caller.
+
+use crate::{
+  addressing::{self, AddressingError, AddressingErrorKind, DiscLayout, FileSize},
+  core::{
+    album_file::KnownFileType,
+    timestamp::CueTimeStamp,
+    track::{DataType, TrackNo},
+  },
+  document::{AlbumFile, CueSheet, Track},
+  layout::{Layout, LayoutError, LayoutErrorKind},
+};
+use alloc::{string::String, vec::Vec};
+
+pub mod iso9660;
+pub mod sector;
+
+/// Size of the user data in a cooked (ISO) sector.
+pub const USER_DATA_SIZE: usize = 2048;
+
+/// Byte range in a source file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ImageCopy {
+  /// Position of the file in [CueSheet::files].
+  pub file: usize,
+  pub start: u64,
+
+  /// End byte (exclusive).
+  pub end: u64,
+
+  /// Audio samples are stored big-endian (`MOTOROLA`) and must be swapped.
+  pub swap_bytes: bool,
+}
+
+/// Instructions for rewriting an image with a different file layout.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ImagePlan {
+  /// Regenerated cuesheet, every file is a `BINARY` file.
+  pub cuesheet: CueSheet,
+
+  /// Byte ranges written to each file in the new [CueSheet::files], in order.
+  pub outputs: Vec<Vec<ImageCopy>>,
+}
+
+/// Location of a data track's sectors.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DataTrack {
+  pub track_no: TrackNo,
+  pub data_type: DataType,
+
+  /// Disc address of the first sector (INDEX 01).
+  pub lba: u32,
+
+  /// Byte ranges of the track, from INDEX 01 to the next track.
+  pub copies: Vec<ImageCopy>,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct ImageError {
+  kind: ImageErrorKind,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum ImageErrorKind {
+  /// Size of the file at the given position is missing.
+  MissingFileSize(usize),
+
+  /// Size of the file at the given position is smaller than its last track start.
+  InvalidFileSize(usize),
+
+  /// File at the given position is not a raw sector image.
+  UnsupportedFileType(usize),
+
+  /// Index has no `FILE` command.
+  MissingFile,
+
+  /// Track does not exist, or the cuesheet has no data track.
+  TrackNotFound,
+
+  /// Track is an audio track.
+  NotDataTrack,
+}
+
+impl ImageError {
+  #[inline]
+  pub const fn new(kind: ImageErrorKind) -> Self {
+    Self { kind }
+  }
+
+  #[inline]
+  pub const fn kind(&self) -> ImageErrorKind {
+    self.kind
+  }
+}
+
+impl core::fmt::Display for ImageError {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    match self.kind {
+      ImageErrorKind::MissingFileSize(position) => f.write_fmt(format_args!(
+        "image conversion failed: missing size of file #{position}"
+      )),
+      ImageErrorKind::InvalidFileSize(position) => f.write_fmt(format_args!(
+        "image conversion failed: file #{position} is smaller than its tracks"
+      )),
+      ImageErrorKind::UnsupportedFileType(position) => f.write_fmt(format_args!(
+        "image conversion failed: file #{position} is not a BINARY or MOTOROLA file"
+      )),
+      ImageErrorKind::MissingFile => {
+        f.write_str("image conversion failed: index does not belong to a file")
+      }
+      ImageErrorKind::TrackNotFound => f.write_str("image conversion failed: track not found"),
+      ImageErrorKind::NotDataTrack => {
+        f.write_str("image conversion failed: track is not a data track")
+      }
+    }
+  }
+}
+
+impl core::error::Error for ImageError {}
+
+impl From<AddressingError> for ImageError {
+  #[inline]
+  fn from(value: AddressingError) -> Self {
+    Self::new(match value.kind() {
+      AddressingErrorKind::MissingFileSize(position) => ImageErrorKind::MissingFileSize(position),
+      AddressingErrorKind::InvalidFileSize(position) => ImageErrorKind::InvalidFileSize(position),
+      AddressingErrorKind::MissingFile => ImageErrorKind::MissingFile,
+    })
+  }
+}
+
+impl From<LayoutError> for ImageError {
+  #[inline]
+  fn from(value: LayoutError) -> Self {
+    Self::new(match value.kind() {
+      LayoutErrorKind::MissingFileLength(position) => ImageErrorKind::MissingFileSize(position),
+      LayoutErrorKind::MissingFile => ImageErrorKind::MissingFile,
+    })
+  }
+}
+
+impl ImagePlan {
+  /// Plans one `BINARY` file per track, pregaps are kept at the start of their own track.
+  ///
+  /// `file_sizes` are the byte sizes of each file in [CueSheet::files] order.
+  pub fn split<F>(
+    cuesheet: &CueSheet,
+    file_sizes: &[u64],
+    mut file_name: F,
+  ) -> Result<Self, ImageError>
+  where
+    F: FnMut(&Track) -> String,
+  {
+    let image = SourceImage::raw(cuesheet, file_sizes)?;
+    let output = Layout::GapsPrepended.convert(cuesheet, &image.lengths(), |track| AlbumFile {
+      file_type: KnownFileType::Binary,
+      name: track.map(&mut file_name).unwrap_or_default(),
+    })?;
+
+    let mut outputs = Vec::with_capacity(cuesheet.tracks.len());
+
+    for idx in 0..cuesheet.tracks.len() {
+      let mut copies = Vec::new();
+      image.track_copies(idx, &mut copies)?;
+      outputs.push(copies);
+    }
+
+    Ok(Self {
+      cuesheet: output,
+      outputs,
+    })
+  }
+
+  /// Plans a single `BINARY` file with every track.
+  ///
+  /// `file_sizes` are the byte sizes of each file in [CueSheet::files] order.
+  pub fn merge(
+    cuesheet: &CueSheet,
+    file_sizes: &[u64],
+    file_name: String,
+  ) -> Result<Self, ImageError> {
+    let image = SourceImage::raw(cuesheet, file_sizes)?;
+    let output = Layout::SingleFile.convert(cuesheet, &image.lengths(), |_| AlbumFile {
+      file_type: KnownFileType::Binary,
+      name: file_name.clone(),
+    })?;
+
+    let mut copies = Vec::new();
+
+    for idx in 0..cuesheet.tracks.len() {
+      image.track_copies(idx, &mut copies)?;
+    }
+
+    Ok(Self {
+      cuesheet: output,
+      outputs: Vec::from([copies]),
+    })
+  }
+}
+
+impl DataTrack {
+  /// Locates the data track, or the first data track when `track_no` is `None`.
+  ///
+  /// `file_sizes` are the sizes of each file in [CueSheet::files] order, like [DiscLayout::new].
+  /// Only the files of the data track must be `BINARY` or `MOTOROLA` files, audio files such as
+  /// `WAVE` only need their [FileSize::Length].
+  pub fn new(
+    cuesheet: &CueSheet,
+    file_sizes: &[FileSize],
+    track_no: Option<TrackNo>,
+  ) -> Result<Self, ImageError> {
+    let position = match track_no {
+      Some(track_no) => cuesheet.tracks.iter().position(|v| v.track_no == track_no),
+      None => cuesheet.tracks.iter().position(|v| !v.data_type.is_audio()),
+    }
+    .ok_or(ImageError::new(ImageErrorKind::TrackNotFound))?;
+
+    if cuesheet.tracks[position].data_type.is_audio() {
+      return Err(ImageError::new(ImageErrorKind::NotDataTrack));
+    }
+
+    let image = SourceImage::new(cuesheet, file_sizes)?;
+    let layout = DiscLayout::new(cuesheet, file_sizes)?;
+
+    Self::from_position(&image, &layout, position)
+  }
+
+  /// Locates every data track, see [DataTrack::new].
+  pub fn all(cuesheet: &CueSheet, file_sizes: &[FileSize]) -> Result<Vec<Self>, ImageError> {
+    let image = SourceImage::new(cuesheet, file_sizes)?;
+    let layout = DiscLayout::new(cuesheet, file_sizes)?;
+
+    cuesheet
+      .tracks
+      .iter()
+      .enumerate()
+      .filter(|(_, track)| !track.data_type.is_audio())
+      .map(|(position, _)| Self::from_position(&image, &layout, position))
+      .collect()
+  }
+
+  /// Returns the file position and byte offset of the sector, relative to the track start.
+  pub fn sector_offset(&self, sector: u32) -> Option<(usize, u64)> {
+    let sector_size = self.data_type.sector_size() as u64;
+    let mut offset = sector as u64 * sector_size;
+
+    for copy in &self.copies {
+      let length = copy.end - copy.start;
+
+      if offset + sector_size <= length {
+        return Some((copy.file, copy.start + offset));
+      }
+
+      offset = offset.saturating_sub(length);
+    }
+
+    None
+  }
+
+  fn from_position(
+    image: &SourceImage<'_>,
+    layout: &DiscLayout,
+    position: usize,
+  ) -> Result<Self, ImageError> {
+    let track = &image.cuesheet.tracks[position];
+    let start = track
+      .start_index()
+      .ok_or(ImageError::new(ImageErrorKind::MissingFile))?;
+
+    let mut copies = Vec::new();
+    image.copies(
+      image.absolute(start.file, start.timestamp)?,
+      image.track_end(position)?,
+      track.data_type,
+      &mut copies,
+    );
+
+    if let Some(copy) = copies.iter().find(|v| !image.is_raw(v.file)) {
+      return Err(ImageError::new(ImageErrorKind::UnsupportedFileType(
+        copy.file,
+      )));
+    }
+
+    Ok(Self {
+      track_no: track.track_no,
+      data_type: track.data_type,
+      lba: layout.tracks[position].start_lba,
+      copies,
+    })
+  }
+}
+
+/// Returns the 2048 bytes user data of a raw sector, `None` for audio tracks or when the sector
+/// length does not match the data type.
+///
+/// `MODE2` and `CDI` sectors are read as Form 1 sectors.
+pub fn user_data(data_type: DataType, sector: &[u8]) -> Option<&[u8]> {
+  if sector.len() != data_type.sector_size() as usize {
+    return None;
+  }
+
+  let offset = match data_type {
+    DataType::Audio | DataType::CDG => return None,
+    DataType::Mode1_2048 => 0,
+    DataType::Mode1_2352 => 16,
+    DataType::Mode2_2336 | DataType::CDI_2336 => 8,
+    DataType::Mode2_2352 | DataType::CDI_2352 => 24,
+  };
+
+  sector.get(offset..(offset + USER_DATA_SIZE))
+}
+
+/// Swaps the byte order of 16-bit samples, a trailing odd byte is left unchanged.
+#[inline]
+pub fn swap_bytes(buffer: &mut [u8]) {
+  for sample in buffer.chunks_exact_mut(2) {
+    sample.swap(0, 1);
+  }
+}
+
+/// Sector positions of the source files.
+struct SourceImage<'a> {
+  cuesheet: &'a CueSheet,
+  file_sectors: Vec<u32>,
+  file_starts: Vec<u32>,
+}
+
+impl<'a> SourceImage<'a> {
+  /// Creates the image where every file is a raw sector image.
+  fn raw(cuesheet: &'a CueSheet, file_sizes: &[u64]) -> Result<Self, ImageError> {
+    if let Some(position) = (0..cuesheet.files.len()).find(|v| !is_raw_file(cuesheet, *v)) {
+      return Err(ImageError::new(ImageErrorKind::UnsupportedFileType(
+        position,
+      )));
+    }
+
+    let file_sizes: Vec<FileSize> = file_sizes.iter().map(|v| FileSize::Bytes(*v)).collect();
+    Self::new(cuesheet, &file_sizes)
+  }
+
+  fn new(cuesheet: &'a CueSheet, file_sizes: &[FileSize]) -> Result<Self, ImageError> {
+    let file_sectors = addressing::file_sectors(cuesheet, file_sizes)?;
+    let file_starts = file_sectors
+      .iter()
+      .scan(0, |start, length| {
+        let current = *start;
+        *start += length;
+        Some(current)
+      })
+      .collect();
+
+    Ok(Self {
+      cuesheet,
+      file_sectors,
+      file_starts,
+    })
+  }
+
+  #[inline]
+  fn is_raw(&self, file: usize) -> bool {
+    is_raw_file(self.cuesheet, file)
+  }
+
+  #[inline]
+  fn lengths(&self) -> Vec<CueTimeStamp> {
+    self
+      .file_sectors
+      .iter()
+      .map(|v| CueTimeStamp::from_frames(*v as u64))
+      .collect()
+  }
+
+  #[inline]
+  fn absolute(&self, file: Option<usize>, timestamp: CueTimeStamp) -> Result<u32, ImageError> {
+    let file = file.ok_or(ImageError::new(ImageErrorKind::MissingFile))?;
+    Ok(self.file_starts[file] + timestamp.as_frames() as u32)
+  }
+
+  /// Returns the absolute sector of the track's first index.
+  fn track_start(&self, position: usize) -> Result<u32, ImageError> {
+    let index = self.cuesheet.tracks[position]
+      .indexes
+      .first()
+      .ok_or(ImageError::new(ImageErrorKind::MissingFile))?;
+
+    self.absolute(index.file, index.timestamp)
+  }
+
+  /// Returns the absolute sector where the next track starts, or the end of the image.
+  fn track_end(&self, position: usize) -> Result<u32, ImageError> {
+    if position + 1 < self.cuesheet.tracks.len() {
+      self.track_start(position + 1)
+    } else {
+      Ok(self.file_sectors.iter().sum())
+    }
+  }
+
+  /// Adds the byte ranges of the track with its pregap, the first track starts at the beginning
+  /// of the image.
+  fn track_copies(&self, position: usize, copies: &mut Vec<ImageCopy>) -> Result<(), ImageError> {
+    let start = if position == 0 {
+      0
+    } else {
+      self.track_start(position)?
+    };
+
+    let data_type = self.cuesheet.tracks[position].data_type;
+    self.copies(start, self.track_end(position)?, data_type, copies);
+
+    Ok(())
+  }
+
+  /// Adds the byte ranges of the absolute sector range, split at file boundaries.
+  fn copies(&self, start: u32, end: u32, data_type: DataType, copies: &mut Vec<ImageCopy>) {
+    for (file, (file_start, length)) in self.file_starts.iter().zip(&self.file_sectors).enumerate()
+    {
+      let first = start.max(*file_start);
+      let last = end.min(file_start + length);
+
+      if first >= last {
+        continue;
+      }
+
+      let copy = ImageCopy {
+        file,
+        start: addressing::byte_offset(self.cuesheet, file, first - file_start),
+        end: addressing::byte_offset(self.cuesheet, file, last - file_start),
+        swap_bytes: data_type.is_audio()
+          && self.cuesheet.files[file].file_type == KnownFileType::Motorola,
+      };
+
+      match copies.last_mut() {
+        Some(prev)
+          if prev.file == copy.file
+            && prev.end == copy.start
+            && prev.swap_bytes == copy.swap_bytes =>
+        {
+          prev.end = copy.end;
+        }
+        _ => copies.push(copy),
+      }
+    }
+  }
+}
+
+/// Returns `true` for `BINARY` and `MOTOROLA` files.
+#[inline]
+fn is_raw_file(cuesheet: &CueSheet, file: usize) -> bool {
+  matches!(
+    cuesheet.files[file].file_type,
+    KnownFileType::Binary | KnownFileType::Motorola
+  )
+}
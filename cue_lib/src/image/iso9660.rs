@@ -0,0 +1,119 @@
+//! ISO 9660 Primary Volume Descriptor (PVD).
+
+use alloc::string::String;
+
+/// Sector of the PVD, relative to the start of the data track.
+pub const PRIMARY_VOLUME_SECTOR: u32 = 16;
+
+const STANDARD_ID: &[u8; 5] = b"CD001";
+const PRIMARY_VOLUME_TYPE: u8 = 1;
+
+/// Volume information from the Primary Volume Descriptor.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct PrimaryVolume {
+  pub system_id: String,
+  pub volume_id: String,
+
+  /// Volume size in logical blocks.
+  pub volume_size: u32,
+  pub block_size: u16,
+  pub creation_date: Option<VolumeDate>,
+}
+
+/// Date and time with the GMT offset in 15 minute intervals.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct VolumeDate {
+  pub year: u16,
+  pub month: u8,
+  pub day: u8,
+  pub hour: u8,
+  pub minute: u8,
+  pub second: u8,
+  pub hundredths: u8,
+  pub gmt_offset: i8,
+}
+
+impl PrimaryVolume {
+  /// Parses the 2048 bytes user data of the PVD sector, `None` when the sector is not a PVD.
+  pub fn parse(data: &[u8]) -> Option<Self> {
+    if data.len() < 2048 || data[0] != PRIMARY_VOLUME_TYPE || &data[1..6] != STANDARD_ID {
+      return None;
+    }
+
+    Some(Self {
+      system_id: text(&data[8..40]),
+      volume_id: text(&data[40..72]),
+      volume_size: u32::from_le_bytes([data[80], data[81], data[82], data[83]]),
+      block_size: u16::from_le_bytes([data[128], data[129]]),
+      creation_date: VolumeDate::parse(&data[813..830]),
+    })
+  }
+
+  /// Returns the volume size in bytes.
+  #[inline]
+  pub const fn volume_bytes(&self) -> u64 {
+    self.volume_size as u64 * self.block_size as u64
+  }
+}
+
+impl VolumeDate {
+  /// Parses the 17 bytes `YYYYMMDDHHMMSScc` date with the offset, `None` for unset (all zero)
+  /// dates.
+  fn parse(data: &[u8]) -> Option<Self> {
+    let digits = |start: usize, len: usize| {
+      data[start..(start + len)]
+        .iter()
+        .try_fold(0u16, |value, digit| {
+          digit
+            .is_ascii_digit()
+            .then(|| value * 10 + (digit - b'0') as u16)
+        })
+    };
+
+    let date = Self {
+      year: digits(0, 4)?,
+      month: digits(4, 2)? as u8,
+      day: digits(6, 2)? as u8,
+      hour: digits(8, 2)? as u8,
+      minute: digits(10, 2)? as u8,
+      second: digits(12, 2)? as u8,
+      hundredths: digits(14, 2)? as u8,
+      gmt_offset: data[16] as i8,
+    };
+
+    if date.year == 0 && date.month == 0 && date.day == 0 {
+      None
+    } else {
+      Some(date)
+    }
+  }
+}
+
+impl core::fmt::Display for VolumeDate {
+  /// Writes the date in ISO 8601 format.
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    let offset = self.gmt_offset as i16 * 15;
+
+    f.write_fmt(format_args!(
+      "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:02}{}{:02}:{:02}",
+      self.year,
+      self.month,
+      self.day,
+      self.hour,
+      self.minute,
+      self.second,
+      self.hundredths,
+      if offset < 0 { '-' } else { '+' },
+      offset.abs() / 60,
+      offset.abs() % 60
+    ))
+  }
+}
+
+/// Returns the identifier without the space padding.
+#[inline]
+fn text(data: &[u8]) -> String {
+  String::from_utf8_lossy(data)
+    .trim_end_matches([' ', '\0'])
+    .into()
+}
@@ -0,0 +1,151 @@
+//! Raw 2352 bytes data sector checks.
+//!
+//! Every raw `MODE1` and `MODE2` Form 1 sector carries a sync pattern, an address header, an EDC
+//! checksum and P/Q parity. `MODE2` Form 2 sectors only have an optional EDC checksum.
+
+use crate::{
+  addressing::Msf,
+  core::track::DataType,
+  internal::ecc::{self, P_PARITY_SIZE, Q_PARITY_SIZE},
+};
+
+/// Size of a raw sector.
+pub const RAW_SECTOR_SIZE: usize = 2352;
+
+/// Sync pattern at the start of every raw data sector.
+pub const SYNC: [u8; 12] = [
+  0x00, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x00,
+];
+
+const HEADER: usize = 12;
+const MODE: usize = 15;
+const SUBHEADER: usize = 16;
+const FORM_2_FLAG: u8 = 0x20;
+const P_PARITY: usize = 2076;
+const Q_PARITY: usize = P_PARITY + P_PARITY_SIZE;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum SectorError {
+  /// Sector is not 2352 bytes.
+  Size,
+
+  /// Sync pattern is missing.
+  Sync,
+
+  /// Header address does not match the sector's position.
+  Address,
+
+  /// Header mode does not match the track data type.
+  Mode,
+
+  /// EDC checksum mismatch.
+  Edc,
+
+  /// P parity mismatch.
+  PParity,
+
+  /// Q parity mismatch.
+  QParity,
+}
+
+impl core::fmt::Display for SectorError {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    f.write_str(match self {
+      SectorError::Size => "incomplete sector",
+      SectorError::Sync => "missing sync pattern",
+      SectorError::Address => "header address mismatch",
+      SectorError::Mode => "header mode mismatch",
+      SectorError::Edc => "EDC mismatch",
+      SectorError::PParity => "ECC P parity mismatch",
+      SectorError::QParity => "ECC Q parity mismatch",
+    })
+  }
+}
+
+impl core::error::Error for SectorError {}
+
+/// Returns `true` when the data type stores raw sectors with sync, header and EDC/ECC.
+#[inline]
+pub const fn is_raw(data_type: DataType) -> bool {
+  matches!(
+    data_type,
+    DataType::Mode1_2352 | DataType::Mode2_2352 | DataType::CDI_2352
+  )
+}
+
+/// Verifies a raw data sector at the LBA. Cooked and audio sectors are not checked.
+pub fn verify_sector(data_type: DataType, lba: u32, sector: &[u8]) -> Result<(), SectorError> {
+  if !is_raw(data_type) {
+    return Ok(());
+  }
+
+  if sector.len() != RAW_SECTOR_SIZE {
+    return Err(SectorError::Size);
+  }
+
+  if sector[..HEADER] != SYNC {
+    return Err(SectorError::Sync);
+  }
+
+  let msf = Msf::from_lba(lba);
+
+  if sector[HEADER..MODE] != [bcd(msf.minute), bcd(msf.second), bcd(msf.frame)] {
+    return Err(SectorError::Address);
+  }
+
+  match (data_type, sector[MODE]) {
+    (DataType::Mode1_2352, 1) => verify_mode1(sector),
+    (DataType::Mode2_2352 | DataType::CDI_2352, 2) => verify_mode2(sector),
+    _ => Err(SectorError::Mode),
+  }
+}
+
+fn verify_mode1(sector: &[u8]) -> Result<(), SectorError> {
+  verify_edc(&sector[..2064], &sector[2064..2068])?;
+  verify_parity(&sector[HEADER..Q_PARITY], &sector[P_PARITY..])
+}
+
+fn verify_mode2(sector: &[u8]) -> Result<(), SectorError> {
+  if sector[SUBHEADER + 2] & FORM_2_FLAG == FORM_2_FLAG {
+    // EDC is optional for Form 2 sectors.
+    let stored = &sector[2348..RAW_SECTOR_SIZE];
+
+    if stored == [0; 4] {
+      Ok(())
+    } else {
+      verify_edc(&sector[SUBHEADER..2348], stored)
+    }
+  } else {
+    verify_edc(&sector[SUBHEADER..2072], &sector[2072..2076])?;
+
+    // Form 1 parity is calculated with a zeroed header.
+    let mut data = [0u8; Q_PARITY - HEADER];
+    data[4..].copy_from_slice(&sector[(HEADER + 4)..Q_PARITY]);
+    verify_parity(&data, &sector[P_PARITY..])
+  }
+}
+
+#[inline]
+fn verify_edc(data: &[u8], stored: &[u8]) -> Result<(), SectorError> {
+  if ecc::edc(data).to_le_bytes() == stored {
+    Ok(())
+  } else {
+    Err(SectorError::Edc)
+  }
+}
+
+/// Checks the parity of the data starting at the header, `parity` is the stored P and Q parity.
+fn verify_parity(data: &[u8], parity: &[u8]) -> Result<(), SectorError> {
+  if ecc::p_parity(data) != parity[..P_PARITY_SIZE] {
+    Err(SectorError::PParity)
+  } else if ecc::q_parity(data) != parity[P_PARITY_SIZE..(P_PARITY_SIZE + Q_PARITY_SIZE)] {
+    Err(SectorError::QParity)
+  } else {
+    Ok(())
+  }
+}
+
+#[inline]
+const fn bcd(value: u8) -> u8 {
+  ((value / 10) << 4) | (value % 10)
+}
@@ -1,5 +1,10 @@
 pub mod bitflag;
+
+#[cfg(feature = "alloc")]
+pub mod ecc;
+
 pub mod enum_str;
 pub mod lexer;
 pub mod range;
+pub mod sha1;
 pub mod tokenizer;
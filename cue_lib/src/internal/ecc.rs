@@ -0,0 +1,104 @@
+//! CD-ROM error detection (EDC) and Reed-Solomon product code (ECC) of raw data sectors.
+
+const EDC_TABLE: [u32; 256] = edc_table();
+const ECC_F_TABLE: [u8; 256] = ecc_tables().0;
+const ECC_B_TABLE: [u8; 256] = ecc_tables().1;
+
+/// Size of the P parity bytes.
+pub const P_PARITY_SIZE: usize = 172;
+
+/// Size of the Q parity bytes.
+pub const Q_PARITY_SIZE: usize = 104;
+
+const fn edc_table() -> [u32; 256] {
+  let mut table = [0u32; 256];
+  let mut idx = 0;
+
+  while idx < 256 {
+    let mut edc = idx as u32;
+    let mut bit = 0;
+
+    while bit < 8 {
+      edc = (edc >> 1) ^ if edc & 1 == 1 { 0xD8018001 } else { 0 };
+      bit += 1;
+    }
+
+    table[idx] = edc;
+    idx += 1;
+  }
+
+  table
+}
+
+const fn ecc_tables() -> ([u8; 256], [u8; 256]) {
+  let mut forward = [0u8; 256];
+  let mut backward = [0u8; 256];
+  let mut idx = 0;
+
+  while idx < 256 {
+    let value = ((idx << 1) ^ if idx & 0x80 != 0 { 0x11D } else { 0 }) as u8;
+    forward[idx] = value;
+    backward[idx ^ value as usize] = idx as u8;
+    idx += 1;
+  }
+
+  (forward, backward)
+}
+
+/// CRC-32 of the sector data, stored little-endian after the data it covers.
+pub fn edc(data: &[u8]) -> u32 {
+  data.iter().fold(0, |edc, byte| {
+    (edc >> 8) ^ EDC_TABLE[((edc ^ *byte as u32) & 0xFF) as usize]
+  })
+}
+
+/// Calculates the P parity over the 2064 bytes from the sector header to the end of the EDC
+/// area.
+#[inline]
+pub fn p_parity(data: &[u8]) -> [u8; P_PARITY_SIZE] {
+  let mut parity = [0u8; P_PARITY_SIZE];
+  compute_block(data, 86, 24, 2, 86, &mut parity);
+  parity
+}
+
+/// Calculates the Q parity over the 2236 bytes from the sector header to the end of the P parity.
+#[inline]
+pub fn q_parity(data: &[u8]) -> [u8; Q_PARITY_SIZE] {
+  let mut parity = [0u8; Q_PARITY_SIZE];
+  compute_block(data, 52, 43, 86, 88, &mut parity);
+  parity
+}
+
+fn compute_block(
+  data: &[u8],
+  major_count: usize,
+  minor_count: usize,
+  major_mult: usize,
+  minor_inc: usize,
+  parity: &mut [u8],
+) {
+  let size = major_count * minor_count;
+
+  for major in 0..major_count {
+    let mut idx = (major >> 1) * major_mult + (major & 1);
+    let mut ecc_a = 0u8;
+    let mut ecc_b = 0u8;
+
+    for _ in 0..minor_count {
+      let value = data[idx];
+      idx += minor_inc;
+
+      if idx >= size {
+        idx -= size;
+      }
+
+      ecc_a ^= value;
+      ecc_b ^= value;
+      ecc_a = ECC_F_TABLE[ecc_a as usize];
+    }
+
+    ecc_a = ECC_B_TABLE[(ECC_F_TABLE[ecc_a as usize] ^ ecc_b) as usize];
+    parity[major] = ecc_a;
+    parity[major + major_count] = ecc_a ^ ecc_b;
+  }
+}
@@ -12,7 +12,7 @@ use crate::{
     timestamp::CueTimeStamp,
     track::{DataType, IndexNo, Track, TrackIndex, TrackNo},
   },
-  discid::isrc::Isrc,
+  discid::{isrc::Isrc, mcn::Mcn},
   error::{ParseError, ParseErrorKind},
   internal::tokenizer::Token,
 };
@@ -22,6 +22,8 @@ use core::str::FromStr;
 pub struct CueLexer<'a> {
   tokenizer: Tokenizer<'a>,
   position: Position,
+  command_position: Position,
+  unknown_commands: bool,
 }
 
 struct UnknownCommand;
@@ -30,19 +32,25 @@ impl_enum_str!(
   CommandName,
   parse_error = UnknownCommand,
   values = [
+    (Arranger, "ARRANGER"),
     (Catalog, "CATALOG"),
     (CdTextFile, "CDTEXTFILE"),
+    (Composer, "COMPOSER"),
+    (DiscId, "DISC_ID"),
     (File, "FILE"),
     (Flags, "FLAGS"),
+    (Genre, "GENRE"),
     (Index, "INDEX"),
     (Isrc, "ISRC"),
+    (Message, "MESSAGE"),
     (Performer, "PERFORMER"),
     (Postgap, "POSTGAP"),
     (Pregap, "PREGAP"),
     (Remark, "REM"),
     (Songwriter, "SONGWRITER"),
     (Title, "TITLE"),
-    (Track, "TRACK")
+    (Track, "TRACK"),
+    (UpcEan, "UPC_EAN")
   ]
 );
 
@@ -52,6 +60,8 @@ impl<'a> CueLexer<'a> {
     Self {
       tokenizer,
       position,
+      command_position: position,
+      unknown_commands: false,
     }
   }
 
@@ -60,14 +70,28 @@ impl<'a> CueLexer<'a> {
     Self {
       tokenizer: self.tokenizer.snapshot(),
       position: self.position,
+      command_position: self.command_position,
+      unknown_commands: self.unknown_commands,
     }
   }
 
+  /// Returns unknown commands as [Command::Unknown] instead of an error.
+  #[inline]
+  pub const fn set_unknown_commands(&mut self, value: bool) {
+    self.unknown_commands = value;
+  }
+
   #[inline]
   pub const fn position(&self) -> &Position {
     &self.position
   }
 
+  /// Position of the last command's first token.
+  #[inline]
+  pub const fn command_position(&self) -> &Position {
+    &self.command_position
+  }
+
   #[inline]
   pub const fn cursor_position(&self) -> usize {
     self.tokenizer.cursor_position()
@@ -80,13 +104,23 @@ impl<'a> CueLexer<'a> {
 
   pub fn next_command(&mut self) -> Result<Option<Command<'a>>, ParseError> {
     loop {
-      match self.next_token() {
+      let token = self.next_token();
+      self.command_position = self.position;
+
+      match token {
         Ok(Some(command_token)) => match command_token {
           Token::Text {
             value: CueStr::Text(cmd_text),
           } => {
             let command_name = match CommandName::from_str(cmd_text) {
               Ok(name) => Ok(name),
+              Err(_) if self.unknown_commands => {
+                let args = self.read_raw_line();
+                return Ok(Some(Command::Unknown {
+                  name: cmd_text,
+                  args,
+                }));
+              }
               Err(_) => Err(ParseError::new_with_position(
                 ParseErrorKind::UnknownCommand,
                 self.position(),
@@ -94,12 +128,17 @@ impl<'a> CueLexer<'a> {
             }?;
 
             return match command_name {
+              CommandName::Arranger => self.read_arranger(),
               CommandName::Catalog => self.read_catalog(),
               CommandName::CdTextFile => self.read_cdtextfile(),
+              CommandName::Composer => self.read_composer(),
+              CommandName::DiscId => self.read_disc_id(),
               CommandName::File => self.read_file(),
               CommandName::Flags => self.read_flags(),
+              CommandName::Genre => self.read_genre(),
               CommandName::Index => self.read_index(),
               CommandName::Isrc => self.read_isrc(),
+              CommandName::Message => self.read_message(),
               CommandName::Performer => self.read_performer(),
               CommandName::Postgap => self.read_postgap(),
               CommandName::Pregap => self.read_pregap(),
@@ -107,6 +146,7 @@ impl<'a> CueLexer<'a> {
               CommandName::Songwriter => self.read_songwriter(),
               CommandName::Title => self.read_title(),
               CommandName::Track => self.read_track(),
+              CommandName::UpcEan => self.read_upc_ean(),
             }
             .map(|v| Some(v));
           }
@@ -124,7 +164,16 @@ impl<'a> CueLexer<'a> {
     }
   }
 
+  /// Skips the rest of the last command's line. Used for recovering from a [ParseError] returned
+  /// by [next_command](Self::next_command).
+  pub fn skip_line(&mut self) {
+    if self.tokenizer.position().line == self.command_position.line {
+      self.tokenizer.skip_line();
+    }
+  }
+
   fn next_token(&mut self) -> Result<Option<Token<'a>>, CueStrError> {
+    self.tokenizer.eat_whitespace();
     self.position = *self.tokenizer.position();
     self.tokenizer.next_token()
   }
@@ -163,10 +212,27 @@ impl<'a> CueLexer<'a> {
     }
   }
 
-  fn read_catalog(&mut self) -> Result<Command<'a>, ParseError> {
+  fn read_arranger(&mut self) -> Result<Command<'a>, ParseError> {
     let value = self.expect_cue_str()?;
     self.expect_line_end()?;
 
+    Ok(Command::Arranger { value })
+  }
+
+  fn read_catalog(&mut self) -> Result<Command<'a>, ParseError> {
+    let value = {
+      // Catalog number might be quoted
+      let value = match self.expect_cue_str()? {
+        CueStr::QuotedText(text) => &text[1..(text.len() - 1)],
+        value => value.as_raw_str(),
+      };
+
+      Mcn::from_str(value)
+        .map_err(|err| ParseError::new_with_position(err.into(), self.position()))?
+    };
+
+    self.expect_line_end()?;
+
     Ok(Command::Catalog { value })
   }
 
@@ -177,6 +243,20 @@ impl<'a> CueLexer<'a> {
     Ok(Command::CdTextFile { value })
   }
 
+  fn read_composer(&mut self) -> Result<Command<'a>, ParseError> {
+    let value = self.expect_cue_str()?;
+    self.expect_line_end()?;
+
+    Ok(Command::Composer { value })
+  }
+
+  fn read_disc_id(&mut self) -> Result<Command<'a>, ParseError> {
+    let value = self.expect_cue_str()?;
+    self.expect_line_end()?;
+
+    Ok(Command::DiscId { value })
+  }
+
   fn read_file(&mut self) -> Result<Command<'a>, ParseError> {
     let name = self.expect_cue_str()?;
     let type_str = self.expect_str()?;
@@ -222,6 +302,13 @@ impl<'a> CueLexer<'a> {
     }
   }
 
+  fn read_genre(&mut self) -> Result<Command<'a>, ParseError> {
+    let value = self.expect_cue_str()?;
+    self.expect_line_end()?;
+
+    Ok(Command::Genre { value })
+  }
+
   fn read_index(&mut self) -> Result<Command<'a>, ParseError> {
     let index_no = {
       let value = self.expect_str()?;
@@ -257,6 +344,13 @@ impl<'a> CueLexer<'a> {
     Ok(Command::ISRC { value })
   }
 
+  fn read_message(&mut self) -> Result<Command<'a>, ParseError> {
+    let value = self.expect_cue_str()?;
+    self.expect_line_end()?;
+
+    Ok(Command::Message { value })
+  }
+
   fn read_performer(&mut self) -> Result<Command<'a>, ParseError> {
     let value = self.expect_cue_str()?;
     self.expect_line_end()?;
@@ -289,19 +383,25 @@ impl<'a> CueLexer<'a> {
   }
 
   fn read_remark(&mut self) -> Result<Command<'a>, ParseError> {
+    let value = self.read_raw_line();
+
+    Ok(Command::Remark { value })
+  }
+
+  /// Reads the rest of the line without tokenizing.
+  fn read_raw_line(&mut self) -> &'a str {
     let start = self.tokenizer.cursor_position();
     let end = loop {
       match self.next_token() {
-        Ok(Some(Token::LF) | None) => {
+        Ok(Some(Token::LF)) => {
           break self.tokenizer.cursor_position() - '\n'.len_utf8();
         }
+        Ok(None) => break self.tokenizer.cursor_position(),
         _ => continue,
       }
     };
 
-    let value = &self.tokenizer.as_raw_buffer()[start..end].trim();
-
-    Ok(Command::Remark { value })
+    self.tokenizer.as_raw_buffer()[start..end].trim()
   }
 
   fn read_songwriter(&mut self) -> Result<Command<'a>, ParseError> {
@@ -340,6 +440,13 @@ impl<'a> CueLexer<'a> {
 
     Ok(Command::Track { value })
   }
+
+  fn read_upc_ean(&mut self) -> Result<Command<'a>, ParseError> {
+    let value = self.expect_cue_str()?;
+    self.expect_line_end()?;
+
+    Ok(Command::UpcEan { value })
+  }
 }
 
 impl<'a> From<Tokenizer<'a>> for CueLexer<'a> {
@@ -0,0 +1,100 @@
+/// Minimal SHA-1 implementation, only used for computing MusicBrainz disc ids.
+pub struct Sha1 {
+  state: [u32; 5],
+  block: [u8; 64],
+  block_len: usize,
+  total_len: u64,
+}
+
+impl Sha1 {
+  pub const fn new() -> Self {
+    Self {
+      state: [0x67452301, 0xEFCDAB89, 0x98BADCFE, 0x10325476, 0xC3D2E1F0],
+      block: [0; 64],
+      block_len: 0,
+      total_len: 0,
+    }
+  }
+
+  pub fn update(&mut self, mut data: &[u8]) {
+    self.total_len += data.len() as u64;
+
+    while !data.is_empty() {
+      let len = (64 - self.block_len).min(data.len());
+      self.block[self.block_len..(self.block_len + len)].copy_from_slice(&data[..len]);
+      self.block_len += len;
+      data = &data[len..];
+
+      if self.block_len == 64 {
+        self.compress();
+        self.block_len = 0;
+      }
+    }
+  }
+
+  pub fn finalize(mut self) -> [u8; 20] {
+    let bit_len = self.total_len.wrapping_mul(8);
+
+    self.block[self.block_len] = 0x80;
+    self.block_len += 1;
+
+    if self.block_len > 56 {
+      self.block[self.block_len..].fill(0);
+      self.compress();
+      self.block_len = 0;
+    }
+
+    self.block[self.block_len..56].fill(0);
+    self.block[56..].copy_from_slice(&bit_len.to_be_bytes());
+    self.compress();
+
+    let mut digest = [0u8; 20];
+
+    for (chunk, word) in digest.chunks_exact_mut(4).zip(self.state) {
+      chunk.copy_from_slice(&word.to_be_bytes());
+    }
+
+    digest
+  }
+
+  fn compress(&mut self) {
+    let mut words = [0u32; 80];
+
+    for (word, chunk) in words.iter_mut().zip(self.block.chunks_exact(4)) {
+      *word = u32::from_be_bytes([chunk[0], chunk[1], chunk[2], chunk[3]]);
+    }
+
+    for idx in 16..80 {
+      words[idx] =
+        (words[idx - 3] ^ words[idx - 8] ^ words[idx - 14] ^ words[idx - 16]).rotate_left(1);
+    }
+
+    let [mut a, mut b, mut c, mut d, mut e] = self.state;
+
+    for (idx, word) in words.iter().enumerate() {
+      let (f, k) = match idx {
+        0..=19 => ((b & c) | (!b & d), 0x5A827999),
+        20..=39 => (b ^ c ^ d, 0x6ED9EBA1),
+        40..=59 => ((b & c) | (b & d) | (c & d), 0x8F1BBCDC),
+        _ => (b ^ c ^ d, 0xCA62C1D6),
+      };
+
+      let temp = a
+        .rotate_left(5)
+        .wrapping_add(f)
+        .wrapping_add(e)
+        .wrapping_add(k)
+        .wrapping_add(*word);
+
+      e = d;
+      d = c;
+      c = b.rotate_left(30);
+      b = a;
+      a = temp;
+    }
+
+    for (state, value) in self.state.iter_mut().zip([a, b, c, d, e]) {
+      *state = state.wrapping_add(value);
+    }
+  }
+}
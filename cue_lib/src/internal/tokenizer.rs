@@ -6,10 +6,19 @@ use crate::core::{
 /// Zero Width No-break Space aka Byte Order Mark
 const ZWNBP: char = '\u{feff}';
 
-#[derive(Default, Debug, Clone, Copy)]
+/// Location in the cuesheet text. Lines and columns are zero-based.
+#[derive(Default, Debug, Clone, Copy, PartialEq, Eq)]
 pub struct Position {
   pub line: usize,
+
+  /// Column in chars.
   pub column: usize,
+
+  /// Column in UTF-16 code units.
+  pub utf16_column: usize,
+
+  /// Byte offset from the beginning of the buffer.
+  pub offset: usize,
 }
 
 #[derive(Debug)]
@@ -28,15 +37,18 @@ impl<'a> From<CueStr<'a>> for Token<'a> {
 pub struct Tokenizer<'a> {
   buffer: &'a str,
   position: Position,
-  cursor_index: usize,
 }
 
 impl<'a> Tokenizer<'a> {
   pub const fn new(buffer: &'a str) -> Self {
     Self {
       buffer,
-      position: Position { line: 0, column: 0 },
-      cursor_index: 0,
+      position: Position {
+        line: 0,
+        column: 0,
+        utf16_column: 0,
+        offset: 0,
+      },
     }
   }
 
@@ -47,7 +59,7 @@ impl<'a> Tokenizer<'a> {
 
   #[inline]
   pub const fn cursor_position(&self) -> usize {
-    self.cursor_index
+    self.position.offset
   }
 
   /// This function simply clones the underlying buffer reference and position.
@@ -58,11 +70,7 @@ impl<'a> Tokenizer<'a> {
   pub const fn snapshot(&self) -> Self {
     Self {
       buffer: self.buffer,
-      position: Position {
-        line: self.position.line,
-        column: self.position.column,
-      },
-      cursor_index: self.cursor_index,
+      position: self.position,
     }
   }
 
@@ -71,9 +79,28 @@ impl<'a> Tokenizer<'a> {
     &self.buffer
   }
 
+  /// Moves the cursor to the beginning of the next line.
+  pub fn skip_line(&mut self) {
+    let remaining = &self.buffer[self.position.offset..];
+
+    match remaining.find('\n') {
+      Some(offset) => {
+        self.position.offset += offset + '\n'.len_utf8();
+        self.position.line += 1;
+        self.position.column = 0;
+        self.position.utf16_column = 0;
+      }
+      None => {
+        for ch in remaining.chars() {
+          self.advance(ch);
+        }
+      }
+    }
+  }
+
   pub fn next_token(&mut self) -> Result<Option<Token<'a>>, CueStrError> {
     self.eat_whitespace();
-    let start = self.cursor_index;
+    let start = self.position.offset;
 
     if start < self.buffer.len() {
       let remaining = &self.buffer[start..];
@@ -95,8 +122,9 @@ impl<'a> Tokenizer<'a> {
     }
   }
 
-  fn eat_whitespace(&mut self) {
-    let start = self.cursor_index;
+  /// Skips whitespaces and byte order marks until the next token or line feed.
+  pub fn eat_whitespace(&mut self) {
+    let start = self.position.offset;
     let remaining = &self.buffer[start..];
     let mut chars = remaining.chars();
 
@@ -104,8 +132,7 @@ impl<'a> Tokenizer<'a> {
       match chars.next() {
         Some(value) => {
           if value != '\n' && (value.is_whitespace() || value == ZWNBP) {
-            self.cursor_index += value.len_utf8();
-            self.position.column += 1;
+            self.advance(value);
           } else {
             break;
           }
@@ -115,29 +142,40 @@ impl<'a> Tokenizer<'a> {
     }
   }
 
+  #[inline]
+  fn advance(&mut self, ch: char) {
+    self.position.offset += ch.len_utf8();
+    self.position.column += 1;
+    self.position.utf16_column += ch.len_utf16();
+  }
+
   #[inline]
   fn line_feed(&mut self) -> Token<'a> {
-    self.cursor_index += '\n'.len_utf8();
+    self.position.offset += '\n'.len_utf8();
     self.position.line += 1;
     self.position.column = 0;
+    self.position.utf16_column = 0;
 
     Token::LF
   }
 
   #[inline]
   fn quoted_str(&mut self) -> Result<CueStr<'a>, CueStrError> {
-    let start = self.cursor_index;
+    let start = self.position.offset;
     let remaining = &self.buffer[start..];
     let mut has_escape = false;
     let mut chars = remaining.chars();
 
+    // Line feeds are never consumed, unterminated strings end at the line boundary.
     macro_rules! next_char {
       () => {{
-        let next = chars.next();
+        let next = chars.clone().next();
 
-        if let Some(v) = next {
-          self.cursor_index += v.len_utf8();
-          self.position.column += 1;
+        if let Some(v) = next
+          && v != '\n'
+        {
+          _ = chars.next();
+          self.advance(v);
         }
 
         next
@@ -150,7 +188,7 @@ impl<'a> Tokenizer<'a> {
     loop {
       match next_char!() {
         Some('"') => {
-          let end = self.cursor_index;
+          let end = self.position.offset;
           let cue_str = if has_escape {
             CueStr::QuotedTextWithEscape(&self.buffer[start..end])
           } else {
@@ -178,18 +216,17 @@ impl<'a> Tokenizer<'a> {
 
   #[inline]
   fn regular_str(&mut self) -> Result<CueStr<'a>, CueStrError> {
-    let start = self.cursor_index;
+    let start = self.position.offset;
     let remaining = &self.buffer[start..];
     let mut chars = remaining.chars();
 
     loop {
       match chars.next() {
         Some(v) if !v.is_whitespace() => {
-          self.cursor_index += v.len_utf8();
-          self.position.column += 1;
+          self.advance(v);
         }
         _ => {
-          let end = self.cursor_index;
+          let end = self.position.offset;
           let cue_str = CueStr::Text(&self.buffer[start..end]);
 
           return Ok(cue_str);
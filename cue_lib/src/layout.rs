@@ -0,0 +1,162 @@
+//! Conversion between single image and file per track cuesheet layouts.
+//!
+//! Only the `FILE` commands and INDEX timestamps are rewritten, cutting or joining the audio
+//! files is left to the caller.
+
+use crate::{
+  core::timestamp::CueTimeStamp,
+  document::{AlbumFile, CueSheet, Track},
+};
+use alloc::vec::Vec;
+
+/// Cuesheet file layouts.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Layout {
+  /// Every track is in a single image file.
+  SingleFile,
+
+  /// File per track, gaps are at the end of the previous track's file (noncompliant).
+  GapsAppended,
+
+  /// File per track, gaps are at the start of the track's own file with INDEX 00.
+  GapsPrepended,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LayoutError {
+  kind: LayoutErrorKind,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum LayoutErrorKind {
+  /// Length of the file at the given position is needed to place the following files.
+  MissingFileLength(usize),
+
+  /// Index has no `FILE` command.
+  MissingFile,
+}
+
+impl LayoutError {
+  #[inline]
+  pub const fn new(kind: LayoutErrorKind) -> Self {
+    Self { kind }
+  }
+
+  #[inline]
+  pub const fn kind(&self) -> LayoutErrorKind {
+    self.kind
+  }
+}
+
+impl core::fmt::Display for LayoutError {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    match self.kind {
+      LayoutErrorKind::MissingFileLength(position) => f.write_fmt(format_args!(
+        "layout conversion failed: missing length of file #{position}"
+      )),
+      LayoutErrorKind::MissingFile => {
+        f.write_str("layout conversion failed: index does not belong to a file")
+      }
+    }
+  }
+}
+
+impl core::error::Error for LayoutError {}
+
+impl Layout {
+  /// Rewrites the cuesheet for the target layout.
+  ///
+  /// `file_lengths` are the lengths of the source files in [CueSheet::files] order, the last
+  /// file's length is not required. `file_name` is called once for [Layout::SingleFile] with
+  /// `None`, and once for every track otherwise.
+  pub fn convert<F>(
+    &self,
+    cuesheet: &CueSheet,
+    file_lengths: &[CueTimeStamp],
+    mut file_name: F,
+  ) -> Result<CueSheet, LayoutError>
+  where
+    F: FnMut(Option<&Track>) -> AlbumFile,
+  {
+    let file_offsets = file_offsets(cuesheet.files.len(), file_lengths)?;
+    let absolute = |file: Option<usize>, timestamp: CueTimeStamp| {
+      let file = file.ok_or(LayoutError::new(LayoutErrorKind::MissingFile))?;
+      Ok::<_, LayoutError>(file_offsets[file].saturating_add(timestamp))
+    };
+
+    let mut output = CueSheet {
+      files: Vec::new(),
+      tracks: Vec::with_capacity(cuesheet.tracks.len()),
+      ..cuesheet.clone()
+    };
+
+    // Absolute start position of every output file.
+    let mut file_starts = Vec::new();
+
+    match self {
+      Layout::SingleFile => {
+        output.files.push(file_name(None));
+        file_starts.push(CueTimeStamp::ZERO);
+      }
+      Layout::GapsAppended | Layout::GapsPrepended => {
+        for (idx, track) in cuesheet.tracks.iter().enumerate() {
+          let start = match (self, idx, track.pregap_index()) {
+            (_, 0, _) => CueTimeStamp::ZERO,
+            (Layout::GapsPrepended, _, Some(index)) => absolute(index.file, index.timestamp)?,
+            _ => match track.start_index() {
+              Some(index) => absolute(index.file, index.timestamp)?,
+              None => continue,
+            },
+          };
+
+          output.files.push(file_name(Some(track)));
+          file_starts.push(start);
+        }
+      }
+    }
+
+    for track in &cuesheet.tracks {
+      let mut track = track.clone();
+
+      for index in &mut track.indexes {
+        let position = absolute(index.file, index.timestamp)?;
+        let file = file_starts
+          .partition_point(|v| *v <= position)
+          .saturating_sub(1);
+
+        index.file = Some(file);
+        index.timestamp = position.saturating_sub(file_starts[file]);
+      }
+
+      output.tracks.push(track);
+    }
+
+    Ok(output)
+  }
+}
+
+/// Returns the absolute start position of the source files.
+fn file_offsets(
+  file_count: usize,
+  file_lengths: &[CueTimeStamp],
+) -> Result<Vec<CueTimeStamp>, LayoutError> {
+  let mut offsets = Vec::with_capacity(file_count);
+  let mut offset = CueTimeStamp::ZERO;
+
+  for position in 0..file_count {
+    offsets.push(offset);
+
+    if position + 1 < file_count {
+      let length =
+        file_lengths
+          .get(position)
+          .ok_or(LayoutError::new(LayoutErrorKind::MissingFileLength(
+            position,
+          )))?;
+
+      offset = offset.saturating_add(*length);
+    }
+  }
+
+  Ok(offsets)
+}
@@ -0,0 +1,206 @@
+//! Low-level command stream with source spans.
+//!
+//! [CommandStream] yields every [Command] in the cuesheet together with the span of the command
+//! and its arguments. Commands are only checked for syntax, use
+//! [CueSheetProbe](crate::probe::CueSheetProbe) for a validated cuesheet.
+
+use crate::{
+  core::command::Command,
+  error::ParseError,
+  internal::{
+    lexer::CueLexer,
+    tokenizer::{Token, Tokenizer},
+  },
+};
+use core::ops::Range;
+
+pub use crate::internal::tokenizer::Position;
+
+/// Start and end positions of a text in the cuesheet. End position is exclusive.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct Span {
+  pub start: Position,
+  pub end: Position,
+}
+
+/// Iterator over the commands of a cuesheet.
+///
+/// Invalid lines are reported as [ParseError] and the iteration continues from the next line.
+#[derive(Clone)]
+pub struct CommandStream<'a> {
+  lexer: CueLexer<'a>,
+}
+
+/// Command with its location in the cuesheet.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SpannedCommand<'a> {
+  command: Command<'a>,
+  source: &'a str,
+  span: Span,
+}
+
+/// Iterator over the argument spans of a command.
+///
+/// The text after `REM` keyword and unknown command names is returned as a single argument.
+#[derive(Clone)]
+pub struct ArgumentSpans<'a> {
+  tokenizer: Tokenizer<'a>,
+  base: Position,
+  is_remark: bool,
+}
+
+impl Span {
+  /// Returns the byte range.
+  #[inline]
+  pub const fn range(&self) -> Range<usize> {
+    self.start.offset..self.end.offset
+  }
+
+  #[inline]
+  pub const fn len(&self) -> usize {
+    self.end.offset - self.start.offset
+  }
+
+  #[inline]
+  pub const fn is_empty(&self) -> bool {
+    self.len() == 0
+  }
+}
+
+impl<'a> CommandStream<'a> {
+  #[inline]
+  pub const fn new(cuesheet: &'a str) -> Self {
+    Self {
+      lexer: CueLexer::new(Tokenizer::new(cuesheet)),
+    }
+  }
+
+  /// Returns unknown commands as [Command::Unknown] instead of an error.
+  #[inline]
+  pub const fn set_unknown_commands(mut self, value: bool) -> Self {
+    self.lexer.set_unknown_commands(value);
+    self
+  }
+
+  pub fn next_command(&mut self) -> Option<Result<SpannedCommand<'a>, ParseError>> {
+    match self.lexer.next_command() {
+      Ok(Some(command)) => {
+        let source = self.lexer.as_raw_buffer();
+        let start = *self.lexer.command_position();
+        let text = source[start.offset..self.lexer.cursor_position()]
+          .trim_end_matches(|v: char| v.is_whitespace());
+
+        Some(Ok(SpannedCommand {
+          command,
+          source,
+          span: Span {
+            start,
+            end: forward(start, text),
+          },
+        }))
+      }
+      Ok(None) => None,
+      Err(err) => {
+        self.lexer.skip_line();
+        Some(Err(err))
+      }
+    }
+  }
+}
+
+impl<'a> Iterator for CommandStream<'a> {
+  type Item = Result<SpannedCommand<'a>, ParseError>;
+
+  #[inline]
+  fn next(&mut self) -> Option<Self::Item> {
+    self.next_command()
+  }
+}
+
+impl<'a> SpannedCommand<'a> {
+  #[inline]
+  pub const fn command(&self) -> Command<'a> {
+    self.command
+  }
+
+  /// Returns the span from the command keyword to the end of the last argument.
+  #[inline]
+  pub const fn span(&self) -> Span {
+    self.span
+  }
+
+  /// Returns the command text without the indentation and line ending.
+  #[inline]
+  pub fn text(&self) -> &'a str {
+    &self.source[self.span.range()]
+  }
+
+  /// Returns the span of the command keyword.
+  pub fn keyword_span(&self) -> Span {
+    let text = self.text();
+    let keyword = text.split(char::is_whitespace).next().unwrap_or(text);
+
+    Span {
+      start: self.span.start,
+      end: forward(self.span.start, keyword),
+    }
+  }
+
+  /// Returns an iterator over the argument spans.
+  pub fn arguments(&self) -> ArgumentSpans<'a> {
+    let mut tokenizer = Tokenizer::new(self.text());
+    _ = tokenizer.next_token();
+
+    ArgumentSpans {
+      tokenizer,
+      base: self.span.start,
+      is_remark: matches!(
+        self.command,
+        Command::Remark { .. } | Command::Unknown { .. }
+      ),
+    }
+  }
+}
+
+impl Iterator for ArgumentSpans<'_> {
+  type Item = Span;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    let buffer = self.tokenizer.as_raw_buffer();
+
+    let text = if self.is_remark {
+      self.is_remark = false;
+      let text = &buffer[self.tokenizer.cursor_position()..];
+      self.tokenizer = Tokenizer::new("");
+      text
+    } else {
+      match self.tokenizer.next_token() {
+        Ok(Some(Token::Text { value })) => value.as_raw_str(),
+        _ => return None,
+      }
+    };
+
+    if text.is_empty() {
+      None
+    } else {
+      let offset = text.as_ptr() as usize - buffer.as_ptr() as usize;
+      let start = forward(self.base, &buffer[..offset]);
+
+      Some(Span {
+        start,
+        end: forward(start, text),
+      })
+    }
+  }
+}
+
+/// Moves the position to the end of the single line text.
+#[inline]
+fn forward(position: Position, text: &str) -> Position {
+  Position {
+    line: position.line,
+    column: position.column + text.chars().count(),
+    utf16_column: position.utf16_column + text.encode_utf16().count(),
+    offset: position.offset + text.len(),
+  }
+}
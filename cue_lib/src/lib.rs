@@ -8,8 +8,42 @@ mod internal;
 pub mod core;
 pub mod discid;
 pub mod error;
+pub mod lexer;
 pub mod probe;
 
+#[cfg(feature = "alloc")]
+pub mod addressing;
+
+#[cfg(feature = "alloc")]
+pub mod cst;
+
+#[cfg(feature = "alloc")]
+pub mod document;
+
+#[cfg(feature = "encoding")]
+pub mod encoding;
+
+#[cfg(feature = "alloc")]
+pub mod image;
+
+#[cfg(feature = "alloc")]
+pub mod layout;
+
+#[cfg(feature = "alloc")]
+pub mod lint;
+
+#[cfg(feature = "alloc")]
+pub mod split;
+
+#[cfg(feature = "alloc")]
+pub mod stream;
+
+#[cfg(feature = "alloc")]
+pub mod timing;
+
+#[cfg(feature = "alloc")]
+pub mod writer;
+
 #[cfg(feature = "metadata")]
 pub mod metadata;
 
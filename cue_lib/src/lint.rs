@@ -0,0 +1,646 @@
+//! Semantic checks for parseable but questionable cuesheets.
+//!
+//! Each [Rule] has a default [Severity] which can be changed or disabled with [LintConfig].
+
+use crate::{
+  core::{
+    album_file::{AlbumFile, KnownFileType},
+    command::Command,
+    cue_str::CueStr,
+    timestamp::CueTimeStamp,
+    track::TrackNo,
+  },
+  error::{CueLibError, Severity},
+  internal::{enum_str::impl_enum_str, lexer::CueLexer, tokenizer::Tokenizer},
+  probe::{
+    CueSheetProbe,
+    track::{TrackProbe, hidden_track_length},
+  },
+};
+use alloc::{string::ToString, vec::Vec};
+
+/// Minimum track length allowed by the Red Book in milliseconds.
+const MIN_TRACK_LENGTH: u128 = 4000;
+
+/// Minimum pregap of the first track (00:02:00).
+const MIN_FIRST_PREGAP: CueTimeStamp = CueTimeStamp::from_frames(150);
+
+/// Maximum disc length allowed by the Red Book (79:59:74).
+const MAX_DISC_LENGTH: CueTimeStamp = CueTimeStamp::from_frames(79 * 4500 + 59 * 75 + 74);
+
+const MAX_TRACK_COUNT: u8 = 99;
+const MAX_INDEX_NO: u8 = 99;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct UnknownRule;
+
+impl core::fmt::Display for UnknownRule {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    f.write_str("unknown lint rule")
+  }
+}
+
+impl core::error::Error for UnknownRule {}
+
+impl_enum_str!(
+  pub Rule, parse_error = UnknownRule,
+  values = [
+    /// First track's INDEX 01 is not at 00:00:00 and there is no hidden track (HTOA) before it.
+    (FirstIndexOffset, "first-index-offset"),
+
+    /// Track is shorter than 4 seconds.
+    (ShortTrack, "short-track"),
+
+    /// Track has both PREGAP command and INDEX 00.
+    (PregapWithIndex00, "pregap-with-index00"),
+
+    /// FLAGS command is placed after the track's first INDEX command.
+    (FlagsAfterIndex, "flags-after-index"),
+
+    /// FLAC file type, most burning software only accepts the original file types.
+    (FlacFileType, "flac-file-type"),
+
+    /// Track title is already used by another track.
+    (DuplicateTitle, "duplicate-title"),
+
+    /// TITLE command with empty text.
+    (EmptyTitle, "empty-title"),
+
+    /// Disc has more than 99 tracks (Red Book).
+    (TrackCount, "track-count"),
+
+    /// Track numbers do not start from 1, are not sequential or greater than 99 (Red Book).
+    (TrackNumber, "track-number"),
+
+    /// Index number is greater than 99 (Red Book).
+    (IndexNumber, "index-number"),
+
+    /// First track's pregap is shorter than 2 seconds (Red Book). A first track without PREGAP
+    /// or INDEX 00 gets its pregap from the burner.
+    (FirstTrackPregap, "first-track-pregap"),
+
+    /// Disc is longer than 79:59:74 (Red Book). Files without a known length are assumed to end
+    /// at their last index.
+    (DiscLength, "disc-length"),
+
+    /// FLAGS command on a data track (Red Book).
+    (DataTrackFlags, "data-track-flags"),
+
+    /// ISRC command on a data track (Red Book).
+    (DataTrackIsrc, "data-track-isrc")
+  ]
+);
+
+impl Rule {
+  pub const ALL: [Rule; 14] = [
+    Rule::FirstIndexOffset,
+    Rule::ShortTrack,
+    Rule::PregapWithIndex00,
+    Rule::FlagsAfterIndex,
+    Rule::FlacFileType,
+    Rule::DuplicateTitle,
+    Rule::EmptyTitle,
+    Rule::TrackCount,
+    Rule::TrackNumber,
+    Rule::IndexNumber,
+    Rule::FirstTrackPregap,
+    Rule::DiscLength,
+    Rule::DataTrackFlags,
+    Rule::DataTrackIsrc,
+  ];
+
+  /// Audio CD constraints, only enabled by [LintConfig::red_book].
+  pub const RED_BOOK: [Rule; 8] = [
+    Rule::ShortTrack,
+    Rule::TrackCount,
+    Rule::TrackNumber,
+    Rule::IndexNumber,
+    Rule::FirstTrackPregap,
+    Rule::DiscLength,
+    Rule::DataTrackFlags,
+    Rule::DataTrackIsrc,
+  ];
+
+  /// Returns `true` for rules that are only enabled by the Red Book profile.
+  pub const fn is_red_book_only(&self) -> bool {
+    matches!(
+      self,
+      Rule::TrackCount
+        | Rule::TrackNumber
+        | Rule::IndexNumber
+        | Rule::FirstTrackPregap
+        | Rule::DiscLength
+        | Rule::DataTrackFlags
+        | Rule::DataTrackIsrc
+    )
+  }
+
+  pub const fn default_severity(&self) -> Severity {
+    match self {
+      Rule::FlagsAfterIndex => Severity::Error,
+      _ if self.is_red_book_only() => Severity::Error,
+      _ => Severity::Warning,
+    }
+  }
+
+  pub const fn description(&self) -> &'static str {
+    match self {
+      Rule::FirstIndexOffset => "first track does not start at 00:00:00 and has no hidden track",
+      Rule::ShortTrack => "track is shorter than 4 seconds",
+      Rule::PregapWithIndex00 => "track has both PREGAP and INDEX 00",
+      Rule::FlagsAfterIndex => "FLAGS must be placed before the first INDEX",
+      Rule::FlacFileType => "FLAC file type is rejected by most burning software",
+      Rule::DuplicateTitle => "track title is used by another track",
+      Rule::EmptyTitle => "TITLE is empty",
+      Rule::TrackCount => "disc has more than 99 tracks",
+      Rule::TrackNumber => "track numbers must start from 1, be sequential and at most 99",
+      Rule::IndexNumber => "index number is greater than 99",
+      Rule::FirstTrackPregap => "first track's pregap is shorter than 2 seconds",
+      Rule::DiscLength => "disc is longer than 79:59:74",
+      Rule::DataTrackFlags => "4CH, PRE, DCP and SCMS flags are only allowed on audio tracks",
+      Rule::DataTrackIsrc => "ISRC is not allowed on data tracks",
+    }
+  }
+}
+
+/// Rule selection and severities.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct LintConfig {
+  levels: [Option<Severity>; Rule::ALL.len()],
+}
+
+/// Problem reported by a lint rule.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Finding {
+  rule: Rule,
+  severity: Severity,
+  line: usize,
+  track_no: Option<TrackNo>,
+}
+
+impl Default for LintConfig {
+  #[inline]
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl LintConfig {
+  /// Creates a config with every general rule enabled at its default severity, Red Book rules
+  /// are disabled.
+  pub const fn new() -> Self {
+    let mut levels = [None; Rule::ALL.len()];
+    let mut idx = 0;
+
+    while idx < Rule::ALL.len() {
+      if !Rule::ALL[idx].is_red_book_only() {
+        levels[idx] = Some(Rule::ALL[idx].default_severity());
+      }
+
+      idx += 1;
+    }
+
+    Self { levels }
+  }
+
+  /// Creates the strict audio CD profile, every rule is enabled and Red Book violations are
+  /// reported as errors.
+  pub const fn red_book() -> Self {
+    let mut config = Self::new();
+    let mut idx = 0;
+
+    while idx < Rule::RED_BOOK.len() {
+      config = config.set_severity(Rule::RED_BOOK[idx], Some(Severity::Error));
+      idx += 1;
+    }
+
+    config
+  }
+
+  /// Creates a config with every rule disabled.
+  #[inline]
+  pub const fn empty() -> Self {
+    Self {
+      levels: [None; Rule::ALL.len()],
+    }
+  }
+
+  /// Sets the rule severity, `None` disables the rule.
+  #[inline]
+  pub const fn set_severity(mut self, rule: Rule, severity: Option<Severity>) -> Self {
+    self.levels[rule as usize] = severity;
+    self
+  }
+
+  /// Enables the rule with its default severity.
+  #[inline]
+  pub const fn enable(self, rule: Rule) -> Self {
+    self.set_severity(rule, Some(rule.default_severity()))
+  }
+
+  #[inline]
+  pub const fn disable(self, rule: Rule) -> Self {
+    self.set_severity(rule, None)
+  }
+
+  #[inline]
+  pub const fn severity(&self, rule: Rule) -> Option<Severity> {
+    self.levels[rule as usize]
+  }
+
+  /// Parses the cuesheet and runs the enabled rules.
+  #[inline]
+  pub fn lint(&self, cuesheet: &str) -> Result<Vec<Finding>, CueLibError> {
+    self.lint_with_file_lengths(cuesheet, &[])
+  }
+
+  /// Parses the cuesheet and runs the enabled rules, see
+  /// [LintConfig::lint_probe_with_file_lengths].
+  #[inline]
+  pub fn lint_with_file_lengths(
+    &self,
+    cuesheet: &str,
+    file_lengths: &[CueTimeStamp],
+  ) -> Result<Vec<Finding>, CueLibError> {
+    self.lint_probe_with_file_lengths(&CueSheetProbe::new(cuesheet)?, file_lengths)
+  }
+
+  /// Runs the enabled rules over the probe. Findings are ordered by line.
+  #[inline]
+  pub fn lint_probe(&self, probe: &CueSheetProbe<'_>) -> Result<Vec<Finding>, CueLibError> {
+    self.lint_probe_with_file_lengths(probe, &[])
+  }
+
+  /// Runs the enabled rules over the probe. `file_lengths` are the lengths of each file in
+  /// [CueSheet::files](crate::document::CueSheet::files) order like
+  /// [track_timings](crate::timing::track_timings), missing entries are treated as unknown.
+  ///
+  /// Length of a file's last track is only checked when the file length is known.
+  pub fn lint_probe_with_file_lengths(
+    &self,
+    probe: &CueSheetProbe<'_>,
+    file_lengths: &[CueTimeStamp],
+  ) -> Result<Vec<Finding>, CueLibError> {
+    let mut linter = Linter {
+      config: self,
+      source: probe.source(),
+      findings: Vec::new(),
+      file_lengths,
+      files: Vec::new(),
+      file_ends: Vec::new(),
+      current_file: probe.file_info(),
+      silence: CueTimeStamp::ZERO,
+    };
+
+    linter.album(probe);
+
+    let mut tracks = probe.tracks();
+    let mut prev: Option<TrackSummary<'_>> = None;
+    let mut titles = Vec::new();
+    let mut track_count: usize = 0;
+
+    while let Some(track) = tracks.next_track()? {
+      let summary = linter.track(&track);
+      track_count += 1;
+
+      if track_count == MAX_TRACK_COUNT as usize + 1 {
+        linter.report(Rule::TrackCount, summary.track_line, Some(summary.track_no));
+      }
+
+      match prev.as_ref() {
+        Some(prev) => {
+          linter.track_length(prev, Some(&summary));
+          linter.track_number(Some(prev.track_no), &summary);
+        }
+        None => {
+          linter.first_track(&summary);
+          linter.track_number(None, &summary);
+        }
+      }
+
+      if let Some((line, title)) = summary.title {
+        let title = title.to_string();
+
+        if !title.trim().is_empty() && titles.contains(&title) {
+          linter.report(Rule::DuplicateTitle, line, Some(summary.track_no));
+        }
+
+        titles.push(title);
+      }
+
+      prev = Some(summary);
+    }
+
+    if let Some(last) = prev {
+      linter.track_length(&last, None);
+
+      if linter.disc_length() > MAX_DISC_LENGTH {
+        linter.report(Rule::DiscLength, last.track_line, Some(last.track_no));
+      }
+    }
+
+    linter.findings.sort_by_key(|v| v.line);
+    Ok(linter.findings)
+  }
+}
+
+impl Finding {
+  #[inline]
+  pub const fn rule(&self) -> Rule {
+    self.rule
+  }
+
+  #[inline]
+  pub const fn severity(&self) -> Severity {
+    self.severity
+  }
+
+  /// Zero-based line number.
+  #[inline]
+  pub const fn line(&self) -> usize {
+    self.line
+  }
+
+  /// Track number, if the finding is in a track.
+  #[inline]
+  pub const fn track_no(&self) -> Option<TrackNo> {
+    self.track_no
+  }
+}
+
+impl core::fmt::Display for Finding {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    f.write_fmt(format_args!(
+      "{severity} at {line}, {rule}: {description}",
+      severity = self.severity,
+      line = self.line,
+      rule = self.rule,
+      description = self.rule.description(),
+    ))
+  }
+}
+
+struct Linter<'c, 'a> {
+  config: &'c LintConfig,
+  source: &'a str,
+  findings: Vec<Finding>,
+  file_lengths: &'c [CueTimeStamp],
+
+  /// Files in [CueSheet::files](crate::document::CueSheet::files) order.
+  files: Vec<AlbumFile<'a>>,
+
+  /// Last index of each file, the lower bound of its length.
+  file_ends: Vec<CueTimeStamp>,
+
+  /// Last declared file, index timestamps are relative to it.
+  current_file: Option<AlbumFile<'a>>,
+
+  /// Total PREGAP and POSTGAP silence.
+  silence: CueTimeStamp,
+}
+
+/// Track values and command lines needed by the rules.
+struct TrackSummary<'a> {
+  track_no: TrackNo,
+  start: CueTimeStamp,
+  pregap: Option<CueTimeStamp>,
+  start_line: Option<usize>,
+
+  /// Position of the INDEX 01 file.
+  file: Option<usize>,
+  pregap_index: Option<CueTimeStamp>,
+
+  /// Position of the INDEX 00 file.
+  pregap_file: Option<usize>,
+  track_line: usize,
+  title: Option<(usize, CueStr<'a>)>,
+}
+
+impl<'a> Linter<'_, 'a> {
+  fn report(&mut self, rule: Rule, line: usize, track_no: Option<TrackNo>) {
+    if let Some(severity) = self.config.severity(rule) {
+      self.findings.push(Finding {
+        rule,
+        severity,
+        line,
+        track_no,
+      });
+    }
+  }
+
+  /// Returns the zero-based line of the buffer's first character.
+  fn line_of(&self, buffer: &str) -> usize {
+    let offset = buffer.as_ptr() as usize - self.source.as_ptr() as usize;
+    self.source.as_bytes()[..offset]
+      .iter()
+      .filter(|v| **v == b'\n')
+      .count()
+  }
+
+  /// Shared checks for commands allowed in both album and track sections.
+  fn common(&mut self, command: &Command<'a>, line: usize, track_no: Option<TrackNo>) {
+    match command {
+      Command::Title { value } if value.to_string().trim().is_empty() => {
+        self.report(Rule::EmptyTitle, line, track_no)
+      }
+      Command::File { value } if value.file_type == KnownFileType::FLAC => {
+        self.report(Rule::FlacFileType, line, track_no)
+      }
+      _ => {}
+    }
+  }
+
+  fn album(&mut self, probe: &CueSheetProbe<'a>) {
+    let buffer = probe.buffer();
+    let base_line = self.line_of(buffer);
+    let mut lexer = CueLexer::new(Tokenizer::new(buffer));
+    lexer.set_unknown_commands(true);
+
+    while let Ok(Some(command)) = lexer.next_command() {
+      self.common(&command, base_line + lexer.position().line, None);
+    }
+  }
+
+  fn track(&mut self, track: &TrackProbe<'a>) -> TrackSummary<'a> {
+    let buffer = track.buffer();
+    let base_line = self.line_of(buffer);
+    let track_no = Some(track.track_no());
+    let mut lexer = CueLexer::new(Tokenizer::new(buffer));
+    lexer.set_unknown_commands(true);
+    let is_data = !track.track_data_type().is_audio();
+    let mut summary = TrackSummary {
+      track_no: track.track_no(),
+      start: track.start_index(),
+      pregap: track.pregap(),
+      start_line: None,
+      file: None,
+      pregap_index: track.pregap_index(),
+      pregap_file: None,
+      // Track buffer starts right after the TRACK command's line.
+      track_line: base_line.saturating_sub(1),
+      title: None,
+    };
+
+    let mut first_index_line = None;
+    let mut pregap_line = None;
+    let mut pregap_index_line = None;
+
+    self.silence = self
+      .silence
+      .saturating_add(track.pregap().unwrap_or_default())
+      .saturating_add(track.postgap().unwrap_or_default());
+
+    while let Ok(Some(command)) = lexer.next_command() {
+      let line = base_line + lexer.position().line;
+      self.common(&command, line, track_no);
+
+      match command {
+        Command::Index { value } => {
+          first_index_line.get_or_insert(line);
+          let file = self.add_index(value.timestamp);
+
+          match value.index_no.into_inner() {
+            0 => {
+              pregap_index_line = Some(line);
+              summary.pregap_file = file;
+            }
+            1 => {
+              summary.start_line = Some(line);
+              summary.file = file;
+            }
+            index_no if index_no > MAX_INDEX_NO => {
+              self.report(Rule::IndexNumber, line, track_no);
+            }
+            _ => {}
+          }
+        }
+        Command::File { value } => self.current_file = Some(value),
+        Command::Flags { .. } => {
+          if first_index_line.is_some() {
+            self.report(Rule::FlagsAfterIndex, line, track_no);
+          }
+
+          if is_data {
+            self.report(Rule::DataTrackFlags, line, track_no);
+          }
+        }
+        Command::ISRC { .. } if is_data => self.report(Rule::DataTrackIsrc, line, track_no),
+        Command::Pregap { .. } => pregap_line = Some(line),
+        Command::Title { value } => summary.title = Some((line, value)),
+        _ => {}
+      }
+    }
+
+    if let (Some(pregap_line), Some(index_line)) = (pregap_line, pregap_index_line) {
+      self.report(
+        Rule::PregapWithIndex00,
+        pregap_line.max(index_line),
+        track_no,
+      );
+    }
+
+    summary
+  }
+
+  fn first_track(&mut self, track: &TrackSummary<'a>) {
+    let hidden_track = hidden_track_length(
+      track.pregap_index,
+      track.start,
+      track.pregap_file == track.file,
+    );
+
+    if track.start.as_millis() > 0 && hidden_track.is_none() {
+      self.report(
+        Rule::FirstIndexOffset,
+        track.start_line.unwrap_or(track.track_line),
+        Some(track.track_no),
+      );
+    }
+
+    // Pregap audio is only counted when INDEX 00 is in the same file.
+    let pregap_audio = match track.pregap_index {
+      Some(index) if track.pregap_file == track.file => Some(track.start.saturating_sub(index)),
+      _ => None,
+    };
+
+    // Hidden track audio counts as pregap.
+    let pregap = match (track.pregap, pregap_audio) {
+      (None, None) => None,
+      (silence, audio) => Some(
+        silence
+          .unwrap_or_default()
+          .saturating_add(audio.unwrap_or_default()),
+      ),
+    };
+
+    if pregap.is_some_and(|v| v < MIN_FIRST_PREGAP) {
+      self.report(
+        Rule::FirstTrackPregap,
+        track.track_line,
+        Some(track.track_no),
+      );
+    }
+  }
+
+  fn track_number(&mut self, prev: Option<TrackNo>, track: &TrackSummary<'a>) {
+    let track_no = track.track_no.into_inner();
+    let expected = prev.map_or(1, |v| v.into_inner().saturating_add(1));
+
+    if track_no != expected || track_no > MAX_TRACK_COUNT {
+      self.report(Rule::TrackNumber, track.track_line, Some(track.track_no));
+    }
+  }
+
+  /// Track length is only known when the next track starts in the same file, or the track is the
+  /// last one in a file with known length.
+  fn track_length(&mut self, track: &TrackSummary<'a>, next: Option<&TrackSummary<'a>>) {
+    let end = match next {
+      Some(next) if next.pregap_index.is_some() && next.pregap_file == track.file => {
+        next.pregap_index
+      }
+      Some(next) if next.file == track.file => Some(next.start),
+      _ => track
+        .file
+        .and_then(|idx| self.file_lengths.get(idx))
+        .copied(),
+    };
+
+    if let Some(end) = end {
+      let length = end.as_millis().saturating_sub(track.start.as_millis());
+
+      if length < MIN_TRACK_LENGTH {
+        self.report(Rule::ShortTrack, track.track_line, Some(track.track_no));
+      }
+    }
+  }
+
+  /// Records the index in the current file, returns the file position.
+  fn add_index(&mut self, timestamp: CueTimeStamp) -> Option<usize> {
+    let file = self.current_file?;
+    let position = match self.files.iter().position(|v| *v == file) {
+      Some(position) => position,
+      None => {
+        self.files.push(file);
+        self.file_ends.push(CueTimeStamp::ZERO);
+        self.files.len() - 1
+      }
+    };
+
+    self.file_ends[position] = self.file_ends[position].max(timestamp);
+    Some(position)
+  }
+
+  /// Returns the total length of the files and the silences. Files without a known length end
+  /// at their last index, so the result is a lower bound.
+  fn disc_length(&self) -> CueTimeStamp {
+    self
+      .file_ends
+      .iter()
+      .enumerate()
+      .map(|(idx, end)| match self.file_lengths.get(idx) {
+        Some(length) => (*length).max(*end),
+        None => *end,
+      })
+      .fold(self.silence, |total, v| total.saturating_add(v))
+  }
+}
@@ -1,10 +1,11 @@
-mod builder;
+pub(crate) mod builder;
 mod cuesheet;
 
 #[cfg(feature = "metadata")]
 pub mod vorbis_remark;
 
+pub mod extension;
 pub mod remark;
 pub mod track;
 
-pub use cuesheet::CueSheetProbe;
+pub use cuesheet::{CueSheetProbe, ParseOptions};
@@ -4,31 +4,43 @@ use super::{
 };
 use crate::{
   core::{
-    album_file::AlbumFile, cue_str::CueStr, flags::TrackFlag, timestamp::CueTimeStamp, track::Track,
+    album_file::AlbumFile, command::Command, cue_str::CueStr, flags::TrackFlag,
+    timestamp::CueTimeStamp, track::Track,
   },
-  discid::isrc::Isrc,
+  discid::{isrc::Isrc, mcn::Mcn},
   error::ParseErrorKind,
 };
 
 pub struct CueProbeBuilder<'a> {
-  catalog: Option<CueStr<'a>>,
+  arranger: Option<CueStr<'a>>,
+  catalog: Option<Mcn>,
   cdtextfile: Option<CueStr<'a>>,
+  composer: Option<CueStr<'a>>,
+  disc_id: Option<CueStr<'a>>,
   file: Option<AlbumFile<'a>>,
+  genre: Option<CueStr<'a>>,
+  message: Option<CueStr<'a>>,
   performer: Option<CueStr<'a>>,
   songwriter: Option<CueStr<'a>>,
   title: Option<CueStr<'a>>,
   tracks_probe: Option<TrackListProbe<'a>>,
+  upc_ean: Option<CueStr<'a>>,
 }
 
 pub struct TrackProbeBuilder<'a> {
+  arranger: Option<CueStr<'a>>,
+  composer: Option<CueStr<'a>>,
   flags: Option<TrackFlag>,
   isrc: Option<Isrc>,
+  message: Option<CueStr<'a>>,
   performer: Option<CueStr<'a>>,
   postgap: Option<CueTimeStamp>,
   pregap: Option<CueTimeStamp>,
   pregap_index: Option<CueTimeStamp>,
+  pregap_file: Option<AlbumFile<'a>>,
   songwriter: Option<CueStr<'a>>,
   start_index: Option<CueTimeStamp>,
+  start_file: Option<AlbumFile<'a>>,
   sub_index_probe: TrackIndexProbe<'a>,
   title: Option<CueStr<'a>>,
   track: Track,
@@ -38,17 +50,54 @@ impl<'a> CueProbeBuilder<'a> {
   #[inline]
   pub fn new() -> Self {
     Self {
+      arranger: None,
       catalog: None,
       cdtextfile: None,
+      composer: None,
+      disc_id: None,
       file: None,
+      genre: None,
+      message: None,
       performer: None,
       songwriter: None,
       title: None,
       tracks_probe: None,
+      upc_ean: None,
     }
   }
 
-  pub const fn set_catalog(&mut self, catalog: CueStr<'a>) -> Result<(), ParseErrorKind> {
+  /// Applies an album section command. `TRACK` ends the album section and is handled by the
+  /// caller.
+  pub fn apply_command(&mut self, command: Command<'a>) -> Result<(), ParseErrorKind> {
+    match command {
+      Command::Catalog { value } => self.set_catalog(value),
+      Command::CdTextFile { value } => self.set_cdtextfile(value),
+      Command::File { value } => self.set_file(value),
+      Command::Title { value } => self.set_title(value),
+      Command::Performer { value } => self.set_performer(value),
+      Command::SongWriter { value } => self.set_songwriter(value),
+      Command::Arranger { value } => self.set_arranger(value),
+      Command::Composer { value } => self.set_composer(value),
+      Command::Message { value } => self.set_message(value),
+      Command::Genre { value } => self.set_genre(value),
+      Command::DiscId { value } => self.set_disc_id(value),
+      Command::UpcEan { value } => self.set_upc_ean(value),
+      Command::Remark { .. } | Command::Unknown { .. } => Ok(()),
+      _ => Err(ParseErrorKind::InvalidCommandUsage),
+    }
+  }
+
+  #[inline]
+  pub const fn set_arranger(&mut self, arranger: CueStr<'a>) -> Result<(), ParseErrorKind> {
+    if self.arranger.is_some() {
+      return Err(ParseErrorKind::MultipleCommand);
+    }
+
+    self.arranger = Some(arranger);
+    Ok(())
+  }
+
+  pub const fn set_catalog(&mut self, catalog: Mcn) -> Result<(), ParseErrorKind> {
     if self.catalog.is_some() {
       return Err(ParseErrorKind::MultipleCommand);
     }
@@ -67,6 +116,26 @@ impl<'a> CueProbeBuilder<'a> {
     Ok(())
   }
 
+  #[inline]
+  pub const fn set_composer(&mut self, composer: CueStr<'a>) -> Result<(), ParseErrorKind> {
+    if self.composer.is_some() {
+      return Err(ParseErrorKind::MultipleCommand);
+    }
+
+    self.composer = Some(composer);
+    Ok(())
+  }
+
+  #[inline]
+  pub const fn set_disc_id(&mut self, disc_id: CueStr<'a>) -> Result<(), ParseErrorKind> {
+    if self.disc_id.is_some() {
+      return Err(ParseErrorKind::MultipleCommand);
+    }
+
+    self.disc_id = Some(disc_id);
+    Ok(())
+  }
+
   #[inline]
   pub const fn set_file(&mut self, file: AlbumFile<'a>) -> Result<(), ParseErrorKind> {
     if self.file.is_some() {
@@ -77,6 +146,26 @@ impl<'a> CueProbeBuilder<'a> {
     Ok(())
   }
 
+  #[inline]
+  pub const fn set_genre(&mut self, genre: CueStr<'a>) -> Result<(), ParseErrorKind> {
+    if self.genre.is_some() {
+      return Err(ParseErrorKind::MultipleCommand);
+    }
+
+    self.genre = Some(genre);
+    Ok(())
+  }
+
+  #[inline]
+  pub const fn set_message(&mut self, message: CueStr<'a>) -> Result<(), ParseErrorKind> {
+    if self.message.is_some() {
+      return Err(ParseErrorKind::MultipleCommand);
+    }
+
+    self.message = Some(message);
+    Ok(())
+  }
+
   #[inline]
   pub const fn set_performer(&mut self, performer: CueStr<'a>) -> Result<(), ParseErrorKind> {
     if self.performer.is_some() {
@@ -120,20 +209,38 @@ impl<'a> CueProbeBuilder<'a> {
     Ok(())
   }
 
+  #[inline]
+  pub const fn set_upc_ean(&mut self, upc_ean: CueStr<'a>) -> Result<(), ParseErrorKind> {
+    if self.upc_ean.is_some() {
+      return Err(ParseErrorKind::MultipleCommand);
+    }
+
+    self.upc_ean = Some(upc_ean);
+    Ok(())
+  }
+
   pub fn build(self, album_buffer: &'a str) -> Result<CueSheetProbe<'a>, ParseErrorKind> {
-    let tracks_probe = self
+    let mut tracks_probe = self
       .tracks_probe
       .ok_or(ParseErrorKind::MissingTrackCommand)?;
 
+    tracks_probe.set_initial_file(self.file);
+
     let probe = CueSheetProbe {
       album_buffer,
+      arranger: self.arranger,
       catalog: self.catalog,
       cdtextfile: self.cdtextfile,
+      composer: self.composer,
+      disc_id: self.disc_id,
       file: self.file,
+      genre: self.genre,
+      message: self.message,
       performer: self.performer,
       songwriter: self.songwriter,
       title: self.title,
       tracks_probe,
+      upc_ean: self.upc_ean,
     };
 
     Ok(probe)
@@ -146,18 +253,76 @@ impl<'a> TrackProbeBuilder<'a> {
     Self {
       track,
       sub_index_probe: index_probe,
+      arranger: None,
+      composer: None,
       flags: None,
       isrc: None,
+      message: None,
       postgap: None,
       pregap: None,
       performer: None,
       songwriter: None,
       title: None,
       pregap_index: None,
+      pregap_file: None,
       start_index: None,
+      start_file: None,
+    }
+  }
+
+  /// Applies a track section command, `file` is the last declared file and it is updated by
+  /// `FILE` commands. `TRACK` ends the track section and is handled by the caller.
+  pub fn apply_command(
+    &mut self,
+    command: Command<'a>,
+    file: &mut Option<AlbumFile<'a>>,
+  ) -> Result<(), ParseErrorKind> {
+    match command {
+      Command::Index { value } => match value.index_no.into_inner() {
+        0 => self.set_pregap_index(value.timestamp, *file),
+        1 => self.set_start_index(value.timestamp, *file),
+        _ => Ok(()),
+      },
+      Command::File { value } => {
+        // Index timestamps after this point are relative to the new file
+        *file = Some(value);
+        Ok(())
+      }
+      Command::Remark { .. } | Command::Unknown { .. } => Ok(()),
+      Command::Flags { value } => self.set_flags(value),
+      Command::ISRC { value } => self.set_isrc(value),
+      Command::Performer { value } => self.set_performer(value),
+      Command::Postgap { value } => self.set_postgap(value),
+      Command::Pregap { value } => self.set_pregap(value),
+      Command::SongWriter { value } => self.set_songwriter(value),
+      Command::Title { value } => self.set_title(value),
+      Command::Arranger { value } => self.set_arranger(value),
+      Command::Composer { value } => self.set_composer(value),
+      Command::Message { value } => self.set_message(value),
+      _ => Err(ParseErrorKind::InvalidCommandUsage),
     }
   }
 
+  #[inline]
+  pub const fn set_arranger(&mut self, arranger: CueStr<'a>) -> Result<(), ParseErrorKind> {
+    if self.arranger.is_some() {
+      return Err(ParseErrorKind::MultipleCommand);
+    }
+
+    self.arranger = Some(arranger);
+    Ok(())
+  }
+
+  #[inline]
+  pub const fn set_composer(&mut self, composer: CueStr<'a>) -> Result<(), ParseErrorKind> {
+    if self.composer.is_some() {
+      return Err(ParseErrorKind::MultipleCommand);
+    }
+
+    self.composer = Some(composer);
+    Ok(())
+  }
+
   #[inline]
   pub const fn set_flags(&mut self, flags: TrackFlag) -> Result<(), ParseErrorKind> {
     if self.flags.is_some() {
@@ -178,6 +343,16 @@ impl<'a> TrackProbeBuilder<'a> {
     Ok(())
   }
 
+  #[inline]
+  pub const fn set_message(&mut self, message: CueStr<'a>) -> Result<(), ParseErrorKind> {
+    if self.message.is_some() {
+      return Err(ParseErrorKind::MultipleCommand);
+    }
+
+    self.message = Some(message);
+    Ok(())
+  }
+
   #[inline]
   pub const fn set_postgap(&mut self, postgap: CueTimeStamp) -> Result<(), ParseErrorKind> {
     if self.postgap.is_some() {
@@ -219,12 +394,17 @@ impl<'a> TrackProbeBuilder<'a> {
   }
 
   #[inline]
-  pub const fn set_start_index(&mut self, start_index: CueTimeStamp) -> Result<(), ParseErrorKind> {
+  pub const fn set_start_index(
+    &mut self,
+    start_index: CueTimeStamp,
+    file: Option<AlbumFile<'a>>,
+  ) -> Result<(), ParseErrorKind> {
     if self.start_index.is_some() {
       return Err(ParseErrorKind::MultipleCommand);
     }
 
     self.start_index = Some(start_index);
+    self.start_file = file;
     Ok(())
   }
 
@@ -232,6 +412,7 @@ impl<'a> TrackProbeBuilder<'a> {
   pub const fn set_pregap_index(
     &mut self,
     pregap_index: CueTimeStamp,
+    file: Option<AlbumFile<'a>>,
   ) -> Result<(), ParseErrorKind> {
     // Pregap index (INDEX 00) must be set before the start index (INDEX 01)
     if self.start_index.is_some() {
@@ -243,6 +424,7 @@ impl<'a> TrackProbeBuilder<'a> {
     }
 
     self.pregap_index = Some(pregap_index);
+    self.pregap_file = file;
     Ok(())
   }
 
@@ -260,12 +442,17 @@ impl<'a> TrackProbeBuilder<'a> {
     let start_index = self.start_index.ok_or(ParseErrorKind::InvalidTrackIndex)?;
 
     let probe = TrackProbe {
+      arranger: self.arranger,
+      composer: self.composer,
+      file: self.start_file,
       flags: self.flags,
       isrc: self.isrc,
+      message: self.message,
       performer: self.performer,
       postgap: self.postgap,
       pregap: self.pregap,
       pregap_index: self.pregap_index,
+      pregap_file: self.pregap_file,
       songwriter: self.songwriter,
       start_index,
       sub_index_probe: self.sub_index_probe,
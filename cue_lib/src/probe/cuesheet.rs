@@ -1,22 +1,24 @@
 use super::{
   builder::CueProbeBuilder,
+  extension::{CommandExtension, Extensions},
   remark::RemarkIter,
-  track::{TrackListProbe, Tracks},
+  track::{TrackListProbe, Tracks, hidden_track_length},
 };
 use crate::{
-  core::{album_file::AlbumFile, command::Command, cue_str::CueStr},
-  error::{CueLibError, ParseError, ParseErrorKind},
+  core::{album_file::AlbumFile, command::Command, cue_str::CueStr, timestamp::CueTimeStamp},
+  discid::mcn::Mcn,
+  error::{CueLibError, ParseError},
   internal::{lexer::CueLexer, tokenizer::Tokenizer},
 };
 
 pub struct CueSheetProbe<'a> {
   /// Catalog number for the release (CATALOG command)
-  pub(super) catalog: Option<CueStr<'a>>,
+  pub(super) catalog: Option<Mcn>,
 
   /// CD-TEXT file name (CDTEXTFILE command)
   pub(super) cdtextfile: Option<CueStr<'a>>,
 
-  /// Main audio file associated with the album (FILE command)
+  /// First data/audio file declared before the first track (FILE command)
   pub(super) file: Option<AlbumFile<'a>>,
 
   /// Performer name for the entire album (PERFORMER command)
@@ -28,6 +30,24 @@ pub struct CueSheetProbe<'a> {
   /// Album title (TITLE command)
   pub(super) title: Option<CueStr<'a>>,
 
+  /// Arranger name for the entire album (ARRANGER command)
+  pub(super) arranger: Option<CueStr<'a>>,
+
+  /// Composer name for the entire album (COMPOSER command)
+  pub(super) composer: Option<CueStr<'a>>,
+
+  /// Album message (MESSAGE command)
+  pub(super) message: Option<CueStr<'a>>,
+
+  /// Album genre (GENRE command)
+  pub(super) genre: Option<CueStr<'a>>,
+
+  /// Disc identification information (DISC_ID command)
+  pub(super) disc_id: Option<CueStr<'a>>,
+
+  /// Album UPC/EAN code (UPC_EAN command)
+  pub(super) upc_ean: Option<CueStr<'a>>,
+
   /// Collection of track-level probe data
   pub(super) tracks_probe: TrackListProbe<'a>,
 
@@ -35,33 +55,56 @@ pub struct CueSheetProbe<'a> {
   pub(super) album_buffer: &'a str,
 }
 
+/// Cuesheet parsing options.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ParseOptions {
+  unknown_commands: bool,
+}
+
+impl ParseOptions {
+  #[inline]
+  pub const fn new() -> Self {
+    Self {
+      unknown_commands: false,
+    }
+  }
+
+  /// Skips unknown commands instead of failing. Use
+  /// [CueSheetProbe::extensions](CueSheetProbe::extensions) and
+  /// [TrackProbe::extensions](crate::probe::track::TrackProbe::extensions) to read them.
+  #[inline]
+  pub const fn set_unknown_commands(mut self, value: bool) -> Self {
+    self.unknown_commands = value;
+    self
+  }
+}
+
 impl<'a> CueSheetProbe<'a> {
+  #[inline]
   pub fn new(cuesheet: &'a str) -> Result<Self, CueLibError> {
+    Self::new_with_options(cuesheet, ParseOptions::new())
+  }
+
+  pub fn new_with_options(cuesheet: &'a str, options: ParseOptions) -> Result<Self, CueLibError> {
     let tokenizer = Tokenizer::new(cuesheet);
     let mut lexer = CueLexer::new(tokenizer);
+    lexer.set_unknown_commands(options.unknown_commands);
     let mut builder = CueProbeBuilder::new();
     let mut album_buffer_end = 0;
 
     'PARSER: while let Some(command) = lexer.next_command()? {
-      match command {
-        Command::Catalog { value } => builder.set_catalog(value),
-        Command::CdTextFile { value } => builder.set_cdtextfile(value),
-        Command::File { value } => builder.set_file(value),
-        Command::Title { value } => builder.set_title(value),
-        Command::Performer { value } => builder.set_performer(value),
-        Command::SongWriter { value } => builder.set_songwriter(value),
-        Command::Remark { .. } => Ok(()),
-        Command::Track { value } => {
-          // exit condition
-          builder
-            .set_tracks_probe(TrackListProbe::new(lexer.snapshot(), value))
-            .map_err(|kind| ParseError::new_with_position(kind, lexer.position()))?;
-
-          break 'PARSER;
-        }
-        _ => Err(ParseErrorKind::InvalidCommandUsage),
+      if let Command::Track { value } = command {
+        // exit condition
+        builder
+          .set_tracks_probe(TrackListProbe::new(lexer.snapshot(), value))
+          .map_err(|kind| ParseError::new_with_position(kind, lexer.position()))?;
+
+        break 'PARSER;
       }
-      .map_err(|kind| ParseError::new_with_line(kind, lexer.position().line))?;
+
+      builder
+        .apply_command(command)
+        .map_err(|kind| ParseError::new_with_line(kind, lexer.position().line))?;
 
       album_buffer_end = lexer.cursor_position();
     }
@@ -96,6 +139,20 @@ impl<'a> CueSheetProbe<'a> {
     Ok(())
   }
 
+  /// Verifies the cuesheet and reports every problem instead of stopping at the first one.
+  ///
+  /// See [CueSheet::parse_recovering](crate::document::CueSheet::parse_recovering).
+  #[cfg(feature = "alloc")]
+  pub fn verify_all(cuesheet: &str) -> Result<(), alloc::vec::Vec<crate::error::Diagnostic>> {
+    let (_, diagnostics) = crate::document::CueSheet::parse_recovering(cuesheet);
+
+    if diagnostics.is_empty() {
+      Ok(())
+    } else {
+      Err(diagnostics)
+    }
+  }
+
   /// Returns a reference to the album title if present.
   #[inline]
   pub const fn album_title(&self) -> Option<CueStr<'a>> {
@@ -108,9 +165,9 @@ impl<'a> CueSheetProbe<'a> {
     self.performer
   }
 
-  /// Returns a reference to the catalog number if present.
+  /// Returns the media catalog number if present.
   #[inline]
-  pub const fn catalog(&self) -> Option<CueStr<'a>> {
+  pub const fn catalog(&self) -> Option<Mcn> {
     self.catalog
   }
 
@@ -120,6 +177,42 @@ impl<'a> CueSheetProbe<'a> {
     self.songwriter
   }
 
+  /// Returns a reference to the arranger name if present.
+  #[inline]
+  pub const fn arranger(&self) -> Option<CueStr<'a>> {
+    self.arranger
+  }
+
+  /// Returns a reference to the composer name if present.
+  #[inline]
+  pub const fn composer(&self) -> Option<CueStr<'a>> {
+    self.composer
+  }
+
+  /// Returns a reference to the album message if present.
+  #[inline]
+  pub const fn message(&self) -> Option<CueStr<'a>> {
+    self.message
+  }
+
+  /// Returns a reference to the genre if present.
+  #[inline]
+  pub const fn genre(&self) -> Option<CueStr<'a>> {
+    self.genre
+  }
+
+  /// Returns a reference to the disc identification if present.
+  #[inline]
+  pub const fn disc_id(&self) -> Option<CueStr<'a>> {
+    self.disc_id
+  }
+
+  /// Returns a reference to the UPC/EAN code if present.
+  #[inline]
+  pub const fn upc_ean(&self) -> Option<CueStr<'a>> {
+    self.upc_ean
+  }
+
   /// Returns a reference to the CD-TEXT file name if present.
   #[inline]
   pub const fn cdtextfile(&self) -> Option<CueStr<'a>> {
@@ -127,6 +220,9 @@ impl<'a> CueSheetProbe<'a> {
   }
 
   /// Returns a reference to the main audio file information if present.
+  ///
+  /// Cuesheets with multiple files only report the file declared before the first track here, use
+  /// [TrackProbe::file](crate::probe::track::TrackProbe::file) for the per-track files.
   #[inline]
   pub const fn file_info(&self) -> Option<AlbumFile<'a>> {
     self.file
@@ -138,12 +234,51 @@ impl<'a> CueSheetProbe<'a> {
     self.tracks_probe.iter()
   }
 
+  /// Returns the length of the hidden track one audio (HTOA).
+  ///
+  /// Hidden track is the audio between the first track's INDEX 00 at 00:00:00 and its INDEX 01
+  /// in the same file.
+  pub fn hidden_track(&self) -> Result<Option<CueTimeStamp>, CueLibError> {
+    let Some(track) = self.tracks().next_track()? else {
+      return Ok(None);
+    };
+
+    Ok(hidden_track_length(
+      track.pregap_index(),
+      track.start_index(),
+      track.pregap_file() == track.file(),
+    ))
+  }
+
   /// Returns an iterator over the remarks in the album portion of the cuesheet.
   #[inline]
   pub const fn remarks(&self) -> RemarkIter<'a> {
     RemarkIter::new(self.album_buffer)
   }
 
+  /// Returns an iterator over the album level commands parsed by the extension.
+  #[inline]
+  pub fn extensions<E>(&self, extension: E) -> Extensions<'a, E>
+  where
+    E: CommandExtension<'a>,
+  {
+    Extensions::new(self.album_buffer, extension)
+  }
+
+  /// Complete cuesheet buffer.
+  #[cfg(feature = "alloc")]
+  #[inline]
+  pub(crate) const fn source(&self) -> &'a str {
+    self.tracks_probe.source()
+  }
+
+  /// Slice containing the complete album portion of the cuesheet.
+  #[cfg(feature = "alloc")]
+  #[inline]
+  pub(crate) const fn buffer(&self) -> &'a str {
+    self.album_buffer
+  }
+
   /// Returns an iterator over the vorbis metadata remarks in the album portion of the cuesheet.
   #[cfg(feature = "metadata")]
   #[inline]
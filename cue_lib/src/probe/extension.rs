@@ -0,0 +1,66 @@
+use crate::{
+  core::command::Command,
+  internal::{lexer::CueLexer, tokenizer::Tokenizer},
+};
+
+/// Parser for commands which are not part of the cuesheet format, such as `CDDBID` or
+/// `TOC_INFO1`.
+///
+/// Closures with `Fn(name, args) -> Option<T>` signature also implement this trait.
+pub trait CommandExtension<'a> {
+  type Output;
+
+  /// Parses the command, returns `None` for the commands not handled by the extension.
+  ///
+  /// `args` is the raw text after the command name.
+  fn parse(&self, name: &'a str, args: &'a str) -> Option<Self::Output>;
+}
+
+impl<'a, F, T> CommandExtension<'a> for F
+where
+  F: Fn(&'a str, &'a str) -> Option<T>,
+{
+  type Output = T;
+
+  #[inline]
+  fn parse(&self, name: &'a str, args: &'a str) -> Option<Self::Output> {
+    self(name, args)
+  }
+}
+
+/// Iterator over the unknown commands handled by a [CommandExtension].
+pub struct Extensions<'a, E> {
+  lexer: CueLexer<'a>,
+  extension: E,
+}
+
+impl<'a, E> Extensions<'a, E> {
+  pub(super) fn new(buffer: &'a str, extension: E) -> Self {
+    let mut lexer = CueLexer::new(Tokenizer::new(buffer));
+    lexer.set_unknown_commands(true);
+
+    Self { lexer, extension }
+  }
+}
+
+impl<'a, E> Iterator for Extensions<'a, E>
+where
+  E: CommandExtension<'a>,
+{
+  type Item = E::Output;
+
+  fn next(&mut self) -> Option<Self::Item> {
+    loop {
+      match self.lexer.next_command() {
+        Ok(Some(Command::Unknown { name, args })) => {
+          if let Some(value) = self.extension.parse(name, args) {
+            return Some(value);
+          }
+        }
+        Ok(Some(_)) => continue,
+        Ok(None) => return None,
+        Err(_) => self.lexer.skip_line(),
+      }
+    }
+  }
+}
@@ -1,15 +1,75 @@
-use crate::internal::{lexer::CueLexer, tokenizer::Tokenizer};
+use crate::{
+  core::cue_str::CueStr,
+  internal::{lexer::CueLexer, tokenizer::Tokenizer},
+};
 
 pub struct RemarkIter<'a> {
   lexer: CueLexer<'a>,
 }
 
+/// Iterator over the remarks parsed as [Remark].
+pub struct TypedRemarkIter<'a> {
+  inner: RemarkIter<'a>,
+}
+
+/// Remark fields used by EAC, foobar2000 and similar rippers.
+///
+/// Known fields with invalid values are returned as [Remark::Other].
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum Remark<'a> {
+  /// `COMMENT`
+  Comment(CueStr<'a>),
+
+  /// `DATE`
+  Date(RemarkDate),
+
+  /// `DISCID`, CDDB disc id
+  DiscId(u32),
+
+  /// `DISCNUMBER`
+  DiscNumber(u32),
+
+  /// `GENRE`
+  Genre(CueStr<'a>),
+
+  /// `REPLAYGAIN_ALBUM_GAIN` in dB
+  ReplayGainAlbumGain(f32),
+
+  /// `REPLAYGAIN_ALBUM_PEAK`
+  ReplayGainAlbumPeak(f32),
+
+  /// `REPLAYGAIN_TRACK_GAIN` in dB
+  ReplayGainTrackGain(f32),
+
+  /// `REPLAYGAIN_TRACK_PEAK`
+  ReplayGainTrackPeak(f32),
+
+  /// `TOTALDISCS`
+  TotalDiscs(u32),
+
+  /// Any other remark as key and value. Unquoted values are kept as is, including the
+  /// whitespaces between words.
+  Other { key: &'a str, value: CueStr<'a> },
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum RemarkDate {
+  Year(u16),
+  Full { year: u16, month: u8, day: u8 },
+}
+
 impl<'a> RemarkIter<'a> {
   pub(super) const fn new(buffer: &'a str) -> Self {
     Self {
       lexer: CueLexer::new(Tokenizer::new(buffer)),
     }
   }
+
+  /// Parses the remarks as [Remark].
+  #[inline]
+  pub const fn typed(self) -> TypedRemarkIter<'a> {
+    TypedRemarkIter { inner: self }
+  }
 }
 
 impl<'a> Iterator for RemarkIter<'a> {
@@ -22,8 +82,162 @@ impl<'a> Iterator for RemarkIter<'a> {
           return Some(value);
         }
         Ok(None) => return None,
-        _ => continue,
+        Ok(_) => continue,
+        Err(_) => self.lexer.skip_line(),
+      }
+    }
+  }
+}
+
+impl<'a> Iterator for TypedRemarkIter<'a> {
+  type Item = Remark<'a>;
+
+  #[inline]
+  fn next(&mut self) -> Option<Self::Item> {
+    self.inner.next().map(Remark::parse)
+  }
+}
+
+impl<'a> Remark<'a> {
+  /// Parses the remark text after the `REM` keyword.
+  pub fn parse(remark: &'a str) -> Self {
+    let remark = remark.trim();
+    let (key, value) = match remark.split_once(char::is_whitespace) {
+      Some((key, value)) => (key, value.trim_start()),
+      None => (remark, ""),
+    };
+
+    let value = CueStr::from_raw_str(value).unwrap_or(CueStr::Text(value));
+    let typed = match known_key(key) {
+      Some("COMMENT") => Some(Remark::Comment(value)),
+      Some("DATE") => parse_date(value).map(Remark::Date),
+      Some("DISCID") => parse_disc_id(value).map(Remark::DiscId),
+      Some("DISCNUMBER") => unquoted(value).parse().ok().map(Remark::DiscNumber),
+      Some("GENRE") => Some(Remark::Genre(value)),
+      Some("REPLAYGAIN_ALBUM_GAIN") => parse_gain(value).map(Remark::ReplayGainAlbumGain),
+      Some("REPLAYGAIN_ALBUM_PEAK") => unquoted(value)
+        .parse()
+        .ok()
+        .map(Remark::ReplayGainAlbumPeak),
+      Some("REPLAYGAIN_TRACK_GAIN") => parse_gain(value).map(Remark::ReplayGainTrackGain),
+      Some("REPLAYGAIN_TRACK_PEAK") => unquoted(value)
+        .parse()
+        .ok()
+        .map(Remark::ReplayGainTrackPeak),
+      Some("TOTALDISCS") => unquoted(value).parse().ok().map(Remark::TotalDiscs),
+      _ => None,
+    };
+
+    typed.unwrap_or(Remark::Other { key, value })
+  }
+
+  /// Returns the field name. Known fields are returned in upper case.
+  pub const fn key(&self) -> &'a str {
+    match self {
+      Remark::Comment(_) => "COMMENT",
+      Remark::Date(_) => "DATE",
+      Remark::DiscId(_) => "DISCID",
+      Remark::DiscNumber(_) => "DISCNUMBER",
+      Remark::Genre(_) => "GENRE",
+      Remark::ReplayGainAlbumGain(_) => "REPLAYGAIN_ALBUM_GAIN",
+      Remark::ReplayGainAlbumPeak(_) => "REPLAYGAIN_ALBUM_PEAK",
+      Remark::ReplayGainTrackGain(_) => "REPLAYGAIN_TRACK_GAIN",
+      Remark::ReplayGainTrackPeak(_) => "REPLAYGAIN_TRACK_PEAK",
+      Remark::TotalDiscs(_) => "TOTALDISCS",
+      Remark::Other { key, .. } => key,
+    }
+  }
+}
+
+impl core::fmt::Display for RemarkDate {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    match self {
+      RemarkDate::Year(year) => f.write_fmt(format_args!("{year:04}")),
+      RemarkDate::Full { year, month, day } => {
+        f.write_fmt(format_args!("{year:04}-{month:02}-{day:02}"))
+      }
+    }
+  }
+}
+
+/// Field names are matched case-insensitively.
+fn known_key(key: &str) -> Option<&'static str> {
+  const KEYS: [&str; 10] = [
+    "COMMENT",
+    "DATE",
+    "DISCID",
+    "DISCNUMBER",
+    "GENRE",
+    "REPLAYGAIN_ALBUM_GAIN",
+    "REPLAYGAIN_ALBUM_PEAK",
+    "REPLAYGAIN_TRACK_GAIN",
+    "REPLAYGAIN_TRACK_PEAK",
+    "TOTALDISCS",
+  ];
+
+  KEYS.into_iter().find(|v| v.eq_ignore_ascii_case(key))
+}
+
+/// Returns the text of unescaped values, numeric fields never have escape sequences.
+#[inline]
+fn unquoted(value: CueStr<'_>) -> &str {
+  match value {
+    CueStr::Text(v) => v,
+    CueStr::QuotedText(v) => &v[1..(v.len() - 1)],
+    CueStr::QuotedTextWithEscape(_) => "",
+  }
+}
+
+fn parse_date(value: CueStr<'_>) -> Option<RemarkDate> {
+  let value = unquoted(value);
+  let mut parts = value.split('-');
+  let year = parse_digits(parts.next()?, 4)?;
+
+  match (parts.next(), parts.next(), parts.next()) {
+    (None, None, None) => Some(RemarkDate::Year(year)),
+    (Some(month), Some(day), None) => {
+      let month = parse_digits(month, 2)? as u8;
+      let day = parse_digits(day, 2)? as u8;
+
+      if (1..=12).contains(&month) && (1..=31).contains(&day) {
+        Some(RemarkDate::Full { year, month, day })
+      } else {
+        None
       }
     }
+    _ => None,
   }
 }
+
+#[inline]
+fn parse_digits(value: &str, len: usize) -> Option<u16> {
+  if value.len() == len && value.bytes().all(|v| v.is_ascii_digit()) {
+    value.parse().ok()
+  } else {
+    None
+  }
+}
+
+#[inline]
+fn parse_disc_id(value: CueStr<'_>) -> Option<u32> {
+  let value = unquoted(value);
+
+  if value.len() == 8 && value.bytes().all(|v| v.is_ascii_hexdigit()) {
+    u32::from_str_radix(value, 16).ok()
+  } else {
+    None
+  }
+}
+
+/// Parses gain values such as `-7.89 dB`, unit suffix is optional.
+fn parse_gain(value: CueStr<'_>) -> Option<f32> {
+  let value = unquoted(value).trim();
+  let value = match value.len().checked_sub(2) {
+    Some(idx) if value.is_char_boundary(idx) && value[idx..].eq_ignore_ascii_case("db") => {
+      value[..idx].trim_end()
+    }
+    _ => value,
+  };
+
+  value.parse().ok()
+}
@@ -1,6 +1,10 @@
-use super::remark::RemarkIter;
+use super::{
+  extension::{CommandExtension, Extensions},
+  remark::RemarkIter,
+};
 use crate::{
   core::{
+    album_file::AlbumFile,
     command::Command,
     cue_str::CueStr,
     flags::TrackFlag,
@@ -17,11 +21,13 @@ use crate::{
 pub struct TrackListProbe<'a> {
   lexer: CueLexer<'a>,
   initial_track: Track,
+  initial_file: Option<AlbumFile<'a>>,
 }
 
 #[derive(Clone)]
 pub struct TrackIndexProbe<'a> {
   lexer: CueLexer<'a>,
+  file: Option<AlbumFile<'a>>,
 }
 
 #[derive(Clone)]
@@ -50,6 +56,15 @@ pub struct TrackProbe<'a> {
   /// Track title (TITLE command)
   pub(super) title: Option<CueStr<'a>>,
 
+  /// Arranger name for this specific track (ARRANGER command)
+  pub(super) arranger: Option<CueStr<'a>>,
+
+  /// Composer name for this specific track (COMPOSER command)
+  pub(super) composer: Option<CueStr<'a>>,
+
+  /// Track message (MESSAGE command)
+  pub(super) message: Option<CueStr<'a>>,
+
   /// Sub-index probe for the track
   pub(super) sub_index_probe: TrackIndexProbe<'a>,
 
@@ -59,6 +74,12 @@ pub struct TrackProbe<'a> {
   /// Optional timestamp for pregap segment exist in the track file (INDEX 00)
   pub(super) pregap_index: Option<CueTimeStamp>,
 
+  /// File containing the track start (INDEX 01)
+  pub(super) file: Option<AlbumFile<'a>>,
+
+  /// File containing the pregap segment (INDEX 00), it might be the previous track's file
+  pub(super) pregap_file: Option<AlbumFile<'a>>,
+
   /// Slice containing the complete track portion of the cuesheet
   pub(super) track_buffer: &'a str,
 }
@@ -66,11 +87,20 @@ pub struct TrackProbe<'a> {
 pub struct Tracks<'a> {
   lexer: CueLexer<'a>,
   track: Option<Track>,
+  file: Option<AlbumFile<'a>>,
 }
 
 pub struct TrackSubIndexes<'a> {
   lexer: CueLexer<'a>,
+  index_order: IndexOrder<'a>,
+  current_file: Option<AlbumFile<'a>>,
+}
+
+/// Index order of a track, indexes must be sequential and start with INDEX 00 or INDEX 01.
+#[derive(Clone, Copy)]
+pub(crate) struct IndexOrder<'a> {
   prev_index: Option<TrackIndex>,
+  index_file: Option<AlbumFile<'a>>,
 }
 
 impl<'a> TrackProbe<'a> {
@@ -124,6 +154,21 @@ impl<'a> TrackProbe<'a> {
     self.title
   }
 
+  #[inline]
+  pub const fn arranger(&self) -> Option<CueStr<'a>> {
+    self.arranger
+  }
+
+  #[inline]
+  pub const fn composer(&self) -> Option<CueStr<'a>> {
+    self.composer
+  }
+
+  #[inline]
+  pub const fn message(&self) -> Option<CueStr<'a>> {
+    self.message
+  }
+
   #[inline]
   pub const fn sub_indexes(&self) -> TrackSubIndexes<'a> {
     self.sub_index_probe.iter()
@@ -139,11 +184,41 @@ impl<'a> TrackProbe<'a> {
     self.pregap_index
   }
 
+  /// Returns the file which the track start (INDEX 01) timestamp is relative to.
+  #[inline]
+  pub const fn file(&self) -> Option<AlbumFile<'a>> {
+    self.file
+  }
+
+  /// Returns the file which the pregap (INDEX 00) timestamp is relative to.
+  ///
+  /// For "one file per track" layouts this is usually the previous track's file.
+  #[inline]
+  pub const fn pregap_file(&self) -> Option<AlbumFile<'a>> {
+    self.pregap_file
+  }
+
   #[inline]
   pub fn remarks(&self) -> RemarkIter<'a> {
     RemarkIter::new(self.track_buffer)
   }
 
+  /// Returns an iterator over the track level commands parsed by the extension.
+  #[inline]
+  pub fn extensions<E>(&self, extension: E) -> Extensions<'a, E>
+  where
+    E: CommandExtension<'a>,
+  {
+    Extensions::new(self.track_buffer, extension)
+  }
+
+  /// Slice containing the complete track portion of the cuesheet.
+  #[cfg(feature = "alloc")]
+  #[inline]
+  pub(crate) const fn buffer(&self) -> &'a str {
+    self.track_buffer
+  }
+
   #[cfg(feature = "metadata")]
   #[inline]
   pub fn vorbis_comments(&self) -> crate::probe::vorbis_remark::VorbisRemarkIter<'a> {
@@ -157,6 +232,7 @@ impl<'a> TrackListProbe<'a> {
     Tracks {
       lexer: self.lexer.snapshot(),
       track: Some(self.initial_track),
+      file: self.initial_file,
     }
   }
 
@@ -165,16 +241,36 @@ impl<'a> TrackListProbe<'a> {
     Self {
       lexer,
       initial_track,
+      initial_file: None,
     }
   }
+
+  /// Complete cuesheet buffer.
+  #[cfg(feature = "alloc")]
+  #[inline]
+  pub(super) const fn source(&self) -> &'a str {
+    self.lexer.as_raw_buffer()
+  }
+
+  /// Sets the file declared in the album section, which the first track belongs to.
+  #[inline]
+  pub(super) const fn set_initial_file(&mut self, file: Option<AlbumFile<'a>>) {
+    self.initial_file = file;
+  }
 }
 
 impl<'a> TrackIndexProbe<'a> {
+  #[inline]
+  pub(crate) const fn new(lexer: CueLexer<'a>, file: Option<AlbumFile<'a>>) -> Self {
+    Self { lexer, file }
+  }
+
   #[inline]
   const fn iter(&self) -> TrackSubIndexes<'a> {
     TrackSubIndexes {
       lexer: self.lexer.snapshot(),
-      prev_index: None,
+      index_order: IndexOrder::new(self.file),
+      current_file: self.file,
     }
   }
 }
@@ -182,42 +278,26 @@ impl<'a> TrackIndexProbe<'a> {
 impl<'a> Tracks<'a> {
   pub fn next_track(&mut self) -> Result<Option<TrackProbe<'a>>, CueLibError> {
     if let Some(curr_track) = self.track {
-      let index_probe = TrackIndexProbe {
-        lexer: self.lexer.snapshot(),
-      };
+      let index_probe = TrackIndexProbe::new(self.lexer.snapshot(), self.file);
       let mut builder = TrackProbeBuilder::new(index_probe, curr_track);
       let track_buf_start = self.lexer.cursor_position();
       let mut track_buf_end = track_buf_start;
 
       'PARSER: loop {
         match self.lexer.next_command()? {
-          Some(Command::Index { value }) => match value.index_no.into_inner() {
-            0 => builder.set_pregap_index(value.timestamp),
-            1 => builder.set_start_index(value.timestamp),
-            _ => Ok(()),
-          },
-          Some(Command::Remark { .. }) => Ok(()),
-          Some(Command::Flags { value }) => builder.set_flags(value),
-          Some(Command::ISRC { value }) => builder.set_isrc(value),
-          Some(Command::Performer { value }) => builder.set_performer(value),
-          Some(Command::Postgap { value }) => builder.set_postgap(value),
-          Some(Command::Pregap { value }) => builder.set_pregap(value),
-          Some(Command::SongWriter { value }) => builder.set_songwriter(value),
-          Some(Command::Title { value }) => builder.set_title(value),
           Some(Command::Track { value }) => {
-            // Track no's must be sequential
-            if curr_track.track_no.saturating_add(1) == value.track_no {
+            if let Err(kind) = check_track_no(curr_track.track_no, value.track_no) {
+              Err(kind)
+            } else {
               self.track = Some(value);
               break 'PARSER;
-            } else {
-              Err(ParseErrorKind::InvalidTrackNo)
             }
           }
+          Some(command) => builder.apply_command(command, &mut self.file),
           None => {
             self.track = None;
             break 'PARSER;
           }
-          Some(_) => Err(ParseErrorKind::InvalidCommandUsage),
         }
         .map_err(|kind| ParseError::new_with_line(kind, self.lexer.position().line))?;
 
@@ -237,38 +317,95 @@ impl<'a> Tracks<'a> {
 }
 
 impl<'a> TrackSubIndexes<'a> {
+  /// Returns the file which the last returned index timestamp is relative to.
+  #[inline]
+  pub const fn file(&self) -> Option<AlbumFile<'a>> {
+    self.index_order.index_file
+  }
+
   pub fn next_index(&mut self) -> Result<Option<TrackIndex>, CueLibError> {
     loop {
       match self.lexer.next_command()? {
         Some(Command::Index { value }) => {
-          let is_valid = match self.prev_index {
-            Some(prev) => {
-              value.index_no == prev.index_no.saturating_add(1) && value.timestamp >= prev.timestamp
-            }
-            None => value.index_no.into_inner() <= 1,
-          };
-
-          if is_valid {
-            self.prev_index = Some(value);
-
-            match value.index_no.into_inner() {
-              0 | 1 => continue,
-              _ => return Ok(Some(value)),
-            };
-          } else {
-            let parse_error = ParseError::new_with_line(
-              ParseErrorKind::InvalidTrackIndex,
-              self.lexer.position().line,
-            );
-
+          if let Err(kind) = self.index_order.push(value, self.current_file) {
+            let parse_error = ParseError::new_with_line(kind, self.lexer.position().line);
             return Err(parse_error.into());
           }
+
+          match value.index_no.into_inner() {
+            0 | 1 => continue,
+            _ => return Ok(Some(value)),
+          };
         }
         Some(Command::Track { .. }) | None => {
           return Ok(None);
         }
+        Some(Command::File { value }) => {
+          self.current_file = Some(value);
+        }
         Some(_) => continue,
       }
     }
   }
 }
+
+impl<'a> IndexOrder<'a> {
+  #[inline]
+  pub(crate) const fn new(file: Option<AlbumFile<'a>>) -> Self {
+    Self {
+      prev_index: None,
+      index_file: file,
+    }
+  }
+
+  /// Accepts the next index of the track, `file` is the file its timestamp is relative to.
+  pub(crate) fn push(
+    &mut self,
+    value: TrackIndex,
+    file: Option<AlbumFile<'a>>,
+  ) -> Result<(), ParseErrorKind> {
+    let is_valid = match self.prev_index {
+      // Timestamps are only comparable when both indexes are in the same file
+      Some(prev) if self.index_file == file => {
+        value.index_no == prev.index_no.saturating_add(1) && value.timestamp >= prev.timestamp
+      }
+      Some(prev) => value.index_no == prev.index_no.saturating_add(1),
+      None => value.index_no.into_inner() <= 1,
+    };
+
+    if is_valid {
+      self.prev_index = Some(value);
+      self.index_file = file;
+      Ok(())
+    } else {
+      Err(ParseErrorKind::InvalidTrackIndex)
+    }
+  }
+}
+
+/// Track no's must be sequential.
+#[inline]
+pub(crate) fn check_track_no(prev: TrackNo, next: TrackNo) -> Result<(), ParseErrorKind> {
+  if prev.saturating_add(1) == next {
+    Ok(())
+  } else {
+    Err(ParseErrorKind::InvalidTrackNo)
+  }
+}
+
+/// Returns the length of the hidden track one audio (HTOA) from the first track's indexes.
+///
+/// Hidden track is the audio between INDEX 00 at 00:00:00 and INDEX 01 in the same file.
+#[inline]
+pub(crate) fn hidden_track_length(
+  pregap_index: Option<CueTimeStamp>,
+  start_index: CueTimeStamp,
+  is_same_file: bool,
+) -> Option<CueTimeStamp> {
+  match pregap_index {
+    Some(CueTimeStamp::ZERO) if is_same_file && start_index > CueTimeStamp::ZERO => {
+      Some(start_index)
+    }
+    _ => None,
+  }
+}
@@ -6,7 +6,12 @@ use crate::{
     timestamp::CueTimeStamp,
     track::{DataType, IndexNo, Track, TrackIndex, TrackNo},
   },
-  discid::{ean::Ean13, isrc::Isrc, upc::UpcA},
+  discid::{
+    accuraterip::AccurateRipId, cddb::CddbDiscId, ctdb::CtdbTocId, ean::Ean13, isrc::Isrc,
+    mcn::Mcn, musicbrainz::MusicBrainzDiscId, upc::UpcA,
+  },
+  document,
+  image::iso9660::{PrimaryVolume, VolumeDate},
   metadata::{VorbisComment, VorbisTagName},
 };
 use alloc::borrow::Cow;
@@ -55,6 +60,53 @@ impl Serialize for UpcA {
   }
 }
 
+impl Serialize for Mcn {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: serde::Serializer,
+  {
+    let ascii_bytes = self.as_ascii_bytes();
+    let mcn_str = unsafe { core::str::from_utf8_unchecked(&ascii_bytes) };
+    serializer.serialize_str(mcn_str)
+  }
+}
+
+impl Serialize for CddbDiscId {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: serde::Serializer,
+  {
+    serializer.collect_str(self)
+  }
+}
+
+impl Serialize for MusicBrainzDiscId {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: serde::Serializer,
+  {
+    serializer.serialize_str(self.as_str())
+  }
+}
+
+impl Serialize for AccurateRipId {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: serde::Serializer,
+  {
+    serializer.collect_str(self)
+  }
+}
+
+impl Serialize for CtdbTocId {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: serde::Serializer,
+  {
+    serializer.serialize_str(self.as_str())
+  }
+}
+
 impl Serialize for TrackNo {
   fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
   where
@@ -165,3 +217,100 @@ impl<'a> Serialize for AlbumFile<'a> {
     obj.end()
   }
 }
+
+impl Serialize for document::CueSheet {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: serde::Serializer,
+  {
+    let mut obj = serializer.serialize_struct("CueSheet", 14)?;
+    obj.serialize_field("arranger", &self.arranger)?;
+    obj.serialize_field("catalog", &self.catalog)?;
+    obj.serialize_field("cdtextfile", &self.cdtextfile)?;
+    obj.serialize_field("composer", &self.composer)?;
+    obj.serialize_field("disc_id", &self.disc_id)?;
+    obj.serialize_field("files", &self.files)?;
+    obj.serialize_field("genre", &self.genre)?;
+    obj.serialize_field("message", &self.message)?;
+    obj.serialize_field("performer", &self.performer)?;
+    obj.serialize_field("remarks", &self.remarks)?;
+    obj.serialize_field("songwriter", &self.songwriter)?;
+    obj.serialize_field("title", &self.title)?;
+    obj.serialize_field("tracks", &self.tracks)?;
+    obj.serialize_field("upc_ean", &self.upc_ean)?;
+    obj.end()
+  }
+}
+
+impl Serialize for document::AlbumFile {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: serde::Serializer,
+  {
+    let mut obj = serializer.serialize_struct("AlbumFile", 2)?;
+    obj.serialize_field("file_type", &self.file_type)?;
+    obj.serialize_field("name", &self.name)?;
+    obj.end()
+  }
+}
+
+impl Serialize for document::Track {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: serde::Serializer,
+  {
+    let mut obj = serializer.serialize_struct("Track", 14)?;
+    obj.serialize_field("arranger", &self.arranger)?;
+    obj.serialize_field("composer", &self.composer)?;
+    obj.serialize_field("data_type", &self.data_type)?;
+    obj.serialize_field("flags", &self.flags)?;
+    obj.serialize_field("indexes", &self.indexes)?;
+    obj.serialize_field("isrc", &self.isrc)?;
+    obj.serialize_field("message", &self.message)?;
+    obj.serialize_field("performer", &self.performer)?;
+    obj.serialize_field("postgap", &self.postgap)?;
+    obj.serialize_field("pregap", &self.pregap)?;
+    obj.serialize_field("remarks", &self.remarks)?;
+    obj.serialize_field("songwriter", &self.songwriter)?;
+    obj.serialize_field("title", &self.title)?;
+    obj.serialize_field("track_no", &self.track_no)?;
+    obj.end()
+  }
+}
+
+impl Serialize for document::Index {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: serde::Serializer,
+  {
+    let mut obj = serializer.serialize_struct("Index", 3)?;
+    obj.serialize_field("file", &self.file)?;
+    obj.serialize_field("index_no", &self.index_no)?;
+    obj.serialize_field("timestamp", &self.timestamp)?;
+    obj.end()
+  }
+}
+
+impl Serialize for PrimaryVolume {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: serde::Serializer,
+  {
+    let mut obj = serializer.serialize_struct("PrimaryVolume", 5)?;
+    obj.serialize_field("block_size", &self.block_size)?;
+    obj.serialize_field("creation_date", &self.creation_date)?;
+    obj.serialize_field("system_id", &self.system_id)?;
+    obj.serialize_field("volume_id", &self.volume_id)?;
+    obj.serialize_field("volume_size", &self.volume_size)?;
+    obj.end()
+  }
+}
+
+impl Serialize for VolumeDate {
+  fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+  where
+    S: serde::Serializer,
+  {
+    serializer.collect_str(self)
+  }
+}
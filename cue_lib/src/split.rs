@@ -0,0 +1,153 @@
+//! Split plan for cutting a disc image into tracks.
+//!
+//! The plan only contains sample ranges and silence lengths, decoding and encoding are left to
+//! the caller.
+
+use crate::{
+  core::{
+    timestamp::{CueTimeStamp, SampleRate},
+    track::TrackNo,
+  },
+  document::{AlbumFile, CueSheet},
+  error::CueLibError,
+  probe::CueSheetProbe,
+  timing::{TimeRange, TrackTiming},
+};
+use alloc::vec::Vec;
+
+/// Placement of the pregap audio between INDEX 00 and INDEX 01.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub enum GapMode {
+  /// Gaps are appended to the previous track, the first track's pregap is discarded.
+  #[default]
+  AppendToPrevious,
+
+  /// Gaps are prepended to their own track.
+  PrependToNext,
+
+  /// Gaps are discarded.
+  Discard,
+
+  /// Same as [GapMode::AppendToPrevious], but the hidden track one audio (HTOA) is extracted as
+  /// track 0.
+  ExtractHtoa,
+}
+
+/// Sample range in a source file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct SplitSegment {
+  /// Position of the file in [SplitPlan::files].
+  pub file: Option<usize>,
+  pub start_sample: u64,
+
+  /// End sample (exclusive), `None` reads until the end of the file.
+  pub end_sample: Option<u64>,
+}
+
+/// Output track instructions. Silence is written before and after the segments.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SplitTrack {
+  /// Track number, 0 for the hidden track.
+  pub track_no: TrackNo,
+  pub silence_before: u64,
+  pub segments: Vec<SplitSegment>,
+  pub silence_after: u64,
+}
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SplitPlan {
+  pub files: Vec<AlbumFile>,
+  pub tracks: Vec<SplitTrack>,
+}
+
+impl SplitPlan {
+  pub fn new(
+    probe: &CueSheetProbe<'_>,
+    sample_rate: SampleRate,
+    gap_mode: GapMode,
+  ) -> Result<Self, CueLibError> {
+    let cuesheet = CueSheet::from_probe(probe)?;
+    let timings = cuesheet.track_timings(&[]);
+    let samples =
+      |timestamp: Option<CueTimeStamp>| timestamp.map_or(0, |v| v.as_samples(sample_rate));
+
+    let mut tracks = Vec::with_capacity(timings.len() + 1);
+
+    if gap_mode == GapMode::ExtractHtoa
+      && let Some(hidden_track) = cuesheet.hidden_track()
+    {
+      tracks.push(SplitTrack {
+        track_no: hidden_track.track_no,
+        silence_before: 0,
+        segments: Vec::from([segment(&hidden_track.audio, sample_rate)]),
+        silence_after: 0,
+      });
+    }
+
+    for (idx, timing) in timings.iter().enumerate() {
+      let next = timings.get(idx + 1);
+      let mut track = SplitTrack {
+        track_no: timing.track_no,
+        silence_before: 0,
+        segments: Vec::new(),
+        silence_after: samples(timing.postgap_silence),
+      };
+
+      match gap_mode {
+        GapMode::PrependToNext => {
+          track.silence_before = samples(timing.pregap_silence);
+          push_gap(&mut track, timing, sample_rate);
+          push_segment(&mut track, segment(&timing.audio, sample_rate));
+        }
+        GapMode::AppendToPrevious | GapMode::ExtractHtoa => {
+          push_segment(&mut track, segment(&timing.audio, sample_rate));
+
+          if let Some(next) = next {
+            push_gap(&mut track, next, sample_rate);
+            track.silence_after += samples(next.pregap_silence);
+          }
+        }
+        GapMode::Discard => {
+          push_segment(&mut track, segment(&timing.audio, sample_rate));
+        }
+      }
+
+      tracks.push(track);
+    }
+
+    Ok(Self {
+      files: cuesheet.files,
+      tracks,
+    })
+  }
+}
+
+#[inline]
+fn segment(range: &TimeRange, sample_rate: SampleRate) -> SplitSegment {
+  SplitSegment {
+    file: range.file,
+    start_sample: range.start.as_samples(sample_rate),
+    end_sample: range.end.map(|v| v.as_samples(sample_rate)),
+  }
+}
+
+#[inline]
+fn push_gap(track: &mut SplitTrack, timing: &TrackTiming, sample_rate: SampleRate) {
+  if let Some(gap) = timing.pregap_audio {
+    push_segment(track, segment(&gap, sample_rate));
+  }
+}
+
+/// Adds the segment, merging it with the last one when they are contiguous.
+fn push_segment(track: &mut SplitTrack, segment: SplitSegment) {
+  if segment.end_sample == Some(segment.start_sample) {
+    return;
+  }
+
+  match track.segments.last_mut() {
+    Some(last) if last.file == segment.file && last.end_sample == Some(segment.start_sample) => {
+      last.end_sample = segment.end_sample;
+    }
+    _ => track.segments.push(segment),
+  }
+}
@@ -0,0 +1,290 @@
+//! Incremental cuesheet parsing without buffering the whole input.
+//!
+//! [StreamParser] accepts byte chunks and yields commands as their lines complete.
+//! [CommandReader] pulls the input from any [BufRead](std::io::BufRead) (`std` feature).
+//!
+//! Commands are only checked for syntax, command usage rules are applied by
+//! [CueSheetProbe](crate::probe::CueSheetProbe).
+
+use crate::{
+  core::command::OwnedCommand,
+  error::{ParseError, ParseErrorKind},
+  internal::{lexer::CueLexer, tokenizer::Tokenizer},
+};
+use alloc::vec::Vec;
+
+/// Command with its zero-based line and column numbers.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct StreamCommand {
+  command: OwnedCommand,
+  line: usize,
+  column: usize,
+}
+
+/// Push parser for chunked input.
+#[derive(Clone, Debug, Default)]
+pub struct StreamParser {
+  buffer: Vec<u8>,
+
+  /// Start of the unparsed bytes in buffer.
+  cursor: usize,
+
+  /// Line number of the unparsed bytes.
+  line: usize,
+  is_finished: bool,
+  unknown_commands: bool,
+}
+
+impl StreamCommand {
+  #[inline]
+  pub const fn command(&self) -> &OwnedCommand {
+    &self.command
+  }
+
+  #[inline]
+  pub fn into_command(self) -> OwnedCommand {
+    self.command
+  }
+
+  #[inline]
+  pub const fn line(&self) -> usize {
+    self.line
+  }
+
+  #[inline]
+  pub const fn column(&self) -> usize {
+    self.column
+  }
+}
+
+impl StreamParser {
+  #[inline]
+  pub const fn new() -> Self {
+    Self {
+      buffer: Vec::new(),
+      cursor: 0,
+      line: 0,
+      is_finished: false,
+      unknown_commands: false,
+    }
+  }
+
+  /// Returns unknown commands as [OwnedCommand::Unknown] instead of an error.
+  #[inline]
+  pub const fn set_unknown_commands(mut self, value: bool) -> Self {
+    self.unknown_commands = value;
+    self
+  }
+
+  /// Appends the next chunk of input. Chunks may end anywhere, including in the middle of a line
+  /// or a UTF-8 sequence.
+  pub fn push(&mut self, chunk: &[u8]) {
+    if self.cursor > 0 {
+      self.buffer.drain(..self.cursor);
+      self.cursor = 0;
+    }
+
+    self.buffer.extend_from_slice(chunk);
+  }
+
+  /// Marks the end of input, so the last line is parsed even without a line feed.
+  #[inline]
+  pub fn finish(&mut self) {
+    self.is_finished = true;
+  }
+
+  #[inline]
+  pub const fn is_finished(&self) -> bool {
+    self.is_finished
+  }
+
+  /// Returns the next command from the completed lines.
+  ///
+  /// `None` means more input is needed, or the input is finished and fully consumed. Unlike an
+  /// iterator, commands are returned again after the next [push](Self::push), so the parser
+  /// does not implement [Iterator]. Parsing continues from the next line after an error.
+  pub fn next_command(&mut self) -> Option<Result<StreamCommand, ParseError>> {
+    loop {
+      let remaining = &self.buffer[self.cursor..];
+      let line_end = match remaining.iter().position(|v| *v == b'\n') {
+        Some(end) => end + 1,
+        None if self.is_finished && !remaining.is_empty() => remaining.len(),
+        None => return None,
+      };
+
+      let line_no = self.line;
+      self.cursor += line_end;
+      self.line += 1;
+
+      let line = &self.buffer[(self.cursor - line_end)..self.cursor];
+
+      match parse_line(line, line_no, self.unknown_commands) {
+        Some(result) => return Some(result),
+        None => continue,
+      }
+    }
+  }
+}
+
+/// Parses a single line, returns `None` for empty lines.
+fn parse_line(
+  line: &[u8],
+  line_no: usize,
+  unknown_commands: bool,
+) -> Option<Result<StreamCommand, ParseError>> {
+  let line = match core::str::from_utf8(line) {
+    Ok(line) => line,
+    Err(err) => {
+      let column = core::str::from_utf8(&line[..err.valid_up_to()])
+        .map(|v| v.chars().count())
+        .unwrap_or_default();
+
+      return Some(Err(ParseError::new(
+        ParseErrorKind::InvalidUtf8,
+        line_no,
+        column,
+      )));
+    }
+  };
+
+  let mut lexer = CueLexer::new(Tokenizer::new(line));
+  lexer.set_unknown_commands(unknown_commands);
+
+  match lexer.next_command() {
+    Ok(Some(command)) => Some(Ok(StreamCommand {
+      command: command.into(),
+      line: line_no,
+      column: line
+        .chars()
+        .take_while(|v| v.is_whitespace() || *v == '\u{feff}')
+        .count(),
+    })),
+    Ok(None) => None,
+    Err(err) => Some(Err(ParseError::new(err.kind(), line_no, err.column()))),
+  }
+}
+
+#[cfg(feature = "std")]
+pub use self::reader::{CommandReader, StreamError};
+
+#[cfg(feature = "std")]
+mod reader {
+  use super::{StreamCommand, parse_line};
+  use crate::error::ParseError;
+  use std::io::{BufRead, BufReader, Read};
+
+  /// Pull parser over [BufRead].
+  pub struct CommandReader<R> {
+    reader: R,
+    buffer: Vec<u8>,
+    line: usize,
+    unknown_commands: bool,
+  }
+
+  #[derive(Debug)]
+  pub enum StreamError {
+    Io(std::io::Error),
+    Parse(ParseError),
+  }
+
+  impl<R> CommandReader<R>
+  where
+    R: BufRead,
+  {
+    #[inline]
+    pub const fn new(reader: R) -> Self {
+      Self {
+        reader,
+        buffer: Vec::new(),
+        line: 0,
+        unknown_commands: false,
+      }
+    }
+
+    /// Returns unknown commands as
+    /// [OwnedCommand::Unknown](crate::core::command::OwnedCommand::Unknown) instead of an error.
+    #[inline]
+    pub fn set_unknown_commands(mut self, value: bool) -> Self {
+      self.unknown_commands = value;
+      self
+    }
+
+    #[inline]
+    pub fn into_inner(self) -> R {
+      self.reader
+    }
+
+    pub fn next_command(&mut self) -> Option<Result<StreamCommand, StreamError>> {
+      loop {
+        self.buffer.clear();
+
+        match self.reader.read_until(b'\n', &mut self.buffer) {
+          Ok(0) => return None,
+          Ok(_) => {
+            let line_no = self.line;
+            self.line += 1;
+
+            match parse_line(&self.buffer, line_no, self.unknown_commands) {
+              Some(result) => return Some(result.map_err(StreamError::Parse)),
+              None => continue,
+            }
+          }
+          Err(err) if err.kind() == std::io::ErrorKind::Interrupted => continue,
+          Err(err) => return Some(Err(StreamError::Io(err))),
+        }
+      }
+    }
+  }
+
+  impl<R> CommandReader<BufReader<R>>
+  where
+    R: Read,
+  {
+    #[inline]
+    pub fn from_read(reader: R) -> Self {
+      Self::new(BufReader::new(reader))
+    }
+  }
+
+  impl<R> Iterator for CommandReader<R>
+  where
+    R: BufRead,
+  {
+    type Item = Result<StreamCommand, StreamError>;
+
+    #[inline]
+    fn next(&mut self) -> Option<Self::Item> {
+      self.next_command()
+    }
+  }
+
+  impl From<std::io::Error> for StreamError {
+    #[inline]
+    fn from(value: std::io::Error) -> Self {
+      Self::Io(value)
+    }
+  }
+
+  impl From<ParseError> for StreamError {
+    #[inline]
+    fn from(value: ParseError) -> Self {
+      Self::Parse(value)
+    }
+  }
+
+  impl core::fmt::Display for StreamError {
+    fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+      match self {
+        StreamError::Io(err) => err.fmt(f),
+        StreamError::Parse(err) => f.write_fmt(format_args!(
+          "parse error at {line}:{column}, {kind}",
+          line = err.line(),
+          column = err.column(),
+          kind = err.kind()
+        )),
+      }
+    }
+  }
+
+  impl core::error::Error for StreamError {}
+}
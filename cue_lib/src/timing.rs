@@ -0,0 +1,175 @@
+//! Track boundaries and durations.
+//!
+//! Timestamps in a cuesheet are relative to their own file and only mark where tracks start, the
+//! end of a file's last track is only known with the file length.
+
+use crate::{
+  core::{timestamp::CueTimeStamp, track::TrackNo},
+  document::{CueSheet, Track},
+  probe::track::hidden_track_length,
+};
+use alloc::vec::Vec;
+
+/// Audio range within a file.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TimeRange {
+  /// Position of the file in [CueSheet::files].
+  pub file: Option<usize>,
+  pub start: CueTimeStamp,
+
+  /// End of the range, `None` when the file length is unknown.
+  pub end: Option<CueTimeStamp>,
+}
+
+/// Timing of a single track.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct TrackTiming {
+  pub track_no: TrackNo,
+
+  /// Silence inserted before the track (PREGAP command), not part of any file.
+  pub pregap_silence: Option<CueTimeStamp>,
+
+  /// Pregap audio stored in the file, from INDEX 00 to INDEX 01.
+  pub pregap_audio: Option<TimeRange>,
+
+  /// Track audio, from INDEX 01 to the next track's first index or the end of the file.
+  pub audio: TimeRange,
+
+  /// Silence inserted after the track (POSTGAP command), not part of any file.
+  pub postgap_silence: Option<CueTimeStamp>,
+}
+
+impl TimeRange {
+  #[inline]
+  pub fn duration(&self) -> Option<CueTimeStamp> {
+    self.end?.checked_sub(self.start)
+  }
+}
+
+impl TrackTiming {
+  /// Start of the track audio (INDEX 01).
+  #[inline]
+  pub const fn start(&self) -> CueTimeStamp {
+    self.audio.start
+  }
+
+  /// End of the track audio, `None` when the file length is unknown.
+  #[inline]
+  pub const fn end(&self) -> Option<CueTimeStamp> {
+    self.audio.end
+  }
+
+  /// Duration of the track audio, excluding pregap and postgap.
+  #[inline]
+  pub fn duration(&self) -> Option<CueTimeStamp> {
+    self.audio.duration()
+  }
+
+  /// Total length of the pregap, silence and audio combined.
+  pub fn pregap_duration(&self) -> Option<CueTimeStamp> {
+    let audio = match self.pregap_audio {
+      Some(range) => range.duration()?,
+      None => CueTimeStamp::ZERO,
+    };
+
+    audio.checked_add(self.pregap_silence.unwrap_or_default())
+  }
+}
+
+/// Calculates the timings of every track.
+///
+/// `file_lengths` are the lengths of each file in [CueSheet::files] order, missing entries are
+/// treated as unknown.
+pub fn track_timings(cuesheet: &CueSheet, file_lengths: &[CueTimeStamp]) -> Vec<TrackTiming> {
+  let file_end = |file: Option<usize>| file.and_then(|idx| file_lengths.get(idx)).copied();
+  let mut timings = Vec::with_capacity(cuesheet.tracks.len());
+  let mut tracks = cuesheet.tracks.iter().peekable();
+
+  while let Some(track) = tracks.next() {
+    let start = track.start_index();
+    let file = start.and_then(|v| v.file);
+    let start = start.map(|v| v.timestamp).unwrap_or_default();
+
+    let pregap_audio = track.pregap_index().map(|index| TimeRange {
+      file: index.file,
+      start: index.timestamp,
+      end: if index.file == file {
+        Some(start)
+      } else {
+        file_end(index.file)
+      },
+    });
+
+    let end = tracks
+      .peek()
+      .and_then(|next| next_track_start(next, file))
+      .or_else(|| file_end(file));
+
+    timings.push(TrackTiming {
+      track_no: track.track_no,
+      pregap_silence: track.pregap,
+      pregap_audio,
+      audio: TimeRange { file, start, end },
+      postgap_silence: track.postgap,
+    });
+  }
+
+  timings
+}
+
+/// Calculates the timings of every track like [track_timings], with the hidden track one audio
+/// (HTOA) as a virtual track 0 in front.
+///
+/// The hidden track is removed from track 1's pregap, so no audio is counted twice.
+pub fn track_timings_with_htoa(
+  cuesheet: &CueSheet,
+  file_lengths: &[CueTimeStamp],
+) -> Vec<TrackTiming> {
+  let mut timings = track_timings(cuesheet, file_lengths);
+
+  if let Some(hidden_track) = hidden_track(cuesheet) {
+    if let Some(first) = timings.first_mut() {
+      first.pregap_audio = None;
+    }
+
+    timings.insert(0, hidden_track);
+  }
+
+  timings
+}
+
+/// Returns the hidden track one audio (HTOA) as a virtual track 0.
+///
+/// Hidden track is the audio between the first track's INDEX 00 at 00:00:00 and its INDEX 01 in
+/// the same file.
+pub fn hidden_track(cuesheet: &CueSheet) -> Option<TrackTiming> {
+  let track = cuesheet.tracks.first()?;
+  let start = track.start_index()?;
+  let pregap = track.pregap_index();
+  let end = hidden_track_length(
+    pregap.map(|v| v.timestamp),
+    start.timestamp,
+    pregap.is_some_and(|v| v.file == start.file),
+  )?;
+
+  Some(TrackTiming {
+    track_no: TrackNo::MIN,
+    pregap_silence: None,
+    pregap_audio: None,
+    audio: TimeRange {
+      file: start.file,
+      start: CueTimeStamp::ZERO,
+      end: Some(end),
+    },
+    postgap_silence: None,
+  })
+}
+
+/// Returns the first index of the track when it starts in the same file.
+fn next_track_start(track: &Track, file: Option<usize>) -> Option<CueTimeStamp> {
+  track
+    .indexes
+    .iter()
+    .find(|v| v.file == file)
+    .map(|v| v.timestamp)
+}
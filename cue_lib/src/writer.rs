@@ -0,0 +1,330 @@
+//! Cuesheet writer for the owned [CueSheet](crate::document::CueSheet) model.
+//!
+//! Commands are written in canonical order:
+//! - Album: `REM`, `CATALOG`, `CDTEXTFILE`, `TITLE`, `PERFORMER`, `SONGWRITER`, `ARRANGER`,
+//!   `COMPOSER`, `MESSAGE`, `GENRE`, `DISC_ID`, `UPC_EAN`
+//! - Track: `FILE` (when changed), `TRACK`, `TITLE`, `PERFORMER`, `SONGWRITER`, `ARRANGER`,
+//!   `COMPOSER`, `MESSAGE`, `REM`, `FLAGS`, `ISRC`, `PREGAP`, `INDEX`, `POSTGAP`
+
+use crate::document::{CueSheet, Track};
+use alloc::string::String;
+use core::fmt::Write;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum LineEnding {
+  #[default]
+  LF,
+  CRLF,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Indent {
+  Spaces(u8),
+  Tab,
+}
+
+/// Writes [CueSheet] documents as cuesheet text.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct CueWriter {
+  indent: Indent,
+  line_ending: LineEnding,
+}
+
+/// Display wrapper that writes the text as a double quoted cuesheet string.
+///
+/// Double quotes and backslashes are escaped. Since cuesheets are line based, line breaks are
+/// written as spaces.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Quoted<'a>(pub &'a str);
+
+impl Default for CueWriter {
+  #[inline]
+  fn default() -> Self {
+    Self::new()
+  }
+}
+
+impl CueWriter {
+  /// Creates a writer with two spaces indentation and LF line endings.
+  #[inline]
+  pub const fn new() -> Self {
+    Self {
+      indent: Indent::Spaces(2),
+      line_ending: LineEnding::LF,
+    }
+  }
+
+  #[inline]
+  pub const fn set_indent(mut self, value: Indent) -> Self {
+    self.indent = value;
+    self
+  }
+
+  #[inline]
+  pub const fn set_line_ending(mut self, value: LineEnding) -> Self {
+    self.line_ending = value;
+    self
+  }
+
+  pub fn write<W>(&self, cuesheet: &CueSheet, out: &mut W) -> core::fmt::Result
+  where
+    W: Write,
+  {
+    let mut writer = LineWriter {
+      out,
+      indent: self.indent,
+      line_ending: self.line_ending,
+    };
+
+    for remark in cuesheet.remarks.iter() {
+      writer.line(0, format_args!("REM {remark}"))?;
+    }
+
+    if let Some(catalog) = cuesheet.catalog {
+      writer.line(0, format_args!("CATALOG {catalog}"))?;
+    }
+
+    if let Some(cdtextfile) = cuesheet.cdtextfile.as_deref() {
+      writer.line(0, format_args!("CDTEXTFILE {}", Quoted(cdtextfile)))?;
+    }
+
+    writer.text_commands(
+      0,
+      &[
+        ("TITLE", &cuesheet.title),
+        ("PERFORMER", &cuesheet.performer),
+        ("SONGWRITER", &cuesheet.songwriter),
+        ("ARRANGER", &cuesheet.arranger),
+        ("COMPOSER", &cuesheet.composer),
+        ("MESSAGE", &cuesheet.message),
+        ("GENRE", &cuesheet.genre),
+        ("DISC_ID", &cuesheet.disc_id),
+        ("UPC_EAN", &cuesheet.upc_ean),
+      ],
+    )?;
+
+    let mut current_file = None;
+
+    for track in cuesheet.tracks.iter() {
+      writer.track(cuesheet, track, &mut current_file)?;
+    }
+
+    Ok(())
+  }
+
+  #[cfg(feature = "std")]
+  pub fn write_io<W>(&self, cuesheet: &CueSheet, out: &mut W) -> std::io::Result<()>
+  where
+    W: std::io::Write,
+  {
+    let mut adapter = IoAdapter {
+      inner: out,
+      error: None,
+    };
+
+    match self.write(cuesheet, &mut adapter) {
+      Ok(()) => Ok(()),
+      Err(_) => Err(
+        adapter
+          .error
+          .unwrap_or_else(|| std::io::Error::other("formatter error")),
+      ),
+    }
+  }
+
+  pub fn to_string(&self, cuesheet: &CueSheet) -> String {
+    let mut buffer = String::new();
+    self
+      .write(cuesheet, &mut buffer)
+      .expect("writing to a String never fails");
+
+    buffer
+  }
+}
+
+struct LineWriter<'w, W> {
+  out: &'w mut W,
+  indent: Indent,
+  line_ending: LineEnding,
+}
+
+impl<W> LineWriter<'_, W>
+where
+  W: Write,
+{
+  fn line(&mut self, depth: u8, args: core::fmt::Arguments<'_>) -> core::fmt::Result {
+    for _ in 0..depth {
+      match self.indent {
+        Indent::Spaces(count) => {
+          for _ in 0..count {
+            self.out.write_char(' ')?;
+          }
+        }
+        Indent::Tab => self.out.write_char('\t')?,
+      }
+    }
+
+    self.out.write_fmt(args)?;
+
+    match self.line_ending {
+      LineEnding::LF => self.out.write_str("\n"),
+      LineEnding::CRLF => self.out.write_str("\r\n"),
+    }
+  }
+
+  /// Writes the quoted text commands in the given order, missing values are skipped.
+  fn text_commands(
+    &mut self,
+    depth: u8,
+    commands: &[(&str, &Option<String>)],
+  ) -> core::fmt::Result {
+    for (name, value) in commands.iter() {
+      if let Some(value) = value.as_deref() {
+        self.line(depth, format_args!("{name} {}", Quoted(value)))?;
+      }
+    }
+
+    Ok(())
+  }
+
+  fn file(&mut self, cuesheet: &CueSheet, file: Option<usize>) -> core::fmt::Result {
+    match file.and_then(|position| cuesheet.files.get(position)) {
+      Some(file) => self.line(
+        0,
+        format_args!("FILE {} {}", Quoted(&file.name), file.file_type),
+      ),
+      None => Ok(()),
+    }
+  }
+
+  fn track(
+    &mut self,
+    cuesheet: &CueSheet,
+    track: &Track,
+    current_file: &mut Option<usize>,
+  ) -> core::fmt::Result {
+    // FILE must precede the TRACK command when the first index is in a new file.
+    if let Some(index) = track.indexes.first()
+      && index.file != *current_file
+    {
+      self.file(cuesheet, index.file)?;
+      *current_file = index.file;
+    }
+
+    self.line(
+      1,
+      format_args!("TRACK {} {}", track.track_no, track.data_type),
+    )?;
+
+    self.text_commands(
+      2,
+      &[
+        ("TITLE", &track.title),
+        ("PERFORMER", &track.performer),
+        ("SONGWRITER", &track.songwriter),
+        ("ARRANGER", &track.arranger),
+        ("COMPOSER", &track.composer),
+        ("MESSAGE", &track.message),
+      ],
+    )?;
+
+    for remark in track.remarks.iter() {
+      self.line(2, format_args!("REM {remark}"))?;
+    }
+
+    if let Some(flags) = track.flags
+      && !flags.is_empty()
+    {
+      self.line(2, format_args!("FLAGS {}", FlagNames(flags)))?;
+    }
+
+    if let Some(isrc) = track.isrc {
+      self.line(2, format_args!("ISRC {isrc}"))?;
+    }
+
+    if let Some(pregap) = track.pregap {
+      self.line(2, format_args!("PREGAP {pregap}"))?;
+    }
+
+    for index in track.indexes.iter() {
+      if index.file != *current_file {
+        self.file(cuesheet, index.file)?;
+        *current_file = index.file;
+      }
+
+      self.line(
+        2,
+        format_args!("INDEX {} {}", index.index_no, index.timestamp),
+      )?;
+    }
+
+    if let Some(postgap) = track.postgap {
+      self.line(2, format_args!("POSTGAP {postgap}"))?;
+    }
+
+    Ok(())
+  }
+}
+
+struct FlagNames(crate::core::flags::TrackFlag);
+
+impl core::fmt::Display for FlagNames {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    for (idx, name) in self.0.iter().enumerate() {
+      if idx > 0 {
+        f.write_char(' ')?;
+      }
+
+      f.write_str(name)?;
+    }
+
+    Ok(())
+  }
+}
+
+impl core::fmt::Display for Quoted<'_> {
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    f.write_char('"')?;
+
+    for ch in self.0.chars() {
+      match ch {
+        '"' => f.write_str("\\\"")?,
+        '\\' => f.write_str("\\\\")?,
+        '\r' | '\n' => f.write_char(' ')?,
+        _ => f.write_char(ch)?,
+      }
+    }
+
+    f.write_char('"')
+  }
+}
+
+impl core::fmt::Display for CueSheet {
+  /// Writes the cuesheet with the default [CueWriter] options.
+  #[inline]
+  fn fmt(&self, f: &mut core::fmt::Formatter<'_>) -> core::fmt::Result {
+    CueWriter::new().write(self, f)
+  }
+}
+
+#[cfg(feature = "std")]
+struct IoAdapter<'w, W> {
+  inner: &'w mut W,
+  error: Option<std::io::Error>,
+}
+
+#[cfg(feature = "std")]
+impl<W> Write for IoAdapter<'_, W>
+where
+  W: std::io::Write,
+{
+  fn write_str(&mut self, s: &str) -> core::fmt::Result {
+    match self.inner.write_all(s.as_bytes()) {
+      Ok(()) => Ok(()),
+      Err(err) => {
+        self.error = Some(err);
+        Err(core::fmt::Error)
+      }
+    }
+  }
+}
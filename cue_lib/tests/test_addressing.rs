@@ -0,0 +1,127 @@
+#![cfg(feature = "alloc")]
+
+use cue_lib::{
+  addressing::{AddressingErrorKind, DiscLayout, FileSize, MediaSize, Msf},
+  core::{timestamp::CueTimeStamp, track::DataType},
+  document::CueSheet,
+};
+use std::str::FromStr;
+
+#[test]
+fn sector_sizes() {
+  assert_eq!(DataType::Audio.sector_size(), 2352);
+  assert_eq!(DataType::CDG.sector_size(), 2448);
+  assert_eq!(DataType::Mode1_2048.sector_size(), 2048);
+  assert_eq!(DataType::Mode2_2336.sector_size(), 2336);
+  assert_eq!(DataType::Mode2_2352.sector_size(), 2352);
+  assert!(DataType::CDG.is_audio());
+  assert!(!DataType::Mode1_2352.is_audio());
+}
+
+#[test]
+fn msf_conversion() {
+  let msf = Msf::from_lba(0);
+  assert_eq!(msf.to_string(), "00:02:00");
+  assert_eq!(msf.to_lba(), Some(0));
+  assert_eq!(Msf::from_lba(15213).to_string(), "03:24:63");
+  assert_eq!(
+    Msf {
+      minute: 0,
+      second: 1,
+      frame: 0
+    }
+    .to_lba(),
+    None
+  );
+}
+
+#[test]
+fn mixed_mode_image() {
+  let cuesheet = CueSheet::from_str(
+    r#"FILE "game.bin" BINARY
+  TRACK 01 MODE1/2048
+    INDEX 01 00:00:00
+  TRACK 02 AUDIO
+    PREGAP 00:02:00
+    INDEX 01 00:10:00
+  TRACK 03 AUDIO
+    INDEX 00 01:00:00
+    INDEX 01 01:02:00
+"#,
+  )
+  .unwrap();
+
+  // 750 data sectors, 3750 audio sectors for track 2 and 4650 for track 3 with its pregap.
+  let size = 750 * 2048 + (3750 + 4650) * 2352;
+  let layout = DiscLayout::new(&cuesheet, &[FileSize::Bytes(size)]).unwrap();
+  let tracks = &layout.tracks;
+
+  assert_eq!(tracks.len(), 3);
+  assert_eq!((tracks[0].start_lba, tracks[0].length), (0, 750));
+  assert_eq!(tracks[0].file_offset, Some(0));
+  assert_eq!(tracks[0].byte_size(), 750 * 2048);
+
+  assert_eq!(tracks[1].pregap_lba, 750);
+  assert_eq!((tracks[1].start_lba, tracks[1].length), (900, 3750));
+  assert_eq!(tracks[1].file_offset, Some(750 * 2048));
+
+  assert_eq!(tracks[2].pregap_lba, 4650);
+  assert_eq!((tracks[2].start_lba, tracks[2].length), (4800, 4500));
+  assert_eq!(tracks[2].file_offset, Some(750 * 2048 + 3900 * 2352));
+  assert!(tracks.iter().all(|v| v.session == 1));
+
+  assert_eq!(layout.lead_out, 9300);
+  assert_eq!(layout.lead_out_msf().to_string(), "02:06:00");
+}
+
+#[test]
+fn cd_extra_session_gap() {
+  let cuesheet = CueSheet::from_str(
+    r#"FILE "audio.wav" WAVE
+  TRACK 01 AUDIO
+    INDEX 01 00:00:00
+FILE "data.bin" BINARY
+  TRACK 02 MODE2/2352
+    INDEX 01 00:00:00
+"#,
+  )
+  .unwrap();
+
+  let sizes = [
+    FileSize::Length(CueTimeStamp::from_str("70:00:00").unwrap()),
+    FileSize::Bytes(1000 * 2352),
+  ];
+  let layout = DiscLayout::new(&cuesheet, &sizes).unwrap();
+
+  assert_eq!(layout.tracks[0].length, 315000);
+  assert_eq!(layout.tracks[0].file_offset, None);
+  assert_eq!(layout.tracks[1].session, 2);
+  assert_eq!(layout.tracks[1].start_lba, 315000 + 11400);
+  assert_eq!(layout.tracks[1].length, 1000);
+  assert_eq!(layout.lead_out, 315000 + 11400 + 1000);
+
+  let capacity = layout.capacity(MediaSize::Cd74);
+  assert_eq!(capacity.used, 327550);
+  assert!(capacity.fits());
+  assert_eq!(capacity.free_sectors(), Some(333000 - 327550));
+
+  let sizes = [
+    FileSize::Length(CueTimeStamp::from_str("79:00:00").unwrap()),
+    FileSize::Bytes(1000 * 2352),
+  ];
+  let layout = DiscLayout::new(&cuesheet, &sizes).unwrap();
+
+  assert!(!layout.capacity(MediaSize::Cd74).fits());
+  assert!(!layout.capacity(MediaSize::Cd80).fits());
+  assert_eq!(layout.capacity(MediaSize::Cd80).free_sectors(), None);
+}
+
+#[test]
+fn missing_file_size() {
+  let cuesheet =
+    CueSheet::from_str("FILE \"a.bin\" BINARY\n  TRACK 01 MODE1/2352\n    INDEX 01 00:00:00")
+      .unwrap();
+
+  let err = DiscLayout::new(&cuesheet, &[]).unwrap_err();
+  assert_eq!(err.kind(), AddressingErrorKind::MissingFileSize(0));
+}
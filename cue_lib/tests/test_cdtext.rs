@@ -0,0 +1,109 @@
+use cue_lib::{
+  core::command::Command,
+  error::{CueLibErrorKind, ParseErrorKind},
+  lexer::CommandStream,
+  probe::CueSheetProbe,
+};
+
+const CUESHEET: &str = r#"TITLE "Album"
+ARRANGER "Album Arranger"
+COMPOSER "Album Composer"
+MESSAGE "Thanks for listening"
+GENRE "Soundtrack"
+DISC_ID "XY12345"
+UPC_EAN 4988002345673
+FILE "album.wav" WAVE
+  TRACK 01 AUDIO
+    TITLE "Track 1"
+    ARRANGER "Arranger 1"
+    COMPOSER "Composer 1"
+    MESSAGE "Message 1"
+    INDEX 01 00:00:00
+  TRACK 02 AUDIO
+    TITLE "Track 2"
+    INDEX 01 04:10:00
+"#;
+
+fn parse_error_kind(cuesheet: &str) -> ParseErrorKind {
+  match CueSheetProbe::verify(cuesheet) {
+    Ok(_) => panic!("cuesheet should've failed"),
+    Err(err) => match err.kind() {
+      CueLibErrorKind::ParseError(err) => err.kind(),
+    },
+  }
+}
+
+#[test]
+fn album_cdtext() {
+  let probe = CueSheetProbe::new(CUESHEET).unwrap();
+
+  assert_eq!(probe.arranger().unwrap(), "Album Arranger");
+  assert_eq!(probe.composer().unwrap(), "Album Composer");
+  assert_eq!(probe.message().unwrap(), "Thanks for listening");
+  assert_eq!(probe.genre().unwrap(), "Soundtrack");
+  assert_eq!(probe.disc_id().unwrap(), "XY12345");
+  assert_eq!(probe.upc_ean().unwrap(), "4988002345673");
+}
+
+#[test]
+fn track_cdtext() {
+  let probe = CueSheetProbe::new(CUESHEET).unwrap();
+  let mut tracks = probe.tracks();
+
+  let track = tracks.next_track().unwrap().unwrap();
+  assert_eq!(track.arranger().unwrap(), "Arranger 1");
+  assert_eq!(track.composer().unwrap(), "Composer 1");
+  assert_eq!(track.message().unwrap(), "Message 1");
+
+  let track = tracks.next_track().unwrap().unwrap();
+  assert!(track.arranger().is_none());
+  assert!(track.composer().is_none());
+  assert!(track.message().is_none());
+}
+
+#[test]
+fn cdtext_scoping() {
+  let album_only = r#"FILE "album.wav" WAVE
+  TRACK 01 AUDIO
+    GENRE "Soundtrack"
+    INDEX 01 00:00:00
+"#;
+
+  let duplicate = r#"COMPOSER "A"
+COMPOSER "B"
+FILE "album.wav" WAVE
+  TRACK 01 AUDIO
+    INDEX 01 00:00:00
+"#;
+
+  assert_eq!(
+    parse_error_kind(album_only),
+    ParseErrorKind::InvalidCommandUsage
+  );
+  assert_eq!(parse_error_kind(duplicate), ParseErrorKind::MultipleCommand);
+}
+
+#[test]
+fn cdtext_commands() {
+  let command = CommandStream::new("UPC_EAN 4988002345673")
+    .next()
+    .unwrap()
+    .unwrap()
+    .command();
+
+  assert!(matches!(command, Command::UpcEan { value } if value == "4988002345673"));
+}
+
+#[cfg(feature = "alloc")]
+#[test]
+fn cdtext_round_trip() {
+  use cue_lib::document::CueSheet;
+  use std::str::FromStr;
+
+  let cuesheet = CueSheet::from_str(CUESHEET).unwrap();
+  let written = cuesheet.to_string();
+
+  assert!(written.contains("UPC_EAN \"4988002345673\"\n"));
+  assert!(written.contains("    COMPOSER \"Composer 1\"\n"));
+  assert_eq!(CueSheet::from_str(&written).unwrap(), cuesheet);
+}
@@ -0,0 +1,108 @@
+#![cfg(feature = "alloc")]
+
+use cue_lib::{
+  core::{
+    timestamp::CueTimeStamp,
+    track::{IndexNo, TrackNo},
+  },
+  cst::{SyntaxTree, TokenKind},
+  document::CueSheet,
+  writer::LineEnding,
+};
+use std::str::FromStr;
+
+const CUESHEET: &str = "\u{feff}REM GENRE \"Touhou\"\r\nPERFORMER   \"ZUN\"  \r\nTITLE \"Old Title\"\r\n\r\nFILE \"image.wav\" WAVE\r\n\tTRACK 01 AUDIO\r\n\t\tTITLE \"First\"\r\n   REM   odd indent \"unbalanced\r\n\t\tINDEX 01 00:00:00\r\n  TRACK 02 AUDIO\r\n      INDEX 00 03:00:00\r\n      INDEX 01 03:02:00";
+
+#[test]
+fn lossless_print() {
+  let tree = SyntaxTree::parse(CUESHEET);
+
+  assert!(tree.has_bom());
+  assert_eq!(tree.lines().len(), 12);
+  assert_eq!(tree.tracks().len(), 2);
+  assert_eq!(tree.lines()[0].line_ending(), Some(LineEnding::CRLF));
+  assert_eq!(tree.lines()[11].line_ending(), None);
+  assert_eq!(tree.to_string(), CUESHEET);
+}
+
+#[test]
+fn tokens_and_trivia() {
+  let tree = SyntaxTree::parse(CUESHEET);
+  let performer = &tree.lines()[1];
+
+  assert_eq!(performer.keyword(), Some("PERFORMER"));
+  assert_eq!(performer.arguments()[0].text(), "\"ZUN\"");
+  assert_eq!(performer.trailing_trivia(), "  ");
+  assert_eq!(&CUESHEET[performer.arguments()[0].span()], "\"ZUN\"");
+
+  let remark = &tree.lines()[7];
+  assert_eq!(remark.indent(), "   ");
+  assert_eq!(remark.tokens()[1].kind(), TokenKind::Remark);
+  assert_eq!(remark.tokens()[1].text(), "odd indent \"unbalanced");
+
+  assert!(tree.lines()[3].tokens().is_empty());
+}
+
+#[test]
+fn edit_title_and_timestamp() {
+  let mut tree = SyntaxTree::parse(CUESHEET);
+
+  assert!(tree.set_title(None, "New \"Title\""));
+  assert!(tree.set_index_timestamp(
+    TrackNo::new(2).unwrap(),
+    IndexNo::new(1).unwrap(),
+    CueTimeStamp::from_str("03:04:05").unwrap()
+  ));
+
+  let expected = CUESHEET
+    .replace("TITLE \"Old Title\"", "TITLE \"New \\\"Title\\\"\"")
+    .replace("INDEX 01 03:02:00", "INDEX 01 03:04:05");
+
+  assert_eq!(tree.to_string(), expected);
+  assert!(tree.lines()[2].is_edited());
+  assert!(!tree.lines()[1].is_edited());
+}
+
+#[test]
+fn insert_missing_title() {
+  let mut tree = SyntaxTree::parse(CUESHEET);
+
+  assert!(tree.set_title(Some(TrackNo::new(2).unwrap()), "Second"));
+  assert!(!tree.set_title(Some(TrackNo::new(3).unwrap()), "Missing"));
+
+  let expected = CUESHEET.replace(
+    "  TRACK 02 AUDIO\r\n",
+    "  TRACK 02 AUDIO\r\n      TITLE \"Second\"\r\n",
+  );
+
+  assert_eq!(tree.to_string(), expected);
+  assert_eq!(tree.tracks()[1].lines, 9..13);
+  assert!(tree.lines()[10].is_inserted());
+}
+
+#[test]
+fn edit_round_trip() {
+  let source =
+    "TITLE \"Album\"\nFILE \"image.wav\" WAVE\n  TRACK 01 AUDIO\n    INDEX 01 00:00:00\n";
+  let mut tree = SyntaxTree::parse(source);
+
+  assert!(tree.set_performer(None, "Performer"));
+  assert!(tree.set_title(Some(TrackNo::new(1).unwrap()), "First"));
+
+  let cuesheet = CueSheet::from_str(&tree.to_string()).unwrap();
+  assert_eq!(cuesheet.performer.as_deref(), Some("Performer"));
+  assert_eq!(cuesheet.tracks[0].title.as_deref(), Some("First"));
+  assert_eq!(
+    tree.to_string(),
+    "TITLE \"Album\"\nPERFORMER \"Performer\"\nFILE \"image.wav\" WAVE\n  TRACK 01 AUDIO\n    TITLE \"First\"\n    INDEX 01 00:00:00\n"
+  );
+}
+
+#[test]
+fn broken_line_is_preserved() {
+  let source = "TITLE \"unterminated\nFILE \"a.wav\" WAVE\n";
+  let tree = SyntaxTree::parse(source);
+
+  assert_eq!(tree.lines()[0].tokens()[1].kind(), TokenKind::Error);
+  assert_eq!(tree.to_string(), source);
+}
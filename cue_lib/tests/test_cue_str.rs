@@ -132,6 +132,12 @@ test_cue_str!(
   expects = "hello  \\\"hell\""
 );
 
+test_cue_str!(
+  quoted_utf8_with_special_quote,
+  "\"東方 \\\"紅魔郷\\\"\"",
+  expects = "東方 \"紅魔郷\""
+);
+
 test_cue_str!(
   missing_ending_quote,
   "\"hell-oh",
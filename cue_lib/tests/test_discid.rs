@@ -0,0 +1,187 @@
+use cue_lib::{
+  discid::{
+    error::TocErrorKind,
+    toc::{LeadOut, Toc, TocTrack},
+  },
+  probe::CueSheetProbe,
+};
+
+const CUESHEET: &str = r#"FILE "album.wav" WAVE
+  TRACK 01 AUDIO
+    INDEX 01 00:00:00
+  TRACK 02 AUDIO
+    INDEX 01 03:22:63
+  TRACK 03 AUDIO
+    INDEX 01 07:08:64
+  TRACK 04 AUDIO
+    INDEX 01 10:19:17
+  TRACK 05 AUDIO
+    INDEX 00 14:01:39
+    INDEX 01 14:03:39
+  TRACK 06 AUDIO
+    INDEX 01 17:51:14
+"#;
+
+#[test]
+fn disc_ids_from_probe() {
+  let probe = CueSheetProbe::new(CUESHEET).unwrap();
+  let toc = Toc::from_probe(&probe, LeadOut::Frames(95312)).unwrap();
+
+  assert_eq!(toc.first_track(), 1);
+  assert_eq!(toc.last_track(), 6);
+  assert_eq!(toc.track(5).unwrap().offset, 63264);
+  assert_eq!(toc.cddb_id().to_string(), "3404f606");
+  assert_eq!(
+    toc.musicbrainz_toc().to_string(),
+    "1 6 95462 150 15363 32314 46592 63414 80489"
+  );
+  assert_eq!(
+    toc.musicbrainz_id().to_string(),
+    "49HHV7Eb8UKF3aQiNmu1GR8vKTY-"
+  );
+  assert_eq!(
+    toc.accuraterip_id().to_string(),
+    "006-000513be-001b2231-3404f606"
+  );
+  assert_eq!(
+    toc.ctdb_toc_id().to_string(),
+    "iCHDkr.7dpqDbPy3ehdjqp8oRT0-"
+  );
+}
+
+#[test]
+fn multiple_files() {
+  let cuesheet = r#"FILE "01.wav" WAVE
+  TRACK 01 AUDIO
+    INDEX 01 00:00:00
+FILE "02.wav" WAVE
+  TRACK 02 AUDIO
+    INDEX 01 00:00:00
+FILE "03.wav" WAVE
+  TRACK 03 AUDIO
+    INDEX 00 00:00:00
+    INDEX 01 00:02:00
+"#;
+
+  let probe = CueSheetProbe::new(cuesheet).unwrap();
+  let lengths = [
+    LeadOut::Frames(20000),
+    LeadOut::Frames(29850),
+    LeadOut::Frames(10000),
+  ];
+  let toc = Toc::from_probe_files(&probe, &lengths).unwrap();
+  let offsets: Vec<u32> = toc.tracks().iter().map(|v| v.offset).collect();
+
+  assert_eq!(offsets, [0, 20000, 50000]);
+  assert_eq!(toc.lead_out(), 59850);
+
+  let err = Toc::from_probe_files(&probe, &lengths[..2]).unwrap_err();
+  assert_eq!(err.kind(), TocErrorKind::InvalidFileCount);
+}
+
+#[test]
+fn lead_out_from_samples() {
+  let probe = CueSheetProbe::new(CUESHEET).unwrap();
+  let toc = Toc::from_probe(&probe, LeadOut::Samples(95311 * 588 + 1)).unwrap();
+  assert_eq!(toc.lead_out(), 95312);
+}
+
+#[test]
+fn pregap_shifts_offsets() {
+  let cuesheet = r#"FILE "album.wav" WAVE
+  TRACK 01 AUDIO
+    INDEX 01 00:00:00
+  TRACK 02 AUDIO
+    PREGAP 00:02:00
+    INDEX 01 01:00:00
+"#;
+
+  let probe = CueSheetProbe::new(cuesheet).unwrap();
+  let toc = Toc::from_probe(&probe, LeadOut::Frames(9000)).unwrap();
+  assert_eq!(toc.track(2).unwrap().offset, 4650);
+  assert_eq!(toc.lead_out(), 9150);
+}
+
+#[test]
+fn enhanced_cd_excludes_data_track() {
+  let tracks = [
+    TocTrack {
+      offset: 0,
+      is_data: false,
+    },
+    TocTrack {
+      offset: 20000,
+      is_data: false,
+    },
+    TocTrack {
+      offset: 50000,
+      is_data: true,
+    },
+  ];
+
+  let toc = Toc::new(1, &tracks, 60000).unwrap();
+  assert_eq!(toc.cddb_id().track_count(), 3);
+  assert_eq!(toc.musicbrainz_toc().to_string(), "1 2 38750 150 20150");
+  assert_eq!(
+    toc.accuraterip_id().to_string(),
+    "002-00013880-00035b61-26032003"
+  );
+  assert_eq!(
+    toc.ctdb_toc_id().to_string(),
+    "QYDWFgIY9TPH.8hwamSRr1IQJy8-"
+  );
+}
+
+#[test]
+fn mixed_mode_data_track() {
+  let tracks = [
+    TocTrack {
+      offset: 0,
+      is_data: true,
+    },
+    TocTrack {
+      offset: 20000,
+      is_data: false,
+    },
+    TocTrack {
+      offset: 50000,
+      is_data: false,
+    },
+  ];
+
+  let toc = Toc::new(1, &tracks, 60000).unwrap();
+  let accuraterip_id = toc.accuraterip_id();
+
+  assert_eq!(accuraterip_id.audio_track_count(), 2);
+  assert_eq!(accuraterip_id.id1(), 130000);
+  assert_eq!(accuraterip_id.id2(), 300000);
+  assert_eq!(
+    toc.ctdb_toc_id().to_string(),
+    "c_GJlHnKDUkDmy5VJjxkqLij89w-"
+  );
+}
+
+#[test]
+fn invalid_toc() {
+  let track = |offset| TocTrack {
+    offset,
+    is_data: false,
+  };
+
+  let err = Toc::new(1, &[], 100).unwrap_err();
+  assert_eq!(err.kind(), TocErrorKind::InvalidTrackCount);
+
+  let err = Toc::new(0, &[track(0)], 100).unwrap_err();
+  assert_eq!(err.kind(), TocErrorKind::InvalidTrackNo);
+
+  let err = Toc::new(1, &[track(50), track(10)], 100).unwrap_err();
+  assert_eq!(err.kind(), TocErrorKind::InvalidOffset);
+
+  let err = Toc::new(1, &[track(0), track(100)], 100).unwrap_err();
+  assert_eq!(err.kind(), TocErrorKind::InvalidLeadOut);
+
+  let cuesheet = "FILE \"a.wav\" WAVE\n  TRACK 01 AUDIO\n    INDEX 01 00:00:00\nFILE \"b.wav\" WAVE\n  TRACK 02 AUDIO\n    INDEX 01 00:00:00";
+  let probe = CueSheetProbe::new(cuesheet).unwrap();
+  let err = Toc::from_probe(&probe, LeadOut::Frames(100)).unwrap_err();
+  assert_eq!(err.kind(), TocErrorKind::MultipleFiles);
+}
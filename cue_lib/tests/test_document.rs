@@ -0,0 +1,86 @@
+#![cfg(feature = "alloc")]
+
+use cue_lib::{
+  core::{album_file::KnownFileType, timestamp::CueTimeStamp, track::DataType},
+  document::CueSheet,
+};
+use std::str::FromStr;
+
+const CUESHEET: &str = r#"REM GENRE "Touhou"
+REM DATE 2008
+PERFORMER "ZUN"
+TITLE "東方 \"紅魔郷\""
+FILE "01.wav" WAVE
+  TRACK 01 AUDIO
+    TITLE "赤より紅い夢"
+    FLAGS DCP
+    INDEX 01 00:00:00
+  TRACK 02 AUDIO
+    TITLE "ほおずきみたいに紅い魂"
+    REM COMPOSER ZUN
+    INDEX 00 02:10:00
+FILE "02.wav" WAVE
+    INDEX 01 00:00:00
+    INDEX 02 00:30:00
+"#;
+
+fn timestamp(value: &str) -> CueTimeStamp {
+  CueTimeStamp::from_str(value).unwrap()
+}
+
+#[test]
+fn from_str() {
+  let cuesheet = CueSheet::from_str(CUESHEET).unwrap();
+
+  assert_eq!(cuesheet.performer.as_deref(), Some("ZUN"));
+  assert_eq!(cuesheet.title.as_deref(), Some("東方 \"紅魔郷\""));
+  assert_eq!(cuesheet.remarks, ["GENRE \"Touhou\"", "DATE 2008"]);
+  assert_eq!(cuesheet.files.len(), 2);
+  assert_eq!(cuesheet.files[0].name, "01.wav");
+  assert_eq!(cuesheet.files[1].file_type, KnownFileType::WAVE);
+  assert_eq!(cuesheet.tracks.len(), 2);
+
+  let first = &cuesheet.tracks[0];
+  assert_eq!(first.data_type, DataType::Audio);
+  assert_eq!(first.title.as_deref(), Some("赤より紅い夢"));
+  assert_eq!(first.indexes.len(), 1);
+  assert!(first.pregap_index().is_none());
+
+  let second = &cuesheet.tracks[1];
+  assert_eq!(second.remarks, ["COMPOSER ZUN"]);
+  assert_eq!(second.indexes.len(), 3);
+
+  let pregap = second.pregap_index().unwrap();
+  assert_eq!(pregap.timestamp, timestamp("02:10:00"));
+  assert_eq!(cuesheet.index_file(pregap).unwrap().name, "01.wav");
+
+  let start = second.start_index().unwrap();
+  assert_eq!(start.timestamp, timestamp("00:00:00"));
+  assert_eq!(cuesheet.index_file(start).unwrap().name, "02.wav");
+  assert_eq!(second.indexes[2].index_no.into_inner(), 2);
+  assert_eq!(second.indexes[2].file, Some(1));
+}
+
+#[test]
+fn clone_eq() {
+  let cuesheet = CueSheet::from_str(CUESHEET).unwrap();
+  let mut copy = cuesheet.clone();
+
+  assert_eq!(cuesheet, copy);
+
+  copy.tracks[0].title = None;
+  assert_ne!(cuesheet, copy);
+}
+
+#[test]
+fn send_across_threads() {
+  let cuesheet = CueSheet::from_str(CUESHEET).unwrap();
+  let handle = std::thread::spawn(move || cuesheet.tracks.len());
+
+  assert_eq!(handle.join().unwrap(), 2);
+}
+
+#[test]
+fn invalid_cuesheet() {
+  assert!(CueSheet::from_str("TITLE \"No Tracks\"").is_err());
+}
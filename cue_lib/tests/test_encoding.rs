@@ -0,0 +1,67 @@
+#![cfg(feature = "encoding")]
+
+use cue_lib::encoding::{DecodedCueSheet, Encoding, EncodingSource};
+
+const SHIFT_JIS: &[u8] = b"TITLE \"\x93\x8C\x95\xFB\x8Dg\x96\x82\x8B\xBD\x81@\x81` the Embodiment of Scarlet Devil.\"\nFILE \"\x93\x8C\x95\xFB\x8Dg\x96\x82\x8B\xBD.wav\" WAVE\n  TRACK 01 AUDIO\n    TITLE \"\x90\xD4\x82\xE6\x82\xE8\x8Dg\x82\xA2\x96\xB2\"\n    INDEX 01 00:00:00\n";
+
+const WINDOWS_1252: &[u8] = b"PERFORMER \"Beyonc\xE9\"\nTITLE \"Caf\xE9 del Mar \x96 Volumen Uno\"\nFILE \"a.wav\" WAVE\n  TRACK 01 AUDIO\n    TITLE \"Se\xF1or\"\n    INDEX 01 00:00:00\n";
+
+const UTF8: &str =
+  "TITLE \"赤より紅い夢\"\nFILE \"a.wav\" WAVE\n  TRACK 01 AUDIO\n    INDEX 01 00:00:00\n";
+
+#[test]
+fn detect_shift_jis() {
+  let decoded = DecodedCueSheet::decode(SHIFT_JIS);
+
+  assert_eq!(decoded.encoding_name(), "Shift_JIS");
+  assert_eq!(decoded.source(), EncodingSource::Heuristic);
+  assert!(!decoded.had_errors());
+
+  let probe = decoded.probe().unwrap();
+  let track = probe.tracks().next_track().unwrap().unwrap();
+  assert_eq!(track.title().unwrap().to_string(), "赤より紅い夢");
+}
+
+#[test]
+fn detect_windows_1252() {
+  let decoded = DecodedCueSheet::decode(WINDOWS_1252);
+
+  assert_eq!(decoded.encoding_name(), "windows-1252");
+  assert_eq!(decoded.source(), EncodingSource::Heuristic);
+  assert!(decoded.as_str().contains("Café del Mar – Volumen Uno"));
+}
+
+#[test]
+fn utf16_bom() {
+  let mut bytes = vec![0xFF, 0xFE];
+  bytes.extend(UTF8.encode_utf16().flat_map(|v| v.to_le_bytes()));
+
+  let decoded = DecodedCueSheet::decode(&bytes);
+
+  assert_eq!(decoded.encoding_name(), "UTF-16LE");
+  assert_eq!(decoded.source(), EncodingSource::Bom);
+  assert_eq!(decoded.as_str(), UTF8);
+}
+
+#[test]
+fn utf8_input() {
+  let decoded = DecodedCueSheet::decode(UTF8.as_bytes());
+
+  assert_eq!(decoded.encoding_name(), "UTF-8");
+  assert_eq!(decoded.source(), EncodingSource::Utf8);
+  assert_eq!(decoded.as_str(), UTF8);
+  assert!(decoded.probe().is_ok());
+}
+
+#[test]
+fn explicit_encoding() {
+  let decoded = DecodedCueSheet::decode_with_label(SHIFT_JIS, "windows-1252").unwrap();
+  assert_eq!(decoded.encoding_name(), "windows-1252");
+  assert_eq!(decoded.source(), EncodingSource::Explicit);
+
+  let decoded = DecodedCueSheet::decode_with(SHIFT_JIS, Encoding::for_label(b"sjis").unwrap());
+  assert_eq!(decoded.encoding_name(), "Shift_JIS");
+  assert!(decoded.as_str().contains("赤より紅い夢"));
+
+  assert!(DecodedCueSheet::decode_with_label(SHIFT_JIS, "unknown").is_err());
+}
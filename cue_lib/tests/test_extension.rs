@@ -0,0 +1,105 @@
+use cue_lib::{
+  core::command::Command,
+  error::{CueLibErrorKind, ParseErrorKind},
+  lexer::CommandStream,
+  probe::{CueSheetProbe, ParseOptions, extension::CommandExtension},
+};
+
+const CUESHEET: &str = r#"CDDBID 3B0A4C05
+TITLE "Album"
+TOC_INFO1 0 16777216
+FILE "album.wav" WAVE
+  TRACK 01 AUDIO
+    LYRICIST "Lyricist 1"
+    INDEX 01 00:00:00
+  TRACK 02 AUDIO
+    SIZE_INFO 0x01
+    LYRICIST "Lyricist 2"
+    INDEX 01 04:10:00
+"#;
+
+/// Reads `CDDBID` as a 32-bit hex value.
+struct CddbId;
+
+impl<'a> CommandExtension<'a> for CddbId {
+  type Output = u32;
+
+  fn parse(&self, name: &'a str, args: &'a str) -> Option<Self::Output> {
+    if name == "CDDBID" {
+      u32::from_str_radix(args.trim(), 16).ok()
+    } else {
+      None
+    }
+  }
+}
+
+fn lyricist<'a>(name: &'a str, args: &'a str) -> Option<&'a str> {
+  match name {
+    "LYRICIST" => Some(args.trim().trim_matches('"')),
+    _ => None,
+  }
+}
+
+#[test]
+fn strict_parse_rejects_unknown() {
+  match CueSheetProbe::new(CUESHEET) {
+    Ok(_) => panic!("unknown commands should've failed"),
+    Err(err) => match err.kind() {
+      CueLibErrorKind::ParseError(err) => {
+        assert_eq!(err.kind(), ParseErrorKind::UnknownCommand);
+        assert_eq!(err.line(), 0);
+      }
+    },
+  }
+}
+
+#[test]
+fn album_extensions() {
+  let options = ParseOptions::new().set_unknown_commands(true);
+  let probe = CueSheetProbe::new_with_options(CUESHEET, options).unwrap();
+
+  assert_eq!(probe.album_title().unwrap(), "Album");
+  assert_eq!(probe.extensions(CddbId).collect::<Vec<_>>(), [0x3B0A4C05]);
+
+  let names: Vec<&str> = probe.extensions(|name, _| Some(name)).collect();
+  assert_eq!(names, ["CDDBID", "TOC_INFO1"]);
+}
+
+#[test]
+fn track_extensions() {
+  let options = ParseOptions::new().set_unknown_commands(true);
+  let probe = CueSheetProbe::new_with_options(CUESHEET, options).unwrap();
+  let mut tracks = probe.tracks();
+
+  let track = tracks.next_track().unwrap().unwrap();
+  assert_eq!(
+    track.extensions(lyricist).collect::<Vec<_>>(),
+    ["Lyricist 1"]
+  );
+  assert_eq!(track.extensions(CddbId).count(), 0);
+
+  let track = tracks.next_track().unwrap().unwrap();
+  assert_eq!(
+    track.extensions(lyricist).collect::<Vec<_>>(),
+    ["Lyricist 2"]
+  );
+  assert_eq!(track.sub_indexes().next_index().unwrap(), None);
+  assert!(tracks.next_track().unwrap().is_none());
+}
+
+#[test]
+fn unknown_command_stream() {
+  let commands: Vec<Command> = CommandStream::new("SIZE_INFO 0x01 0x02\nTITLE \"A\"")
+    .set_unknown_commands(true)
+    .map(|v| v.unwrap().command())
+    .collect();
+
+  assert_eq!(commands.len(), 2);
+  assert!(matches!(
+    commands[0],
+    Command::Unknown {
+      name: "SIZE_INFO",
+      args: "0x01 0x02"
+    }
+  ));
+}
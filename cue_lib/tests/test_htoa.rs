@@ -0,0 +1,70 @@
+#![cfg(feature = "alloc")]
+
+use cue_lib::{
+  core::timestamp::CueTimeStamp, document::CueSheet, probe::CueSheetProbe, timing::TimeRange,
+};
+use std::str::FromStr;
+
+const HTOA: &str = r#"FILE "album.wav" WAVE
+  TRACK 01 AUDIO
+    INDEX 00 00:00:00
+    INDEX 01 01:23:45
+  TRACK 02 AUDIO
+    INDEX 01 05:00:00
+"#;
+
+#[test]
+fn probe_hidden_track() {
+  let probe = CueSheetProbe::new(HTOA).unwrap();
+  assert_eq!(
+    probe.hidden_track().unwrap(),
+    Some(CueTimeStamp::from_str("01:23:45").unwrap())
+  );
+
+  let not_hidden = [
+    "FILE \"a.wav\" WAVE\n  TRACK 01 AUDIO\n    INDEX 01 00:00:00",
+    "FILE \"a.wav\" WAVE\n  TRACK 01 AUDIO\n    INDEX 00 00:00:00\n    INDEX 01 00:00:00",
+    "FILE \"a.wav\" WAVE\n  TRACK 01 AUDIO\n    INDEX 00 00:00:01\n    INDEX 01 00:32:00",
+    "FILE \"a.wav\" WAVE\n  TRACK 01 AUDIO\n    INDEX 00 00:00:00\nFILE \"b.wav\" WAVE\n    INDEX 01 00:00:00",
+  ];
+
+  for cuesheet in not_hidden {
+    let probe = CueSheetProbe::new(cuesheet).unwrap();
+    assert_eq!(probe.hidden_track().unwrap(), None, "{cuesheet}");
+  }
+}
+
+#[test]
+fn document_hidden_track() {
+  let cuesheet = CueSheet::from_str(HTOA).unwrap();
+  let hidden_track = cuesheet.hidden_track().unwrap();
+
+  assert_eq!(hidden_track.track_no.into_inner(), 0);
+  assert_eq!(
+    hidden_track.audio,
+    TimeRange {
+      file: Some(0),
+      start: CueTimeStamp::ZERO,
+      end: Some(CueTimeStamp::from_str("01:23:45").unwrap()),
+    }
+  );
+  assert_eq!(hidden_track.duration(), hidden_track.end());
+  assert_eq!(
+    cuesheet.track_timings(&[])[0].start(),
+    hidden_track.end().unwrap()
+  );
+
+  let timings = cuesheet.track_timings_with_htoa(&[]);
+  assert_eq!(timings.len(), 3);
+  assert_eq!(timings[0], hidden_track);
+  assert_eq!(timings[1].track_no.into_inner(), 1);
+  assert_eq!(timings[1].pregap_audio, None);
+  assert_eq!(timings[2], cuesheet.track_timings(&[])[1]);
+
+  let cuesheet =
+    CueSheet::from_str("FILE \"a.wav\" WAVE\n  TRACK 01 AUDIO\n    INDEX 01 00:00:00").unwrap();
+  assert_eq!(
+    cuesheet.track_timings_with_htoa(&[]),
+    cuesheet.track_timings(&[])
+  );
+}
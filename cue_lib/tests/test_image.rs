@@ -0,0 +1,340 @@
+#![cfg(feature = "alloc")]
+
+use cue_lib::{
+  addressing::FileSize,
+  core::{
+    album_file::KnownFileType,
+    timestamp::CueTimeStamp,
+    track::{DataType, TrackNo},
+  },
+  document::CueSheet,
+  image::{
+    DataTrack, ImageCopy, ImageErrorKind, ImagePlan,
+    iso9660::{PRIMARY_VOLUME_SECTOR, PrimaryVolume},
+    sector::{SYNC, SectorError, verify_sector},
+    swap_bytes, user_data,
+  },
+  writer::CueWriter,
+};
+use std::str::FromStr;
+
+const GAME_CUE: &str = r#"FILE "game.bin" BINARY
+  TRACK 01 MODE1/2352
+    INDEX 01 00:00:00
+  TRACK 02 AUDIO
+    INDEX 00 00:10:00
+    INDEX 01 00:12:00
+  TRACK 03 AUDIO
+    INDEX 01 00:20:00
+"#;
+
+// 750 data sectors, 750 sectors for track 2 with its pregap and 150 sectors for track 3.
+const GAME_SIZE: u64 = (750 + 750 + 150) * 2352;
+
+#[test]
+fn split_image() {
+  let cuesheet = CueSheet::from_str(GAME_CUE).unwrap();
+  let plan = ImagePlan::split(&cuesheet, &[GAME_SIZE], |track| {
+    format!("game (Track {}).bin", track.track_no)
+  })
+  .unwrap();
+
+  assert_eq!(plan.outputs.len(), 3);
+  assert_eq!(
+    plan.outputs[1],
+    [ImageCopy {
+      file: 0,
+      start: 750 * 2352,
+      end: 1500 * 2352,
+      swap_bytes: false
+    }]
+  );
+  assert_eq!(plan.outputs[2][0].end, GAME_SIZE);
+  assert_eq!(plan.cuesheet.files.len(), 3);
+  assert_eq!(plan.cuesheet.files[1].name, "game (Track 02).bin");
+
+  let track = &plan.cuesheet.tracks[1];
+  assert_eq!(track.indexes[0].file, Some(1));
+  assert_eq!(track.indexes[0].timestamp, CueTimeStamp::ZERO);
+  assert_eq!(track.indexes[1].timestamp, CueTimeStamp::from_frames(150));
+}
+
+#[test]
+fn merge_image() {
+  let cuesheet = CueSheet::from_str(
+    r#"FILE "data.bin" BINARY
+  TRACK 01 MODE2/2352
+    INDEX 01 00:00:00
+FILE "audio.bin" MOTOROLA
+  TRACK 02 AUDIO
+    INDEX 00 00:00:00
+    INDEX 01 00:02:00
+"#,
+  )
+  .unwrap();
+
+  let plan = ImagePlan::merge(&cuesheet, &[750 * 2352, 300 * 2352], "disc.bin".into()).unwrap();
+
+  assert_eq!(
+    plan.outputs,
+    [[
+      ImageCopy {
+        file: 0,
+        start: 0,
+        end: 750 * 2352,
+        swap_bytes: false
+      },
+      ImageCopy {
+        file: 1,
+        start: 0,
+        end: 300 * 2352,
+        swap_bytes: true
+      }
+    ]]
+  );
+
+  assert_eq!(plan.cuesheet.files[0].file_type, KnownFileType::Binary);
+  assert_eq!(
+    CueWriter::new().to_string(&plan.cuesheet),
+    r#"FILE "disc.bin" BINARY
+  TRACK 01 MODE2/2352
+    INDEX 01 00:00:00
+  TRACK 02 AUDIO
+    INDEX 00 00:10:00
+    INDEX 01 00:12:00
+"#
+  );
+}
+
+#[test]
+fn data_track() {
+  let cuesheet = CueSheet::from_str(GAME_CUE).unwrap();
+  let track = DataTrack::new(&cuesheet, &[FileSize::Bytes(GAME_SIZE)], None).unwrap();
+
+  assert_eq!(track.data_type, DataType::Mode1_2352);
+  assert_eq!(track.lba, 0);
+  assert_eq!(track.copies.len(), 1);
+  assert_eq!(
+    (track.copies[0].start, track.copies[0].end),
+    (0, 750 * 2352)
+  );
+
+  let error = DataTrack::new(
+    &cuesheet,
+    &[FileSize::Bytes(GAME_SIZE)],
+    Some(TrackNo::new(2).unwrap()),
+  )
+  .unwrap_err();
+  assert_eq!(error.kind(), ImageErrorKind::NotDataTrack);
+}
+
+#[test]
+fn enhanced_cd_data_track() {
+  let cuesheet = CueSheet::from_str(
+    r#"FILE "audio.wav" WAVE
+  TRACK 01 AUDIO
+    INDEX 01 00:00:00
+  TRACK 02 AUDIO
+    INDEX 01 01:00:00
+FILE "data.bin" BINARY
+  TRACK 03 MODE1/2352
+    INDEX 01 00:00:00
+"#,
+  )
+  .unwrap();
+
+  let file_sizes = [
+    FileSize::Length(CueTimeStamp::from_frames(9000)),
+    FileSize::Bytes(750 * 2352),
+  ];
+  let tracks = DataTrack::all(&cuesheet, &file_sizes).unwrap();
+
+  assert_eq!(tracks.len(), 1);
+  assert_eq!(tracks[0].track_no, TrackNo::new(3).unwrap());
+  // Second session starts after the audio and the 11400 frames gap.
+  assert_eq!(tracks[0].lba, 9000 + 11400);
+  assert_eq!(
+    tracks[0].copies,
+    [ImageCopy {
+      file: 1,
+      start: 0,
+      end: 750 * 2352,
+      swap_bytes: false
+    }]
+  );
+  assert_eq!(
+    tracks[0].sector_offset(PRIMARY_VOLUME_SECTOR),
+    Some((1, 16 * 2352))
+  );
+
+  let error = DataTrack::all(&cuesheet, &file_sizes[..1]).unwrap_err();
+  assert_eq!(error.kind(), ImageErrorKind::MissingFileSize(1));
+}
+
+#[test]
+fn data_track_in_audio_file() {
+  let cuesheet = CueSheet::from_str(
+    r#"FILE "disc.wav" WAVE
+  TRACK 01 MODE1/2352
+    INDEX 01 00:00:00
+"#,
+  )
+  .unwrap();
+
+  let file_sizes = [FileSize::Length(CueTimeStamp::from_frames(4500))];
+  let error = DataTrack::new(&cuesheet, &file_sizes, None).unwrap_err();
+  assert_eq!(error.kind(), ImageErrorKind::UnsupportedFileType(0));
+}
+
+#[test]
+fn unsupported_file_type() {
+  let cuesheet = CueSheet::from_str(
+    r#"FILE "album.wav" WAVE
+  TRACK 01 AUDIO
+    INDEX 01 00:00:00
+"#,
+  )
+  .unwrap();
+
+  let error = ImagePlan::merge(&cuesheet, &[0], "disc.bin".into()).unwrap_err();
+  assert_eq!(error.kind(), ImageErrorKind::UnsupportedFileType(0));
+}
+
+#[test]
+fn sector_user_data() {
+  let mut sector = [0u8; 2352];
+  sector[16] = 0xAA;
+  sector[24] = 0xBB;
+
+  assert_eq!(user_data(DataType::Mode1_2352, &sector).unwrap()[0], 0xAA);
+  assert_eq!(user_data(DataType::Mode2_2352, &sector).unwrap()[0], 0xBB);
+  assert_eq!(
+    user_data(DataType::Mode1_2352, &sector).unwrap().len(),
+    2048
+  );
+  assert_eq!(user_data(DataType::Audio, &sector), None);
+  assert_eq!(user_data(DataType::Mode1_2048, &sector), None);
+
+  let mut samples = [1, 2, 3, 4, 5];
+  swap_bytes(&mut samples);
+  assert_eq!(samples, [2, 1, 4, 3, 5]);
+}
+
+/// EDC and P/Q parity of an empty MODE1 sector at LBA 0.
+const EMPTY_SECTOR_EDC: &str = "c513682b";
+const EMPTY_SECTOR_PARITY: &str = concat!(
+  "00f700f50000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+  "00000000000000000000000000000000000000000000000000005235b87d000000000000000000f500f4000000000000",
+  "000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000000",
+  "000000000000000000000000000000009726d05600000000000000000041000000000000000000000000000000000000",
+  "00002d172e1bb148b24400000000000000000000000000000000006500c200e600430000000000000000000000000000",
+  "000000000000453c5375332b256200000000000000000000000000000000009000c10012",
+);
+
+fn hex_bytes(hex: &str) -> Vec<u8> {
+  (0..hex.len())
+    .step_by(2)
+    .map(|idx| u8::from_str_radix(&hex[idx..(idx + 2)], 16).unwrap())
+    .collect()
+}
+
+#[test]
+fn raw_sector_checks() {
+  let mut sector = [0u8; 2352];
+  sector[..12].copy_from_slice(&SYNC);
+  sector[12..16].copy_from_slice(&[0x00, 0x02, 0x00, 0x01]);
+  sector[2064..2068].copy_from_slice(&hex_bytes(EMPTY_SECTOR_EDC));
+  sector[2076..].copy_from_slice(&hex_bytes(EMPTY_SECTOR_PARITY));
+
+  assert_eq!(verify_sector(DataType::Mode1_2352, 0, &sector), Ok(()));
+  assert_eq!(
+    verify_sector(DataType::Mode1_2352, 1, &sector),
+    Err(SectorError::Address)
+  );
+  assert_eq!(
+    verify_sector(DataType::Mode2_2352, 0, &sector),
+    Err(SectorError::Mode)
+  );
+  assert_eq!(
+    verify_sector(DataType::Mode1_2352, 0, &sector[..2048]),
+    Err(SectorError::Size)
+  );
+
+  let mut damaged = sector;
+  damaged[100] = 1;
+  assert_eq!(
+    verify_sector(DataType::Mode1_2352, 0, &damaged),
+    Err(SectorError::Edc)
+  );
+
+  let mut damaged = sector;
+  damaged[2100] ^= 1;
+  assert_eq!(
+    verify_sector(DataType::Mode1_2352, 0, &damaged),
+    Err(SectorError::PParity)
+  );
+
+  let mut damaged = sector;
+  damaged[2300] ^= 1;
+  assert_eq!(
+    verify_sector(DataType::Mode1_2352, 0, &damaged),
+    Err(SectorError::QParity)
+  );
+
+  damaged[0] = 0xFF;
+  assert_eq!(
+    verify_sector(DataType::Mode1_2352, 0, &damaged),
+    Err(SectorError::Sync)
+  );
+}
+
+#[test]
+fn mode2_form2_sector() {
+  let mut sector = [0u8; 2352];
+  sector[..12].copy_from_slice(&SYNC);
+  sector[12..16].copy_from_slice(&[0x00, 0x02, 0x00, 0x02]);
+  sector[18] = 0x20;
+  sector[22] = 0x20;
+
+  // Form 2 sectors without EDC are not checked.
+  assert_eq!(verify_sector(DataType::Mode2_2352, 0, &sector), Ok(()));
+
+  sector[2348] = 1;
+  assert_eq!(
+    verify_sector(DataType::Mode2_2352, 0, &sector),
+    Err(SectorError::Edc)
+  );
+  assert_eq!(verify_sector(DataType::Audio, 0, &sector), Ok(()));
+}
+
+#[test]
+fn primary_volume() {
+  let mut data = [0u8; 2048];
+  data[..7].copy_from_slice(b"\x01CD001\x01");
+  data[8..40].copy_from_slice(b"PLAYSTATION                     ");
+  data[40..49].copy_from_slice(b"GAME_DISC");
+  data[80..84].copy_from_slice(&1000u32.to_le_bytes());
+  data[128..130].copy_from_slice(&2048u16.to_le_bytes());
+  data[813..829].copy_from_slice(b"1999031512304500");
+  data[829] = -20i8 as u8;
+
+  let volume = PrimaryVolume::parse(&data).unwrap();
+  assert_eq!(volume.system_id, "PLAYSTATION");
+  assert_eq!(volume.volume_id, "GAME_DISC");
+  assert_eq!(volume.volume_bytes(), 1000 * 2048);
+  assert_eq!(
+    volume.creation_date.unwrap().to_string(),
+    "1999-03-15T12:30:45.00-05:00"
+  );
+
+  data[1] = b'X';
+  assert_eq!(PrimaryVolume::parse(&data), None);
+
+  let cuesheet = CueSheet::from_str(GAME_CUE).unwrap();
+  let track = DataTrack::new(&cuesheet, &[FileSize::Bytes(GAME_SIZE)], None).unwrap();
+  assert_eq!(
+    track.sector_offset(PRIMARY_VOLUME_SECTOR),
+    Some((0, 16 * 2352))
+  );
+  assert_eq!(track.sector_offset(750), None);
+}
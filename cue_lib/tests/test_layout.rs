@@ -0,0 +1,110 @@
+#![cfg(feature = "alloc")]
+
+use cue_lib::{
+  core::{album_file::KnownFileType, timestamp::CueTimeStamp},
+  document::{AlbumFile, CueSheet, Track},
+  layout::{Layout, LayoutErrorKind},
+};
+use std::str::FromStr;
+
+const IMAGE: &str = r#"TITLE "Album"
+FILE "album.wav" WAVE
+  TRACK 01 AUDIO
+    INDEX 00 00:00:00
+    INDEX 01 00:01:00
+  TRACK 02 AUDIO
+    INDEX 00 03:00:00
+    INDEX 01 03:02:00
+  TRACK 03 AUDIO
+    INDEX 01 05:00:00
+    INDEX 02 05:30:00
+"#;
+
+const GAPS_APPENDED: &str = r#"TITLE "Album"
+FILE "01.wav" WAVE
+  TRACK 01 AUDIO
+    INDEX 00 00:00:00
+    INDEX 01 00:01:00
+  TRACK 02 AUDIO
+    INDEX 00 03:00:00
+FILE "02.wav" WAVE
+    INDEX 01 00:00:00
+FILE "03.wav" WAVE
+  TRACK 03 AUDIO
+    INDEX 01 00:00:00
+    INDEX 02 00:30:00
+"#;
+
+const GAPS_PREPENDED: &str = r#"TITLE "Album"
+FILE "01.wav" WAVE
+  TRACK 01 AUDIO
+    INDEX 00 00:00:00
+    INDEX 01 00:01:00
+FILE "02.wav" WAVE
+  TRACK 02 AUDIO
+    INDEX 00 00:00:00
+    INDEX 01 00:02:00
+FILE "03.wav" WAVE
+  TRACK 03 AUDIO
+    INDEX 01 00:00:00
+    INDEX 02 00:30:00
+"#;
+
+fn ts(value: &str) -> CueTimeStamp {
+  CueTimeStamp::from_str(value).unwrap()
+}
+
+fn file_name(track: Option<&Track>) -> AlbumFile {
+  AlbumFile {
+    file_type: KnownFileType::WAVE,
+    name: match track {
+      Some(track) => format!("{}.wav", track.track_no),
+      None => "album.wav".to_string(),
+    },
+  }
+}
+
+fn convert(source: &str, lengths: &[CueTimeStamp], layout: Layout) -> CueSheet {
+  let cuesheet = CueSheet::from_str(source).unwrap();
+  layout.convert(&cuesheet, lengths, file_name).unwrap()
+}
+
+#[test]
+fn image_to_gaps_appended() {
+  let output = convert(IMAGE, &[], Layout::GapsAppended);
+  assert_eq!(output, CueSheet::from_str(GAPS_APPENDED).unwrap());
+}
+
+#[test]
+fn image_to_gaps_prepended() {
+  let output = convert(IMAGE, &[], Layout::GapsPrepended);
+  assert_eq!(output, CueSheet::from_str(GAPS_PREPENDED).unwrap());
+}
+
+#[test]
+fn split_to_image() {
+  let lengths = [ts("03:02:00"), ts("01:58:00")];
+  let output = convert(GAPS_APPENDED, &lengths, Layout::SingleFile);
+  assert_eq!(output, CueSheet::from_str(IMAGE).unwrap());
+
+  let lengths = [ts("03:00:00"), ts("02:00:00")];
+  let output = convert(GAPS_PREPENDED, &lengths, Layout::SingleFile);
+  assert_eq!(output.to_string(), IMAGE);
+}
+
+#[test]
+fn gaps_appended_to_prepended() {
+  let lengths = [ts("03:02:00"), ts("01:58:00")];
+  let output = convert(GAPS_APPENDED, &lengths, Layout::GapsPrepended);
+  assert_eq!(output, CueSheet::from_str(GAPS_PREPENDED).unwrap());
+}
+
+#[test]
+fn missing_file_length() {
+  let cuesheet = CueSheet::from_str(GAPS_APPENDED).unwrap();
+  let err = Layout::SingleFile
+    .convert(&cuesheet, &[ts("03:02:00")], file_name)
+    .unwrap_err();
+
+  assert_eq!(err.kind(), LayoutErrorKind::MissingFileLength(1));
+}
@@ -0,0 +1,77 @@
+use cue_lib::{
+  core::command::Command,
+  lexer::{CommandStream, Position},
+};
+
+const CUESHEET: &str = "\u{feff}TITLE \"東方 \\\"紅魔郷\\\"\"\r\nFILE  \"𝄞.wav\" WAVE\n  TRACK 01 AUDIO\n    REM COMPOSER  ZUN  \n    FLAGS DCP PRE\n    INDEX 01 00:00:00";
+
+fn texts(cuesheet: &str, index: usize) -> Vec<&str> {
+  let command = CommandStream::new(cuesheet).nth(index).unwrap().unwrap();
+  command.arguments().map(|v| &cuesheet[v.range()]).collect()
+}
+
+#[test]
+fn command_spans() {
+  let commands: Vec<&str> = CommandStream::new(CUESHEET)
+    .map(|v| v.unwrap())
+    .map(|v| &CUESHEET[v.span().range()])
+    .collect();
+
+  assert_eq!(
+    commands,
+    [
+      "TITLE \"東方 \\\"紅魔郷\\\"\"",
+      "FILE  \"𝄞.wav\" WAVE",
+      "TRACK 01 AUDIO",
+      "REM COMPOSER  ZUN",
+      "FLAGS DCP PRE",
+      "INDEX 01 00:00:00",
+    ]
+  );
+}
+
+#[test]
+fn argument_spans() {
+  assert_eq!(texts(CUESHEET, 0), ["\"東方 \\\"紅魔郷\\\"\""]);
+  assert_eq!(texts(CUESHEET, 1), ["\"𝄞.wav\"", "WAVE"]);
+  assert_eq!(texts(CUESHEET, 3), ["COMPOSER  ZUN"]);
+  assert_eq!(texts(CUESHEET, 4), ["DCP", "PRE"]);
+  assert_eq!(texts(CUESHEET, 5), ["01", "00:00:00"]);
+  assert!(texts("REM\nTRACK 01 AUDIO", 0).is_empty());
+}
+
+#[test]
+fn positions() {
+  let mut stream = CommandStream::new(CUESHEET);
+  let title = stream.next().unwrap().unwrap();
+
+  assert!(matches!(title.command(), Command::Title { .. }));
+  assert_eq!(title.keyword_span().range(), 3..8);
+  assert_eq!(
+    title.span().start,
+    Position {
+      line: 0,
+      column: 1,
+      utf16_column: 1,
+      offset: 3
+    }
+  );
+
+  let file = stream.next().unwrap().unwrap();
+  let wave = file.arguments().nth(1).unwrap();
+
+  assert_eq!(file.span().start.line, 1);
+  assert_eq!(wave.start.column, 14);
+  assert_eq!(wave.start.utf16_column, 15);
+  assert_eq!(wave.start.offset, CUESHEET.find("WAVE").unwrap());
+  assert_eq!(wave.len(), 4);
+}
+
+#[test]
+fn continues_after_error() {
+  let results: Vec<bool> = CommandStream::new("TITLE \"broken\nFLAGS ???\nTRACK 01 AUDIO\n")
+    .map(|v| v.is_ok())
+    .collect();
+
+  assert_eq!(results, [false, false, true]);
+}
@@ -0,0 +1,222 @@
+#![cfg(feature = "alloc")]
+
+use cue_lib::{
+  core::timestamp::CueTimeStamp,
+  error::Severity,
+  lint::{LintConfig, Rule},
+};
+use std::str::FromStr;
+
+const CUESHEET: &str = r#"TITLE ""
+FILE "album.flac" FLAC
+  TRACK 01 AUDIO
+    TITLE "Same"
+    INDEX 01 00:02:00
+    FLAGS DCP
+  TRACK 02 AUDIO
+    TITLE "Same"
+    PREGAP 00:02:00
+    INDEX 00 00:03:00
+    INDEX 01 00:04:00
+  TRACK 03 AUDIO
+    INDEX 01 01:00:00
+"#;
+
+const CLEAN: &str = r#"TITLE "Album"
+FILE "album.wav" WAVE
+  TRACK 01 AUDIO
+    TITLE "First"
+    FLAGS DCP
+    INDEX 00 00:00:00
+    INDEX 01 00:32:00
+  TRACK 02 AUDIO
+    TITLE "Second"
+    INDEX 01 03:00:00
+FILE "other.wav" WAVE
+  TRACK 03 AUDIO
+    INDEX 01 00:00:00
+"#;
+
+#[test]
+fn default_rules() {
+  let findings = LintConfig::new().lint(CUESHEET).unwrap();
+  let found: Vec<(Rule, usize)> = findings.iter().map(|v| (v.rule(), v.line())).collect();
+
+  assert_eq!(
+    found,
+    [
+      (Rule::EmptyTitle, 0),
+      (Rule::FlacFileType, 1),
+      (Rule::ShortTrack, 2),
+      (Rule::FirstIndexOffset, 4),
+      (Rule::FlagsAfterIndex, 5),
+      (Rule::DuplicateTitle, 7),
+      (Rule::PregapWithIndex00, 9),
+    ]
+  );
+
+  assert_eq!(findings[4].severity(), Severity::Error);
+  assert_eq!(findings[2].track_no().unwrap().into_inner(), 1);
+  assert!(findings[0].track_no().is_none());
+}
+
+#[test]
+fn clean_cuesheet() {
+  assert!(LintConfig::new().lint(CLEAN).unwrap().is_empty());
+}
+
+#[test]
+fn rule_selection() {
+  let config = LintConfig::empty()
+    .enable(Rule::ShortTrack)
+    .set_severity(Rule::FlacFileType, Some(Severity::Error));
+
+  let findings = config.lint(CUESHEET).unwrap();
+
+  assert_eq!(findings.len(), 2);
+  assert_eq!(findings[0].rule(), Rule::FlacFileType);
+  assert_eq!(findings[0].severity(), Severity::Error);
+  assert_eq!(findings[1].rule(), Rule::ShortTrack);
+  assert_eq!(
+    config.disable(Rule::ShortTrack).severity(Rule::ShortTrack),
+    None
+  );
+}
+
+#[test]
+fn rule_names() {
+  for rule in Rule::ALL {
+    assert_eq!(Rule::from_str(rule.as_str()), Ok(rule));
+  }
+
+  assert!(Rule::from_str("unknown").is_err());
+}
+
+#[test]
+fn parse_error() {
+  assert!(LintConfig::new().lint("TITLE \"No Tracks\"").is_err());
+}
+
+#[test]
+fn red_book_profile() {
+  let cuesheet = r#"FILE "album.wav" WAVE
+  TRACK 02 MODE1/2352
+    FLAGS DCP
+    ISRC JPZ9L0800001
+    PREGAP 00:01:00
+    INDEX 01 00:00:00
+  TRACK 03 AUDIO
+    INDEX 01 10:00:00
+    INDEX 100 10:30:00
+  TRACK 04 AUDIO
+    INDEX 01 85:00:00
+"#;
+
+  assert!(LintConfig::new().lint(cuesheet).unwrap().is_empty());
+
+  let findings = LintConfig::red_book().lint(cuesheet).unwrap();
+  let found: Vec<(Rule, usize)> = findings.iter().map(|v| (v.rule(), v.line())).collect();
+
+  assert_eq!(
+    found,
+    [
+      (Rule::FirstTrackPregap, 1),
+      (Rule::TrackNumber, 1),
+      (Rule::DataTrackFlags, 2),
+      (Rule::DataTrackIsrc, 3),
+      (Rule::IndexNumber, 8),
+      (Rule::DiscLength, 9),
+    ]
+  );
+  assert!(findings.iter().all(|v| v.severity() == Severity::Error));
+  assert!(LintConfig::red_book().lint(CLEAN).unwrap().is_empty());
+}
+
+#[test]
+fn red_book_track_count() {
+  let mut cuesheet = String::from("FILE \"album.wav\" WAVE\n");
+
+  for track_no in 1..=100 {
+    cuesheet.push_str(&format!(
+      "  TRACK {track_no:02} AUDIO\n    INDEX 01 {:02}:{:02}:00\n",
+      track_no * 10 / 60,
+      track_no * 10 % 60
+    ));
+  }
+
+  let findings = LintConfig::red_book().lint(&cuesheet).unwrap();
+  let found: Vec<(Rule, usize)> = findings.iter().map(|v| (v.rule(), v.line())).collect();
+
+  assert_eq!(
+    found,
+    [
+      (Rule::FirstIndexOffset, 2),
+      (Rule::TrackCount, 199),
+      (Rule::TrackNumber, 199),
+    ]
+  );
+}
+
+#[test]
+fn red_book_first_track_pregap() {
+  let config = LintConfig::empty().enable(Rule::FirstTrackPregap);
+  let pregaps = [
+    ("", false),
+    ("    INDEX 00 00:00:00\n", true),
+    ("    PREGAP 00:02:00\n", false),
+    ("    PREGAP 00:01:00\n    INDEX 00 00:00:00\n", false),
+  ];
+
+  for (pregap, is_reported) in pregaps {
+    // Hidden track audio is the pregap, it is 1 second long when INDEX 00 is present.
+    let cuesheet =
+      format!("FILE \"album.wav\" WAVE\n  TRACK 01 AUDIO\n{pregap}    INDEX 01 00:01:00\n");
+    let findings = config.lint(&cuesheet).unwrap();
+
+    assert_eq!(!findings.is_empty(), is_reported, "{cuesheet}");
+  }
+
+  let htoa =
+    "FILE \"album.wav\" WAVE\n  TRACK 01 AUDIO\n    INDEX 00 00:00:00\n    INDEX 01 00:02:00\n";
+  assert!(config.lint(htoa).unwrap().is_empty());
+}
+
+#[test]
+fn file_lengths() {
+  let cuesheet = r#"FILE "01.wav" WAVE
+  TRACK 01 AUDIO
+    PREGAP 00:02:00
+    INDEX 01 00:00:00
+FILE "02.wav" WAVE
+  TRACK 02 AUDIO
+    INDEX 01 00:00:00
+"#;
+
+  let config = LintConfig::red_book();
+  let lengths = |lengths: &[&str]| -> Vec<CueTimeStamp> {
+    lengths
+      .iter()
+      .map(|v| CueTimeStamp::from_str(v).unwrap())
+      .collect()
+  };
+
+  assert!(config.lint(cuesheet).unwrap().is_empty());
+  assert!(
+    config
+      .lint_with_file_lengths(cuesheet, &lengths(&["04:00:00", "03:00:00"]))
+      .unwrap()
+      .is_empty()
+  );
+
+  let findings = config
+    .lint_with_file_lengths(cuesheet, &lengths(&["00:03:00", "00:02:00"]))
+    .unwrap();
+  let found: Vec<(Rule, usize)> = findings.iter().map(|v| (v.rule(), v.line())).collect();
+  assert_eq!(found, [(Rule::ShortTrack, 1), (Rule::ShortTrack, 5)]);
+
+  let findings = config
+    .lint_with_file_lengths(cuesheet, &lengths(&["50:00:00", "30:00:00"]))
+    .unwrap();
+  let found: Vec<(Rule, usize)> = findings.iter().map(|v| (v.rule(), v.line())).collect();
+  assert_eq!(found, [(Rule::DiscLength, 5)]);
+}
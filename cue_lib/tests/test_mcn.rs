@@ -0,0 +1,96 @@
+use cue_lib::{
+  discid::{error::McnParseErrorKind, mcn::Mcn},
+  error::{CueLibErrorKind, ParseErrorKind},
+  probe::CueSheetProbe,
+};
+use std::str::FromStr;
+
+macro_rules! test_mcn {
+  ($test_name:ident, $code:literal, expects = $expected:literal) => {
+    #[test]
+    fn $test_name() {
+      match Mcn::from_str($code) {
+        Ok(mcn) => {
+          assert_eq!(&mcn.to_string(), $expected);
+          assert_eq!(&mcn.as_ascii_bytes(), $expected.as_bytes());
+        }
+        Err(err) => panic!("MCN parsing should've succeed, {:?}", err),
+      }
+    }
+  };
+
+  ($test_name:ident, $code:literal, expects_err = $err:expr) => {
+    #[test]
+    fn $test_name() {
+      match Mcn::from_str($code) {
+        Ok(_) => panic!("MCN parsing should've failed."),
+        Err(err) => assert_eq!(err.kind(), $err),
+      }
+    }
+  };
+}
+
+test_mcn!(ean_13, "4988002345670", expects = "4988002345670");
+test_mcn!(upc_a, "724384960650", expects = "0724384960650");
+test_mcn!(
+  zero_padded_upc_a,
+  "0724384960650",
+  expects = "0724384960650"
+);
+test_mcn!(
+  ean_checksum_fail,
+  "4988002345671",
+  expects_err = McnParseErrorKind::ChecksumFail
+);
+test_mcn!(
+  upc_checksum_fail,
+  "724384960651",
+  expects_err = McnParseErrorKind::ChecksumFail
+);
+test_mcn!(
+  invalid_length,
+  "49880023456",
+  expects_err = McnParseErrorKind::InvalidLength
+);
+test_mcn!(
+  invalid_character,
+  "49880023456A0",
+  expects_err = McnParseErrorKind::InvalidCharacter
+);
+
+#[test]
+fn upc_a_conversion() {
+  let upc = Mcn::from_str("724384960650").unwrap();
+  assert_eq!(upc, Mcn::from_str("0724384960650").unwrap());
+  assert_eq!(upc.to_upc_a().unwrap().to_string(), "724384960650");
+  assert!(Mcn::from_str("4988002345670").unwrap().to_upc_a().is_none());
+}
+
+#[test]
+fn catalog_command() {
+  let cuesheet =
+    "CATALOG 724384960650\nFILE \"a.wav\" WAVE\n  TRACK 01 AUDIO\n    INDEX 01 00:00:00";
+  let probe = CueSheetProbe::new(cuesheet).unwrap();
+  assert_eq!(probe.catalog().unwrap().to_string(), "0724384960650");
+
+  let cuesheet =
+    "CATALOG \"0000000000000\"\nFILE \"a.wav\" WAVE\n  TRACK 01 AUDIO\n    INDEX 01 00:00:00";
+  let probe = CueSheetProbe::new(cuesheet).unwrap();
+  assert_eq!(probe.catalog().unwrap().to_string(), "0000000000000");
+
+  let cuesheet = "TITLE \"A\"\nCATALOG  4988002345671\nFILE \"a.wav\" WAVE\n  TRACK 01 AUDIO\n    INDEX 01 00:00:00";
+
+  match CueSheetProbe::new(cuesheet) {
+    Ok(_) => panic!("invalid catalog should've failed"),
+    Err(err) => match err.kind() {
+      CueLibErrorKind::ParseError(err) => {
+        assert!(matches!(
+          err.kind(),
+          ParseErrorKind::McnParseError(err) if err.kind() == McnParseErrorKind::ChecksumFail
+        ));
+        assert_eq!(err.line(), 1);
+        assert_eq!(err.column(), 9);
+      }
+    },
+  }
+}
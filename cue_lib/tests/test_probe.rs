@@ -0,0 +1,117 @@
+use cue_lib::{
+  core::{album_file::KnownFileType, timestamp::CueTimeStamp},
+  error::{CueLibErrorKind, ParseErrorKind},
+  probe::CueSheetProbe,
+};
+use std::str::FromStr;
+
+const SINGLE_FILE: &str = r#"PERFORMER "Various Artists"
+TITLE "Single File"
+FILE "image.flac" WAVE
+  TRACK 01 AUDIO
+    TITLE "First"
+    INDEX 01 00:00:00
+  TRACK 02 AUDIO
+    TITLE "Second"
+    INDEX 00 03:20:10
+    INDEX 01 03:22:10
+"#;
+
+const FILE_PER_TRACK: &str = r#"PERFORMER "Various Artists"
+TITLE "File Per Track"
+FILE "01.wav" WAVE
+  TRACK 01 AUDIO
+    TITLE "First"
+    INDEX 01 00:00:00
+  TRACK 02 AUDIO
+    TITLE "Second"
+    INDEX 00 04:58:00
+FILE "02.wav" WAVE
+    INDEX 01 00:00:00
+    INDEX 02 00:10:00
+FILE "03.wav" WAVE
+  TRACK 03 AUDIO
+    TITLE "Third"
+    INDEX 01 00:00:00
+"#;
+
+fn timestamp(value: &str) -> CueTimeStamp {
+  CueTimeStamp::from_str(value).unwrap()
+}
+
+#[test]
+fn single_file_tracks() {
+  let probe = CueSheetProbe::new(SINGLE_FILE).unwrap();
+  let album_file = probe.file_info().unwrap();
+  let mut tracks = probe.tracks();
+
+  assert_eq!(album_file.name, "image.flac");
+  assert_eq!(album_file.file_type, KnownFileType::WAVE);
+
+  let first = tracks.next_track().unwrap().unwrap();
+  assert_eq!(first.file(), Some(album_file));
+  assert_eq!(first.pregap_file(), None);
+
+  let second = tracks.next_track().unwrap().unwrap();
+  assert_eq!(second.file(), Some(album_file));
+  assert_eq!(second.pregap_file(), Some(album_file));
+  assert_eq!(second.pregap_index(), Some(timestamp("03:20:10")));
+
+  assert!(tracks.next_track().unwrap().is_none());
+}
+
+#[test]
+fn file_per_track() {
+  let probe = CueSheetProbe::new(FILE_PER_TRACK).unwrap();
+  let mut tracks = probe.tracks();
+
+  assert_eq!(probe.file_info().unwrap().name, "01.wav");
+
+  let first = tracks.next_track().unwrap().unwrap();
+  assert_eq!(first.title().unwrap(), "First");
+  assert_eq!(first.file().unwrap().name, "01.wav");
+
+  let second = tracks.next_track().unwrap().unwrap();
+  assert_eq!(second.title().unwrap(), "Second");
+  assert_eq!(second.pregap_file().unwrap().name, "01.wav");
+  assert_eq!(second.pregap_index(), Some(timestamp("04:58:00")));
+  assert_eq!(second.file().unwrap().name, "02.wav");
+  assert_eq!(second.start_index(), timestamp("00:00:00"));
+
+  let mut indexes = second.sub_indexes();
+  let index = indexes.next_index().unwrap().unwrap();
+  assert_eq!(index.index_no.into_inner(), 2);
+  assert_eq!(index.timestamp, timestamp("00:10:00"));
+  assert_eq!(indexes.file().unwrap().name, "02.wav");
+  assert!(indexes.next_index().unwrap().is_none());
+
+  let third = tracks.next_track().unwrap().unwrap();
+  assert_eq!(third.title().unwrap(), "Third");
+  assert_eq!(third.file().unwrap().name, "03.wav");
+  assert_eq!(third.pregap_file(), None);
+
+  assert!(tracks.next_track().unwrap().is_none());
+}
+
+#[test]
+fn verify_file_per_track() {
+  assert!(CueSheetProbe::verify(FILE_PER_TRACK).is_ok());
+}
+
+#[test]
+fn verify_index_order_within_same_file() {
+  let cuesheet = r#"FILE "image.wav" WAVE
+  TRACK 01 AUDIO
+    INDEX 01 00:10:00
+    INDEX 02 00:05:00
+"#;
+
+  match CueSheetProbe::verify(cuesheet) {
+    Ok(_) => panic!("decreasing index timestamps should've failed"),
+    Err(err) => match err.kind() {
+      CueLibErrorKind::ParseError(err) => {
+        assert_eq!(err.kind(), ParseErrorKind::InvalidTrackIndex)
+      }
+    },
+  }
+}
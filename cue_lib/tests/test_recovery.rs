@@ -0,0 +1,113 @@
+#![cfg(feature = "alloc")]
+
+use cue_lib::{
+  document::CueSheet,
+  error::{CueLibErrorKind, ParseErrorKind, Severity},
+  probe::CueSheetProbe,
+};
+use std::str::FromStr;
+
+const VALID: &str = r#"REM GENRE "Touhou"
+PERFORMER "ZUN"
+TITLE "Album"
+FILE "01.wav" WAVE
+  TRACK 01 AUDIO
+    TITLE "First"
+    REM COMPOSER "ZUN
+    INDEX 01 00:00:00
+  TRACK 02 AUDIO
+    FLAGS DCP
+    INDEX 00 02:10:00
+FILE "02.wav" WAVE
+    INDEX 01 00:00:00
+    INDEX 02 00:30:00
+"#;
+
+const BROKEN: &str = r#"TITLE "Album"
+TITLE "Again"
+FILE "01.wav" WAVE
+  TRACK 01 AUDIO
+    TITLE "First
+    INDEX 01 00:0x:00
+    FLAGS FOO
+  TRACK 03 AUDIO
+    TITLE "Third"
+    INDEX 01 00:10:00
+    INDEX 03 00:20:00
+"#;
+
+#[test]
+fn valid_cuesheet_has_no_diagnostics() {
+  let (cuesheet, diagnostics) = CueSheet::parse_recovering(VALID);
+
+  assert!(diagnostics.is_empty());
+  assert_eq!(cuesheet, CueSheet::from_str(VALID).unwrap());
+  assert_eq!(cuesheet.tracks[0].remarks, ["COMPOSER \"ZUN"]);
+  assert!(CueSheetProbe::verify_all(VALID).is_ok());
+}
+
+#[test]
+fn reports_every_error() {
+  let (cuesheet, diagnostics) = CueSheet::parse_recovering(BROKEN);
+  let errors: Vec<(usize, ParseErrorKind)> =
+    diagnostics.iter().map(|v| (v.line(), v.kind())).collect();
+
+  assert_eq!(errors.len(), 7);
+  assert_eq!(errors[0], (1, ParseErrorKind::MultipleCommand));
+  assert_eq!(errors[1], (3, ParseErrorKind::InvalidTrackIndex));
+  assert!(matches!(errors[2], (4, ParseErrorKind::CueStrError(_))));
+  assert!(matches!(
+    errors[3],
+    (5, ParseErrorKind::TimeStampParseError(_))
+  ));
+  assert!(matches!(errors[4], (6, ParseErrorKind::FlagParseError(_))));
+  assert_eq!(errors[5], (7, ParseErrorKind::InvalidTrackNo));
+  assert_eq!(errors[6], (10, ParseErrorKind::InvalidTrackIndex));
+  assert!(diagnostics.iter().all(|v| v.severity() == Severity::Error));
+
+  assert!(CueSheet::from_str(BROKEN).is_err());
+  assert_eq!(CueSheetProbe::verify_all(BROKEN).unwrap_err(), diagnostics);
+
+  // Partial result
+  assert_eq!(cuesheet.title.as_deref(), Some("Album"));
+  assert_eq!(cuesheet.tracks.len(), 2);
+  assert_eq!(cuesheet.tracks[0].title, None);
+  assert!(cuesheet.tracks[0].indexes.is_empty());
+  assert_eq!(cuesheet.tracks[1].title.as_deref(), Some("Third"));
+  assert_eq!(cuesheet.tracks[1].indexes.len(), 1);
+  assert_eq!(cuesheet.files[0].name, "01.wav");
+}
+
+#[test]
+fn missing_tracks() {
+  let (cuesheet, diagnostics) =
+    CueSheet::parse_recovering("TITLE \"No Tracks\"\nINDEX 01 00:00:00");
+
+  assert_eq!(cuesheet.title.as_deref(), Some("No Tracks"));
+  assert_eq!(diagnostics.len(), 2);
+  assert_eq!(diagnostics[0].kind(), ParseErrorKind::InvalidCommandUsage);
+  assert_eq!(diagnostics[1].kind(), ParseErrorKind::MissingTrackCommand);
+}
+
+#[test]
+fn agrees_with_probe() {
+  let cuesheets = [
+    "FILE \"a.wav\" WAVE\nTRACK 01 AUDIO\nINDEX 01 00:00:00\nINDEX 01 00:10:00",
+    "FILE \"a.wav\" WAVE\nTRACK 01 AUDIO\nINDEX 01 00:10:00\nINDEX 00 00:00:00",
+    "FILE \"a.wav\" WAVE\nTRACK 01 AUDIO\nINDEX 01 00:10:00\nINDEX 02 00:05:00",
+    "FILE \"a.wav\" WAVE\nTRACK 01 AUDIO\nINDEX 01 00:00:00\nCATALOG 0000000000000",
+    "FILE \"a.wav\" WAVE\nFILE \"b.wav\" WAVE\nTRACK 01 AUDIO\nINDEX 01 00:00:00",
+    "FILE \"a.wav\" WAVE\nTRACK 01 AUDIO\nINDEX 01 00:00:00\nTRACK 03 AUDIO",
+  ];
+
+  for cuesheet in cuesheets {
+    let CueLibErrorKind::ParseError(expected) = CueSheetProbe::verify(cuesheet).unwrap_err().kind();
+    let diagnostics = CueSheetProbe::verify_all(cuesheet).unwrap_err();
+
+    assert_eq!(
+      (diagnostics[0].line(), diagnostics[0].kind()),
+      (expected.line(), expected.kind()),
+      "{cuesheet}"
+    );
+  }
+}
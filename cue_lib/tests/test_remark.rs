@@ -0,0 +1,104 @@
+use cue_lib::probe::{
+  CueSheetProbe,
+  remark::{Remark, RemarkDate},
+};
+
+const CUESHEET: &str = r#"REM GENRE "Touhou"
+REM DATE 2008-08-16
+REM DISCID 3B0A4C05
+REM COMMENT "ExactAudioCopy v1.0b3"
+REM DISCNUMBER 1
+REM TOTALDISCS 2
+REM REPLAYGAIN_ALBUM_GAIN -7.89 dB
+REM REPLAYGAIN_ALBUM_PEAK 0.988525
+REM ACCURATERIPID 0014c94f-00a1e8d1-b10cf30e
+PERFORMER "ZUN"
+FILE "album.wav" WAVE
+  TRACK 01 AUDIO
+    REM REPLAYGAIN_TRACK_GAIN +1.25 dB
+    REM REPLAYGAIN_TRACK_PEAK 0.5
+    REM DATE 2008
+    INDEX 01 00:00:00
+"#;
+
+#[test]
+fn album_remarks() {
+  let probe = CueSheetProbe::new(CUESHEET).unwrap();
+  let remarks: Vec<Remark> = probe.remarks().typed().collect();
+
+  assert_eq!(remarks.len(), 9);
+  assert!(matches!(remarks[0], Remark::Genre(value) if value == "Touhou"));
+  assert_eq!(
+    remarks[1],
+    Remark::Date(RemarkDate::Full {
+      year: 2008,
+      month: 8,
+      day: 16
+    })
+  );
+  assert_eq!(remarks[2], Remark::DiscId(0x3B0A4C05));
+  assert!(matches!(remarks[3], Remark::Comment(value) if value == "ExactAudioCopy v1.0b3"));
+  assert_eq!(remarks[4], Remark::DiscNumber(1));
+  assert_eq!(remarks[5], Remark::TotalDiscs(2));
+  assert_eq!(remarks[6], Remark::ReplayGainAlbumGain(-7.89));
+  assert_eq!(remarks[7], Remark::ReplayGainAlbumPeak(0.988525));
+  assert!(matches!(
+    remarks[8],
+    Remark::Other { key: "ACCURATERIPID", value } if value == "0014c94f-00a1e8d1-b10cf30e"
+  ));
+}
+
+#[test]
+fn track_remarks() {
+  let probe = CueSheetProbe::new(CUESHEET).unwrap();
+  let track = probe.tracks().next_track().unwrap().unwrap();
+  let remarks: Vec<Remark> = track.remarks().typed().collect();
+
+  assert_eq!(
+    remarks,
+    [
+      Remark::ReplayGainTrackGain(1.25),
+      Remark::ReplayGainTrackPeak(0.5),
+      Remark::Date(RemarkDate::Year(2008)),
+    ]
+  );
+}
+
+#[test]
+fn invalid_values_fall_back() {
+  assert!(matches!(
+    Remark::parse("DISCID 3B0A4C"),
+    Remark::Other { key: "DISCID", value } if value == "3B0A4C"
+  ));
+  assert!(matches!(
+    Remark::parse("DISCID +1234567"),
+    Remark::Other { key: "DISCID", value } if value == "+1234567"
+  ));
+  assert!(matches!(
+    Remark::parse("DATE 2008-13-01"),
+    Remark::Other { key: "DATE", .. }
+  ));
+  assert!(matches!(
+    Remark::parse("free form comment"),
+    Remark::Other { key: "free", value } if value == "form comment"
+  ));
+  assert_eq!(Remark::parse("discnumber \"3\""), Remark::DiscNumber(3));
+  assert_eq!(
+    Remark::parse("REPLAYGAIN_TRACK_GAIN -3.5dB").key(),
+    "REPLAYGAIN_TRACK_GAIN"
+  );
+}
+
+#[test]
+fn remark_date_display() {
+  assert_eq!(RemarkDate::Year(2008).to_string(), "2008");
+  assert_eq!(
+    RemarkDate::Full {
+      year: 2008,
+      month: 8,
+      day: 16
+    }
+    .to_string(),
+    "2008-08-16"
+  );
+}
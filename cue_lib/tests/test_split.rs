@@ -0,0 +1,126 @@
+#![cfg(feature = "alloc")]
+
+use cue_lib::{
+  core::timestamp::SampleRate,
+  probe::CueSheetProbe,
+  split::{GapMode, SplitPlan, SplitSegment},
+};
+
+const CUESHEET: &str = r#"FILE "album.wav" WAVE
+  TRACK 01 AUDIO
+    INDEX 00 00:00:00
+    INDEX 01 00:01:00
+  TRACK 02 AUDIO
+    INDEX 00 01:00:00
+    INDEX 01 01:02:00
+  TRACK 03 AUDIO
+    PREGAP 00:01:00
+    INDEX 01 02:00:00
+    POSTGAP 00:02:00
+"#;
+
+const SECOND: u64 = 44_100;
+
+fn segment(start: u64, end: Option<u64>) -> Vec<SplitSegment> {
+  vec![SplitSegment {
+    file: Some(0),
+    start_sample: start * SECOND,
+    end_sample: end.map(|v| v * SECOND),
+  }]
+}
+
+fn plan(gap_mode: GapMode) -> SplitPlan {
+  let probe = CueSheetProbe::new(CUESHEET).unwrap();
+  SplitPlan::new(&probe, SampleRate::Hz44100, gap_mode).unwrap()
+}
+
+#[test]
+fn append_to_previous() {
+  let plan = plan(GapMode::AppendToPrevious);
+
+  assert_eq!(plan.files.len(), 1);
+  assert_eq!(plan.tracks.len(), 3);
+  assert_eq!(plan.tracks[0].segments, segment(1, Some(62)));
+  assert_eq!(plan.tracks[1].segments, segment(62, Some(120)));
+  assert_eq!(plan.tracks[1].silence_after, SECOND);
+  assert_eq!(plan.tracks[2].segments, segment(120, None));
+  assert_eq!(plan.tracks[2].silence_before, 0);
+  assert_eq!(plan.tracks[2].silence_after, 2 * SECOND);
+}
+
+#[test]
+fn prepend_to_next() {
+  let plan = plan(GapMode::PrependToNext);
+
+  assert_eq!(plan.tracks[0].segments, segment(0, Some(60)));
+  assert_eq!(plan.tracks[1].segments, segment(60, Some(120)));
+  assert_eq!(plan.tracks[1].silence_after, 0);
+  assert_eq!(plan.tracks[2].silence_before, SECOND);
+}
+
+#[test]
+fn discard() {
+  let plan = plan(GapMode::Discard);
+
+  assert_eq!(plan.tracks[0].segments, segment(1, Some(60)));
+  assert_eq!(plan.tracks[1].segments, segment(62, Some(120)));
+  assert_eq!(plan.tracks[2].silence_before, 0);
+  assert_eq!(plan.tracks[2].silence_after, 2 * SECOND);
+}
+
+#[test]
+fn extract_htoa() {
+  let plan = plan(GapMode::ExtractHtoa);
+
+  assert_eq!(plan.tracks.len(), 4);
+  assert_eq!(plan.tracks[0].track_no.into_inner(), 0);
+  assert_eq!(plan.tracks[0].segments, segment(0, Some(1)));
+  assert_eq!(plan.tracks[1].track_no.into_inner(), 1);
+  assert_eq!(plan.tracks[1].segments, segment(1, Some(62)));
+}
+
+#[test]
+fn gap_in_previous_file() {
+  let cuesheet = r#"FILE "01.wav" WAVE
+  TRACK 01 AUDIO
+    INDEX 01 00:00:00
+  TRACK 02 AUDIO
+    INDEX 00 03:00:00
+FILE "02.wav" WAVE
+    INDEX 01 00:00:00
+"#;
+
+  let probe = CueSheetProbe::new(cuesheet).unwrap();
+  let plan = SplitPlan::new(&probe, SampleRate::Hz48000, GapMode::PrependToNext).unwrap();
+
+  assert_eq!(
+    plan.tracks[1].segments,
+    [
+      SplitSegment {
+        file: Some(0),
+        start_sample: 180 * 48_000,
+        end_sample: None,
+      },
+      SplitSegment {
+        file: Some(1),
+        start_sample: 0,
+        end_sample: None,
+      }
+    ]
+  );
+}
+
+#[test]
+fn htoa_requires_index_00_at_start() {
+  let cuesheet = r#"FILE "album.wav" WAVE
+  TRACK 01 AUDIO
+    INDEX 00 00:00:10
+    INDEX 01 00:01:00
+"#;
+
+  let probe = CueSheetProbe::new(cuesheet).unwrap();
+  let plan = SplitPlan::new(&probe, SampleRate::Hz44100, GapMode::ExtractHtoa).unwrap();
+
+  assert_eq!(plan.tracks.len(), 1);
+  assert_eq!(plan.tracks[0].track_no.into_inner(), 1);
+}
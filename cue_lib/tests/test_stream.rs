@@ -0,0 +1,124 @@
+#![cfg(feature = "alloc")]
+
+use cue_lib::{
+  core::{command::OwnedCommand, track::TrackNo},
+  error::ParseErrorKind,
+  stream::StreamParser,
+};
+
+const CUESHEET: &str = "\u{feff}TITLE \"東方紅魔郷\"\r\nFILE \"01.wav\" WAVE\r\n\r\n  TRACK 01 AUDIO\r\n    REM COMPOSER ZUN\r\n    INDEX 01 00:00:00";
+
+fn collect(parser: &mut StreamParser) -> Vec<OwnedCommand> {
+  let mut commands = Vec::new();
+
+  while let Some(command) = parser.next_command() {
+    commands.push(command.unwrap().into_command());
+  }
+
+  commands
+}
+
+#[test]
+fn chunked_input() {
+  let bytes = CUESHEET.as_bytes();
+  let mut expected = StreamParser::new();
+  expected.push(bytes);
+  expected.finish();
+  let expected = collect(&mut expected);
+
+  assert_eq!(expected.len(), 5);
+  assert_eq!(
+    expected[0],
+    OwnedCommand::Title {
+      value: "東方紅魔郷".to_string()
+    }
+  );
+  assert_eq!(
+    expected[3],
+    OwnedCommand::Remark {
+      value: "COMPOSER ZUN".to_string()
+    }
+  );
+
+  // Every chunk size splits lines and multibyte characters at different points
+  for chunk_size in 1..bytes.len() {
+    let mut parser = StreamParser::new();
+    let mut commands = Vec::new();
+
+    for chunk in bytes.chunks(chunk_size) {
+      parser.push(chunk);
+      commands.extend(collect(&mut parser));
+    }
+
+    assert_eq!(commands.len(), 4);
+    assert!(parser.next_command().is_none());
+    parser.finish();
+    commands.extend(collect(&mut parser));
+    assert_eq!(commands, expected);
+  }
+}
+
+#[test]
+fn positions() {
+  let mut parser = StreamParser::new();
+  parser.push(CUESHEET.as_bytes());
+  parser.finish();
+
+  let mut positions = Vec::new();
+
+  while let Some(command) = parser.next_command() {
+    let command = command.unwrap();
+    positions.push((command.line(), command.column()));
+  }
+
+  assert_eq!(positions, [(0, 1), (1, 0), (3, 2), (4, 4), (5, 4)]);
+}
+
+#[test]
+fn recovers_after_error() {
+  let mut parser = StreamParser::new();
+  parser.push(
+    b"TITLE \"unterminated\nTRACK 1 FOO\nINDEX 01 00:00:00\nTITLE \"\xFF\"\nTRACK 02 AUDIO\n",
+  );
+
+  let error = parser.next_command().unwrap().unwrap_err();
+  assert_eq!(error.line(), 0);
+  assert!(matches!(error.kind(), ParseErrorKind::CueStrError(_)));
+
+  let error = parser.next_command().unwrap().unwrap_err();
+  assert_eq!(error.line(), 1);
+  assert!(matches!(
+    error.kind(),
+    ParseErrorKind::DataTypeParseError(_)
+  ));
+
+  assert!(matches!(
+    parser.next_command(),
+    Some(Ok(ref v)) if matches!(v.command(), OwnedCommand::Index { .. })
+  ));
+
+  let error = parser.next_command().unwrap().unwrap_err();
+  assert_eq!((error.line(), error.column()), (3, 7));
+  assert_eq!(error.kind(), ParseErrorKind::InvalidUtf8);
+
+  assert!(matches!(
+    parser.next_command().unwrap().unwrap().into_command(),
+    OwnedCommand::Track { value } if value.track_no == TrackNo::new(2).unwrap()
+  ));
+  assert!(parser.next_command().is_none());
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn command_reader() {
+  use cue_lib::stream::CommandReader;
+
+  let reader = CommandReader::from_read(CUESHEET.as_bytes());
+  let commands: Vec<OwnedCommand> = reader.map(|v| v.unwrap().into_command()).collect();
+
+  let mut parser = StreamParser::new();
+  parser.push(CUESHEET.as_bytes());
+  parser.finish();
+
+  assert_eq!(commands, collect(&mut parser));
+}
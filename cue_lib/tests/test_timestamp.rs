@@ -1,8 +1,8 @@
 use cue_lib::core::{
   error::TimeStampParseErrorKind,
-  timestamp::{CueTimeStamp, Frame, Second},
+  timestamp::{CueTimeStamp, Frame, SampleRate, Second},
 };
-use std::str::FromStr;
+use std::{str::FromStr, time::Duration};
 
 macro_rules! test_timestamp {
   ($test_name:ident, $timestamp:literal, milli_seconds = $ms:expr ) => {
@@ -44,6 +44,74 @@ fn duration_conversion() {
 
   assert_eq!(timestamp.as_duration().as_millis(), timestamp.as_millis());
   assert_eq!(timestamp.to_string(), "12:34:74");
+  assert_eq!(
+    timestamp.as_duration(),
+    Duration::new(12 * 60 + 34, 986_666_666)
+  );
+  assert_eq!(CueTimeStamp::from(timestamp.as_duration()), timestamp);
+}
+
+#[test]
+fn millis_round_trip() {
+  for frames in 0..(75 * 120) {
+    let timestamp = CueTimeStamp::from_frames(frames);
+    assert_eq!(CueTimeStamp::from_millis(timestamp.as_millis()), timestamp);
+  }
+
+  assert_eq!(CueTimeStamp::from_millis(60_000).to_string(), "01:00:00");
+  assert_eq!(CueTimeStamp::from_millis(1_006).as_frames(), 75);
+}
+
+#[test]
+fn sample_conversion() {
+  let timestamp = CueTimeStamp::from_str("03:22:63").unwrap();
+  let expected = [
+    (SampleRate::Hz44100, 15213 * 588),
+    (SampleRate::Hz48000, 15213 * 640),
+    (SampleRate::Hz88200, 15213 * 1176),
+    (SampleRate::Hz96000, 15213 * 1280),
+    (SampleRate::Hz192000, 15213 * 2560),
+  ];
+
+  for (sample_rate, samples) in expected {
+    assert_eq!(timestamp.as_samples(sample_rate), samples);
+    assert_eq!(CueTimeStamp::from_samples(samples, sample_rate), timestamp);
+    assert_eq!(
+      CueTimeStamp::from_samples(samples + sample_rate.samples_per_frame() - 1, sample_rate),
+      timestamp
+    );
+    assert_eq!(SampleRate::from_hz(sample_rate.as_hz()), Some(sample_rate));
+  }
+
+  assert_eq!(SampleRate::from_hz(22_050), None);
+}
+
+#[test]
+fn arithmetic() {
+  let a = CueTimeStamp::from_str("01:59:74").unwrap();
+  let b = CueTimeStamp::from_str("00:00:01").unwrap();
+
+  assert_eq!((a + b).to_string(), "02:00:00");
+  assert_eq!((a + b - b), a);
+  assert!(a > b);
+  assert_eq!(b.checked_sub(a), None);
+  assert_eq!(b.saturating_sub(a), CueTimeStamp::ZERO);
+  assert_eq!(CueTimeStamp::MAX.checked_add(b), None);
+  assert_eq!(CueTimeStamp::MAX.saturating_add(b), CueTimeStamp::MAX);
+  assert_eq!(
+    CueTimeStamp::new(u64::MAX, Second::new(59).unwrap(), Frame::new(74).unwrap()),
+    CueTimeStamp::MAX
+  );
+  assert_eq!(CueTimeStamp::MAX.as_samples(SampleRate::Hz44100), u64::MAX);
+
+  let mut c = a;
+  c += b;
+  c -= a;
+  assert_eq!(c, b);
+  assert_eq!(
+    (c.minute(), c.second().into_inner(), c.frame().into_inner()),
+    (0, 0, 1)
+  );
 }
 
 test_timestamp!(timestamp_00_00_00, "00:00:00", milli_seconds = 0);
@@ -66,7 +134,7 @@ test_timestamp!(
 test_timestamp!(
   timestamp_123_59_74,
   "123:59:74",
-  milli_seconds = ((123 * 60) + 59) * 1000 + (1000 / 75) * 74
+  milli_seconds = ((123 * 60) + 59) * 1000 + 74 * 1000 / 75
 );
 
 test_timestamp!(
@@ -0,0 +1,83 @@
+#![cfg(feature = "alloc")]
+
+use cue_lib::{core::timestamp::CueTimeStamp, document::CueSheet, timing::TimeRange};
+use std::str::FromStr;
+
+fn ts(value: &str) -> CueTimeStamp {
+  CueTimeStamp::from_str(value).unwrap()
+}
+
+#[test]
+fn single_file() {
+  let cuesheet = CueSheet::from_str(
+    r#"FILE "album.wav" WAVE
+  TRACK 01 AUDIO
+    INDEX 01 00:00:00
+  TRACK 02 AUDIO
+    PREGAP 00:01:00
+    INDEX 00 03:00:00
+    INDEX 01 03:02:00
+    POSTGAP 00:02:00
+  TRACK 03 AUDIO
+    INDEX 01 05:00:00
+"#,
+  )
+  .unwrap();
+
+  let timings = cuesheet.track_timings(&[]);
+  assert_eq!(timings.len(), 3);
+  assert_eq!(timings[0].end(), Some(ts("03:00:00")));
+  assert_eq!(timings[0].duration(), Some(ts("03:00:00")));
+
+  assert_eq!(timings[1].pregap_silence, Some(ts("00:01:00")));
+  assert_eq!(
+    timings[1].pregap_audio,
+    Some(TimeRange {
+      file: Some(0),
+      start: ts("03:00:00"),
+      end: Some(ts("03:02:00")),
+    })
+  );
+  assert_eq!(timings[1].pregap_duration(), Some(ts("00:03:00")));
+  assert_eq!(timings[1].start(), ts("03:02:00"));
+  assert_eq!(timings[1].duration(), Some(ts("01:58:00")));
+  assert_eq!(timings[1].postgap_silence, Some(ts("00:02:00")));
+
+  assert_eq!(timings[2].end(), None);
+
+  let timings = cuesheet.track_timings(&[ts("07:30:00")]);
+  assert_eq!(timings[2].end(), Some(ts("07:30:00")));
+  assert_eq!(timings[2].duration(), Some(ts("02:30:00")));
+}
+
+#[test]
+fn multiple_files() {
+  let cuesheet = CueSheet::from_str(
+    r#"FILE "01.wav" WAVE
+  TRACK 01 AUDIO
+    INDEX 01 00:00:00
+  TRACK 02 AUDIO
+    INDEX 00 04:00:00
+FILE "02.wav" WAVE
+    INDEX 01 00:00:00
+FILE "03.wav" WAVE
+  TRACK 03 AUDIO
+    INDEX 01 00:00:00
+"#,
+  )
+  .unwrap();
+
+  let timings = cuesheet.track_timings(&[]);
+  assert_eq!(timings[0].end(), Some(ts("04:00:00")));
+  assert_eq!(timings[1].audio.file, Some(1));
+  assert_eq!(timings[1].end(), None);
+  assert_eq!(timings[1].pregap_audio.unwrap().end, None);
+  assert_eq!(timings[1].pregap_duration(), None);
+
+  let lengths = [ts("04:02:00"), ts("03:00:00"), ts("02:00:00")];
+  let timings = cuesheet.track_timings(&lengths);
+  assert_eq!(timings[1].pregap_duration(), Some(ts("00:02:00")));
+  assert_eq!(timings[1].duration(), Some(ts("03:00:00")));
+  assert_eq!(timings[2].audio.file, Some(2));
+  assert_eq!(timings[2].duration(), Some(ts("02:00:00")));
+}
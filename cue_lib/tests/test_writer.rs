@@ -0,0 +1,94 @@
+#![cfg(feature = "alloc")]
+
+use cue_lib::{
+  document::CueSheet,
+  writer::{CueWriter, Indent, LineEnding, Quoted},
+};
+use std::str::FromStr;
+
+const CUESHEET: &str = r#"REM GENRE "Touhou"
+REM DATE 2008
+PERFORMER "ZUN"
+TITLE "東方 \"紅魔郷\" \\ EoSD"
+FILE "01.wav" WAVE
+  TRACK 01 AUDIO
+    INDEX 01 00:00:00
+    FLAGS PRE DCP
+    ISRC JPZ9L0800001
+    TITLE "赤より紅い夢"
+  TRACK 02 AUDIO
+    TITLE "ほおずきみたいに紅い魂"
+    PREGAP 00:02:00
+    REM COMPOSER ZUN
+    INDEX 00 02:10:00
+FILE "02.wav" WAVE
+    INDEX 01 00:00:00
+    INDEX 02 00:30:00
+    POSTGAP 00:01:00
+"#;
+
+const EXPECTED: &str = r#"REM GENRE "Touhou"
+REM DATE 2008
+TITLE "東方 \"紅魔郷\" \\ EoSD"
+PERFORMER "ZUN"
+FILE "01.wav" WAVE
+  TRACK 01 AUDIO
+    TITLE "赤より紅い夢"
+    FLAGS DCP PRE
+    ISRC JPZ9L0800001
+    INDEX 01 00:00:00
+  TRACK 02 AUDIO
+    TITLE "ほおずきみたいに紅い魂"
+    REM COMPOSER ZUN
+    PREGAP 00:02:00
+    INDEX 00 02:10:00
+FILE "02.wav" WAVE
+    INDEX 01 00:00:00
+    INDEX 02 00:30:00
+    POSTGAP 00:01:00
+"#;
+
+#[test]
+fn canonical_output() {
+  let cuesheet = CueSheet::from_str(CUESHEET).unwrap();
+  assert_eq!(CueWriter::new().to_string(&cuesheet), EXPECTED);
+  assert_eq!(cuesheet.to_string(), EXPECTED);
+}
+
+#[test]
+fn round_trip() {
+  let cuesheet = CueSheet::from_str(CUESHEET).unwrap();
+  let output = CueWriter::new().to_string(&cuesheet);
+
+  assert_eq!(CueSheet::from_str(&output).unwrap(), cuesheet);
+}
+
+#[test]
+fn crlf_and_tab_indent() {
+  let cuesheet = CueSheet::from_str(CUESHEET).unwrap();
+  let output = CueWriter::new()
+    .set_indent(Indent::Tab)
+    .set_line_ending(LineEnding::CRLF)
+    .to_string(&cuesheet);
+
+  assert!(output.contains("FILE \"01.wav\" WAVE\r\n\tTRACK 01 AUDIO\r\n\t\tTITLE"));
+  assert_eq!(output.lines().count(), EXPECTED.lines().count());
+  assert_eq!(CueSheet::from_str(&output).unwrap(), cuesheet);
+}
+
+#[cfg(feature = "std")]
+#[test]
+fn io_writer() {
+  let cuesheet = CueSheet::from_str(CUESHEET).unwrap();
+  let mut buffer: Vec<u8> = Vec::new();
+
+  CueWriter::new().write_io(&cuesheet, &mut buffer).unwrap();
+  assert_eq!(String::from_utf8(buffer).unwrap(), EXPECTED);
+}
+
+#[test]
+fn quoted_escapes() {
+  assert_eq!(Quoted("").to_string(), "\"\"");
+  assert_eq!(Quoted("a \"b\" \\c").to_string(), "\"a \\\"b\\\" \\\\c\"");
+  assert_eq!(Quoted("line\nbreak").to_string(), "\"line break\"");
+}
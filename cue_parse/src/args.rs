@@ -1,5 +1,10 @@
 use clap::{Parser, Subcommand, ValueEnum};
-use std::{ffi::OsString, path::PathBuf};
+use cue_lib::{
+  core::timestamp::CueTimeStamp,
+  encoding::{Encoding, UnknownEncoding},
+  lint::Rule,
+};
+use std::{ffi::OsString, path::PathBuf, str::FromStr};
 
 #[derive(Parser, Debug)]
 #[command(version, about, long_about = None)]
@@ -10,6 +15,10 @@ pub struct Args {
   #[arg(short, long)]
   pub verbose: Option<VerboseLevel>,
 
+  /// Input encoding label (e.g. shift_jis, windows-1252, utf-16le), detected when not set
+  #[arg(short, long, value_parser = parse_encoding)]
+  pub encoding: Option<&'static Encoding>,
+
   #[command(subcommand)]
   pub command: Commands,
 }
@@ -17,6 +26,8 @@ pub struct Args {
 #[derive(Subcommand, Debug)]
 pub enum Commands {
   Verify,
+  /// Converts the cuesheet to JSON. The `file`, `pregap_file` and `sub_indexes` keys are kept for
+  /// compatibility, prefer `files` and `indexes`
   ConvertJson {
     #[arg(short, long)]
     output_file: Option<PathBuf>,
@@ -27,12 +38,69 @@ pub enum Commands {
 
     #[arg(short, long)]
     pretty_print: bool,
+
+    /// Length (MM:SS:FF) of each FILE in order, the end and duration of a file's last track stay
+    /// unknown without it
+    #[arg(short = 'l', long, value_name = "LENGTH", value_parser = CueTimeStamp::from_str)]
+    file_length: Vec<CueTimeStamp>,
+  },
+  /// Checks the cuesheet for common mistakes
+  Lint {
+    /// Enables the strict Red Book (audio CD) profile
+    #[arg(long)]
+    red_book: bool,
+
+    /// Disables the rule
+    #[arg(short, long, value_name = "RULE")]
+    allow: Vec<Rule>,
+
+    /// Reports the rule as warning
+    #[arg(short, long, value_name = "RULE")]
+    warn: Vec<Rule>,
+
+    /// Reports the rule as error
+    #[arg(short, long, value_name = "RULE")]
+    deny: Vec<Rule>,
+
+    /// Length (MM:SS:FF) of each FILE in order, the last track of a file is only checked with it
+    #[arg(short = 'l', long, value_name = "LENGTH", value_parser = CueTimeStamp::from_str)]
+    file_length: Vec<CueTimeStamp>,
+  },
+  /// Splits, merges or extracts BINARY disc images, files are read next to the input cuesheet
+  Image {
+    #[command(subcommand)]
+    command: ImageCommands,
   },
   Query {
     input: OsString,
   },
 }
 
+#[derive(Subcommand, Debug)]
+pub enum ImageCommands {
+  /// Writes one BIN file per track with a new cuesheet
+  Split {
+    #[arg(short, long)]
+    output_dir: PathBuf,
+  },
+  /// Writes every track into a single BIN file, the new cuesheet is written next to it
+  Merge {
+    #[arg(short, long)]
+    output_file: PathBuf,
+  },
+  /// Writes the 2048 bytes user data of a data track as an ISO image
+  ExtractIso {
+    #[arg(short, long)]
+    output_file: PathBuf,
+
+    /// Data track number, the first data track when not set
+    #[arg(short, long)]
+    track: Option<u8>,
+  },
+  /// Checks the sync, header, EDC and ECC of every raw data sector
+  Verify,
+}
+
 #[derive(Copy, Clone, PartialEq, Eq, PartialOrd, Ord, ValueEnum, Debug)]
 pub enum VerboseLevel {
   Default,
@@ -40,6 +108,10 @@ pub enum VerboseLevel {
   Quiet,
 }
 
+fn parse_encoding(label: &str) -> Result<&'static Encoding, UnknownEncoding> {
+  Encoding::for_label(label.as_bytes()).ok_or(UnknownEncoding)
+}
+
 impl Args {
   #[inline]
   pub fn init() -> Self {
@@ -88,6 +88,77 @@ impl Default for AnsiCodes {
   }
 }
 
+impl AnsiCodes {
+  fn stderr() -> Self {
+    if stderr().is_terminal() {
+      AnsiCodes {
+        error: "\x1B[1;91m",
+        info: "\x1B[1;96m",
+        warning: "\x1B[1;93m",
+        reset: "\x1B[0m",
+      }
+    } else {
+      AnsiCodes::default()
+    }
+  }
+}
+
+/// Prints the cuesheet lines around the error and marks the error column.
+///
+/// Returns the error line if exists.
+fn print_error_section<'a>(
+  input_buffer: &'a str,
+  line_no: usize,
+  column: usize,
+  message: &dyn Display,
+  ansi: &AnsiCodes,
+) -> Option<&'a str> {
+  let mut line_iter = input_buffer.lines().skip(line_no).take(10);
+  let error_line = line_iter.next()?;
+  let column = if column > 0 {
+    column
+  } else {
+    error_line.chars().take_while(|v| v.is_whitespace()).count()
+  };
+
+  eprintln!(
+    "{ansi_info}Cuesheet Section:{reset}",
+    ansi_info = ansi.info,
+    reset = ansi.reset
+  );
+
+  for line in input_buffer
+    .lines()
+    .skip(line_no.saturating_sub(5))
+    .take(line_no.min(5))
+  {
+    eprintln!("{line}");
+  }
+
+  eprintln!(
+    "{ansi_err}{error_line}{reset}",
+    ansi_err = ansi.error,
+    reset = ansi.reset
+  );
+
+  if column > 0 {
+    eprint!("{:>column$}", ' ', column = column)
+  }
+
+  eprintln!(
+    "{ansi_warn}^{dash:->24}{message} at {line_no}:{column}{reset}",
+    dash = ' ',
+    ansi_warn = ansi.warning,
+    reset = ansi.reset
+  );
+
+  for line in line_iter {
+    eprintln!("{line}");
+  }
+
+  Some(error_line)
+}
+
 impl ErrorFormat for cue_lib::error::CueLibError {
   fn fmt(
     &self,
@@ -100,62 +171,12 @@ impl ErrorFormat for cue_lib::error::CueLibError {
         VerboseLevel::Quiet => Ok(()),
         VerboseLevel::Default => std::fmt::Display::fmt(&self, f),
         VerboseLevel::Full => {
-          let ansi = if stderr().is_terminal() {
-            AnsiCodes {
-              error: "\x1B[1;91m",
-              info: "\x1B[1;96m",
-              warning: "\x1B[1;93m",
-              reset: "\x1B[0m",
-            }
-          } else {
-            AnsiCodes::default()
-          };
-
+          let ansi = AnsiCodes::stderr();
           let line_no = parse_error.line();
-          let mut line_iter = input_buffer.lines().skip(line_no).take(10);
-
-          if let Some(error_line) = line_iter.next() {
-            let column = if parse_error.column() > 0 {
-              parse_error.column()
-            } else {
-              error_line.chars().take_while(|v| v.is_whitespace()).count()
-            };
-
-            eprintln!(
-              "{ansi_info}Cuesheet Section:{reset}",
-              ansi_info = ansi.info,
-              reset = ansi.reset
-            );
-
-            for line in input_buffer
-              .lines()
-              .skip(line_no.saturating_sub(5))
-              .take_while(|l| *l != error_line)
-            {
-              eprintln!("{line}");
-            }
-
-            eprintln!(
-              "{ansi_err}{error_line}{reset}",
-              ansi_err = ansi.error,
-              reset = ansi.reset
-            );
-
-            if column > 0 {
-              eprint!("{:>column$}", ' ', column = column)
-            }
-
-            eprintln!(
-              "{ansi_warn}^{dash:->24}{self} at {line_no}:{column}{reset}",
-              dash = ' ',
-              ansi_warn = ansi.warning,
-              reset = ansi.reset
-            );
-
-            for line in line_iter {
-              eprintln!("{line}");
-            }
 
+          if let Some(error_line) =
+            print_error_section(input_buffer, line_no, parse_error.column(), self, &ansi)
+          {
             eprintln!(
               "\n{ansi_info}Line {line_no}: UTF-8 Character Breakdown{reset}",
               ansi_info = ansi.info,
@@ -185,6 +206,61 @@ impl ErrorFormat for cue_lib::error::CueLibError {
   }
 }
 
+/// Every problem reported by the error-recovering parser.
+#[derive(Debug)]
+pub struct Diagnostics(pub Vec<cue_lib::error::Diagnostic>);
+
+impl Display for Diagnostics {
+  fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+    for (idx, diagnostic) in self.0.iter().enumerate() {
+      if idx > 0 {
+        f.write_str("\n")?;
+      }
+
+      Display::fmt(diagnostic, f)?;
+    }
+
+    Ok(())
+  }
+}
+
+impl ErrorFormat for Diagnostics {
+  fn fmt(
+    &self,
+    f: &mut std::fmt::Formatter<'_>,
+    input_buffer: &str,
+    verbose_level: VerboseLevel,
+  ) -> std::fmt::Result {
+    match verbose_level {
+      VerboseLevel::Quiet => Ok(()),
+      VerboseLevel::Default => Display::fmt(&self, f),
+      VerboseLevel::Full => {
+        let ansi = AnsiCodes::stderr();
+
+        for diagnostic in self.0.iter() {
+          let message = format!("{}, {}", diagnostic.severity(), diagnostic.kind());
+
+          if print_error_section(
+            input_buffer,
+            diagnostic.line(),
+            diagnostic.column(),
+            &message,
+            &ansi,
+          )
+          .is_none()
+          {
+            eprintln!("{diagnostic}");
+          }
+
+          eprintln!();
+        }
+
+        write!(f, "{count} problem(s) found", count = self.0.len())
+      }
+    }
+  }
+}
+
 impl<'a, E> core::error::Error for CliError<'a, E> where E: ErrorFormat + Display + Debug {}
 
 macro_rules! cli_stderr {
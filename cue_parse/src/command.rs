@@ -1,6 +1,8 @@
 use crate::cli_error::ErrorFormat;
 
 pub mod convert;
+pub mod image;
+pub mod lint;
 pub mod verify;
 
 pub trait Command
@@ -3,20 +3,14 @@ use self::{
   metadata::{MetadataMap, metadata_from_remarks},
 };
 
-use super::Command;
+use super::{Command, image::read_volumes};
 use cue_lib::{
-  core::{
-    album_file::AlbumFile,
-    cue_str::CueStr,
-    flags::TrackFlag,
-    timestamp::CueTimeStamp,
-    track::{DataType, TrackNo},
-  },
-  discid::isrc::Isrc,
-  probe::{
-    CueSheetProbe,
-    track::{TrackSubIndexes, Tracks},
-  },
+  core::timestamp::CueTimeStamp,
+  discid::mcn::Mcn,
+  document::{AlbumFile, CueSheet, Track},
+  image::iso9660::PrimaryVolume,
+  probe::CueSheetProbe,
+  timing::TrackTiming,
 };
 use serde::Serialize;
 use std::{
@@ -32,35 +26,47 @@ pub struct ConvertCommand<'a> {
   cuesheet: &'a str,
   allow_metadata_remarks: bool,
   output_file: Option<PathBuf>,
+  input_path: Option<PathBuf>,
   pretty_print: bool,
+  file_lengths: Vec<CueTimeStamp>,
 }
 
 #[derive(Serialize)]
-struct CueSheet<'a> {
-  pub catalog: Option<CueStr<'a>>,
-  pub cdtextfile: Option<CueStr<'a>>,
-  pub file: Option<AlbumFile<'a>>,
-  pub performer: Option<CueStr<'a>>,
+struct CueSheetInfo<'a> {
+  pub arranger: Option<&'a str>,
+  pub catalog: Option<Mcn>,
+  pub cdtextfile: Option<&'a str>,
+  pub composer: Option<&'a str>,
+  pub disc_id: Option<&'a str>,
+  /// First file, kept for consumers of the single file output. Superseded by `files`.
+  pub file: Option<&'a AlbumFile>,
+  pub files: &'a [AlbumFile],
+  pub genre: Option<&'a str>,
+  /// Hidden track one audio (HTOA) before track 1's INDEX 01.
+  pub hidden_track: Option<TimeInfo>,
+  pub message: Option<&'a str>,
+  pub performer: Option<&'a str>,
   pub remark_metadata: Option<MetadataMap<'a>>,
-  pub songwriter: Option<CueStr<'a>>,
-  pub title: Option<CueStr<'a>>,
+  pub songwriter: Option<&'a str>,
+  pub title: Option<&'a str>,
   pub tracks: Vec<TrackInfo<'a>>,
+  pub upc_ean: Option<&'a str>,
 }
 
 #[derive(Serialize)]
 struct TrackInfo<'a> {
-  pub data_type: DataType,
-  pub flags: Option<TrackFlag>,
-  pub isrc: Option<Isrc>,
-  pub performer: Option<CueStr<'a>>,
-  pub postgap: Option<CueTimeStamp>,
-  pub pregap: Option<CueTimeStamp>,
+  #[serde(flatten)]
+  pub track: &'a Track,
+  /// File of INDEX 01, kept for older consumers. Superseded by `indexes`.
+  pub file: Option<&'a AlbumFile>,
+  /// File of INDEX 00, kept for older consumers. Superseded by `indexes`.
+  pub pregap_file: Option<&'a AlbumFile>,
   pub remark_metadata: Option<MetadataMap<'a>>,
-  pub songwriter: Option<CueStr<'a>>,
+  /// Timestamps of INDEX 02 and later, kept for older consumers. Superseded by `indexes`.
   pub sub_indexes: Option<Vec<CueTimeStamp>>,
   pub time_info: TimeInfo,
-  pub title: Option<CueStr<'a>>,
-  pub track_no: TrackNo,
+  /// ISO 9660 volume of the data track, read from the image next to the input cuesheet.
+  pub volume: Option<PrimaryVolume>,
 }
 
 #[derive(Serialize, Default)]
@@ -71,6 +77,18 @@ struct TimeInfo {
   duration: Option<u128>,
 }
 
+impl From<&TrackTiming> for TimeInfo {
+  #[inline]
+  fn from(value: &TrackTiming) -> Self {
+    Self {
+      start: value.start().as_millis(),
+      pregap_start: value.pregap_audio.map(|v| v.start.as_millis()),
+      end: value.end().map(|v| v.as_millis()),
+      duration: value.duration().map(|v| v.as_millis()),
+    }
+  }
+}
+
 impl<'a> ConvertCommand<'a> {
   #[inline]
   pub const fn new(cuesheet: &'a str) -> Self {
@@ -79,6 +97,8 @@ impl<'a> ConvertCommand<'a> {
       pretty_print: false,
       allow_metadata_remarks: false,
       output_file: None,
+      input_path: None,
+      file_lengths: Vec::new(),
     }
   }
 
@@ -100,78 +120,47 @@ impl<'a> ConvertCommand<'a> {
     self
   }
 
+  /// Sets the cuesheet path, used for reading the volumes of data tracks.
   #[inline]
-  fn process_tracks(
-    &self,
-    mut cuesheet: CueSheet<'a>,
-    mut track_probe: Tracks<'a>,
-  ) -> Result<CueSheet<'a>, ConvertError> {
-    while let Some(track) = track_probe.next_track()? {
-      let mut track_info = TrackInfo {
-        data_type: track.track_data_type(),
-        flags: track.flags(),
-        isrc: track.isrc(),
-        performer: track.performer(),
-        postgap: track.postgap(),
-        pregap: track.pregap(),
-        remark_metadata: None,
-        songwriter: track.songwriter(),
-        sub_indexes: None,
-        time_info: TimeInfo {
-          start: track.start_index().as_millis(),
-          pregap_start: track.pregap_index().map(|v| v.as_millis()),
-          end: None,
-          duration: None,
-        },
-        title: track.title(),
-        track_no: track.track_no(),
-      };
-
-      Self::process_sub_indexes(&mut track_info, track.sub_indexes())?;
-
-      if self.allow_metadata_remarks {
-        track_info.remark_metadata = metadata_from_remarks(track.vorbis_comments());
-      }
-
-      cuesheet.tracks.push(track_info);
-    }
-
-    Self::calc_track_times(&mut cuesheet.tracks);
-
-    Ok(cuesheet)
+  pub fn set_input_path(mut self, value: Option<PathBuf>) -> Self {
+    self.input_path = value;
+    self
   }
 
-  fn process_sub_indexes(
-    track: &mut TrackInfo<'a>,
-    mut indexes: TrackSubIndexes<'a>,
-  ) -> Result<(), ConvertError> {
-    let mut sub_indexes = Vec::new();
-    while let Some(index) = indexes.next_index()? {
-      sub_indexes.push(index.timestamp);
-    }
-
-    if !sub_indexes.is_empty() {
-      track.sub_indexes = Some(sub_indexes);
-    }
-
-    Ok(())
+  /// Sets the file lengths in [CueSheet::files] order, used for the end of each file's last
+  /// track.
+  #[inline]
+  pub fn set_file_lengths(mut self, value: Vec<CueTimeStamp>) -> Self {
+    self.file_lengths = value;
+    self
   }
 
-  #[inline]
-  fn calc_track_times(tracks: &mut Vec<TrackInfo>) {
-    let mut track_iter = tracks.iter_mut().peekable();
-
-    while let Some(track) = track_iter.next() {
-      if let Some(next_track) = track_iter.peek() {
-        let end = if let Some(pregap) = next_track.time_info.pregap_start {
-          pregap
-        } else {
-          next_track.time_info.start
-        };
-
-        track.time_info.end = Some(end);
-        track.time_info.duration = Some(end - track.time_info.start);
-      }
+  fn track_info<'b>(
+    &self,
+    document: &'b CueSheet,
+    track: &'b Track,
+    timing: &TrackTiming,
+    volume: Option<PrimaryVolume>,
+  ) -> TrackInfo<'b> {
+    let sub_indexes: Vec<CueTimeStamp> = track
+      .indexes
+      .iter()
+      .filter(|v| v.index_no.into_inner() > 1)
+      .map(|v| v.timestamp)
+      .collect();
+
+    TrackInfo {
+      track,
+      file: track.start_index().and_then(|v| document.index_file(v)),
+      pregap_file: track.pregap_index().and_then(|v| document.index_file(v)),
+      sub_indexes: (!sub_indexes.is_empty()).then_some(sub_indexes),
+      remark_metadata: if self.allow_metadata_remarks {
+        metadata_from_remarks(track.vorbis_comments())
+      } else {
+        None
+      },
+      time_info: TimeInfo::from(timing),
+      volume,
     }
   }
 }
@@ -181,22 +170,42 @@ impl<'a> Command for &'a ConvertCommand<'a> {
 
   fn run(self) -> Result<(), ConvertError> {
     let probe = CueSheetProbe::new(self.cuesheet)?;
-    let mut cuesheet = CueSheet {
-      catalog: probe.catalog(),
-      cdtextfile: probe.cdtextfile(),
-      file: probe.file_info(),
-      performer: probe.performer(),
-      remark_metadata: None,
-      songwriter: probe.songwriter(),
-      title: probe.album_title(),
-      tracks: Vec::new(),
+    let document = CueSheet::from_probe(&probe)?;
+    let volumes = match self.input_path.as_deref() {
+      Some(path) => read_volumes(&document, path, &self.file_lengths),
+      None => vec![None; document.tracks.len()],
     };
 
-    if self.allow_metadata_remarks {
-      cuesheet.remark_metadata = metadata_from_remarks(probe.vorbis_comments());
-    }
-
-    let cuesheet = self.process_tracks(cuesheet, probe.tracks())?;
+    let tracks: Vec<TrackInfo> = document
+      .tracks
+      .iter()
+      .zip(document.track_timings(&self.file_lengths))
+      .zip(volumes)
+      .map(|((track, timing), volume)| self.track_info(&document, track, &timing, volume))
+      .collect();
+
+    let cuesheet = CueSheetInfo {
+      arranger: document.arranger.as_deref(),
+      catalog: document.catalog,
+      cdtextfile: document.cdtextfile.as_deref(),
+      composer: document.composer.as_deref(),
+      disc_id: document.disc_id.as_deref(),
+      file: document.files.first(),
+      files: &document.files,
+      genre: document.genre.as_deref(),
+      hidden_track: document.hidden_track().map(|v| TimeInfo::from(&v)),
+      message: document.message.as_deref(),
+      performer: document.performer.as_deref(),
+      remark_metadata: if self.allow_metadata_remarks {
+        metadata_from_remarks(document.vorbis_comments())
+      } else {
+        None
+      },
+      songwriter: document.songwriter.as_deref(),
+      title: document.title.as_deref(),
+      tracks,
+      upc_ean: document.upc_ean.as_deref(),
+    };
 
     let target_stream: Box<dyn std::io::Write> = match self.output_file.as_ref() {
       Some(path) => {
@@ -1,11 +1,15 @@
 use cue_lib::{
-  core::cue_str::CueStr, metadata::VorbisTagName, probe::vorbis_remark::VorbisRemarkIter,
+  core::cue_str::CueStr,
+  metadata::{VorbisComment, VorbisTagName},
 };
 use std::collections::HashMap;
 
 pub type MetadataMap<'a> = HashMap<VorbisTagName, Vec<CueStr<'a>>>;
 
-pub fn metadata_from_remarks<'a>(vorbis_remarks: VorbisRemarkIter<'a>) -> Option<MetadataMap<'a>> {
+pub fn metadata_from_remarks<'a, I>(vorbis_remarks: I) -> Option<MetadataMap<'a>>
+where
+  I: Iterator<Item = VorbisComment<'a>>,
+{
   let mut metadata_map = MetadataMap::new();
 
   for metadata in vorbis_remarks {
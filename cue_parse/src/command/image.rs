@@ -0,0 +1,566 @@
+use super::Command;
+use crate::{args::VerboseLevel, cli_error::ErrorFormat};
+use cue_lib::{
+  addressing::{FileSize, Msf},
+  core::{album_file::KnownFileType, timestamp::CueTimeStamp, track::TrackNo},
+  document::CueSheet,
+  error::CueLibError,
+  image::{
+    self, DataTrack, ImageCopy, ImagePlan,
+    iso9660::{self, PrimaryVolume},
+    sector::{self, RAW_SECTOR_SIZE},
+  },
+  probe::CueSheetProbe,
+  writer::CueWriter,
+};
+use std::{
+  fs::File,
+  io::{BufWriter, Read, Seek, SeekFrom, Write},
+  path::{Component, Path, PathBuf},
+};
+
+/// Copy buffer size in bytes, must be even for swapping the byte order of samples.
+const BUFFER_SIZE: usize = 64 * 1024;
+
+/// Sectors read at once while extracting or verifying user data.
+const SECTOR_COUNT: usize = 32;
+
+pub enum ImageAction {
+  /// Writes one BIN file per track and the new cuesheet into the directory.
+  Split { output_dir: PathBuf },
+
+  /// Writes every track into the BIN file, the new cuesheet is written next to it.
+  Merge { output_file: PathBuf },
+
+  /// Writes the user data of the track, or the first data track, as an ISO image.
+  ExtractIso {
+    output_file: PathBuf,
+    track_no: Option<TrackNo>,
+  },
+
+  /// Checks the sync, header, EDC and ECC of every raw data sector.
+  Verify,
+}
+
+pub struct ImageCommand<'a> {
+  cuesheet: &'a str,
+  input_path: Option<PathBuf>,
+  action: ImageAction,
+}
+
+pub enum ImageError {
+  CueLibError(CueLibError),
+  ImageError(image::ImageError),
+  IOError(std::io::Error),
+
+  /// Image files are resolved relative to the cuesheet, stdin input has no location.
+  MissingInputPath,
+
+  /// Output path is the input cuesheet or one of the image files.
+  OutputOverwritesInput(PathBuf),
+
+  /// Number of bad sectors.
+  Failed(usize),
+}
+
+impl<'a> ImageCommand<'a> {
+  #[inline]
+  pub const fn new(cuesheet: &'a str, action: ImageAction) -> Self {
+    Self {
+      cuesheet,
+      input_path: None,
+      action,
+    }
+  }
+
+  #[inline]
+  pub fn set_input_path(mut self, value: Option<PathBuf>) -> Self {
+    self.input_path = value;
+    self
+  }
+
+  /// Returns the cuesheet's file name without extension, used for naming the output files.
+  fn file_stem(&self) -> String {
+    self
+      .input_path
+      .as_deref()
+      .and_then(Path::file_stem)
+      .map_or_else(|| "image".into(), |v| v.to_string_lossy().into_owned())
+  }
+}
+
+impl<'a> Command for &'a ImageCommand<'a> {
+  type Error = ImageError;
+
+  fn run(self) -> Result<(), ImageError> {
+    let input_path = self
+      .input_path
+      .as_deref()
+      .ok_or(ImageError::MissingInputPath)?;
+
+    let probe = CueSheetProbe::new(self.cuesheet)?;
+    let cuesheet = CueSheet::from_probe(&probe)?;
+    let (sources, file_sizes) = image_files(&cuesheet, input_path)?;
+
+    match &self.action {
+      ImageAction::Split { output_dir } => {
+        let stem = self.file_stem();
+        let plan = ImagePlan::split(&cuesheet, &file_sizes, |track| {
+          format!("{stem} (Track {}).bin", track.track_no)
+        })?;
+
+        write_plan(
+          &plan,
+          &sources,
+          input_path,
+          output_dir,
+          &output_dir.join(format!("{stem}.cue")),
+        )
+      }
+      ImageAction::Merge { output_file } => {
+        let file_name = output_file
+          .file_name()
+          .map_or_else(|| "image.bin".into(), |v| v.to_string_lossy().into_owned());
+
+        let plan = ImagePlan::merge(&cuesheet, &file_sizes, file_name)?;
+        let output_dir = output_file.parent().unwrap_or(Path::new(""));
+
+        write_plan(
+          &plan,
+          &sources,
+          input_path,
+          output_dir,
+          &output_file.with_extension("cue"),
+        )
+      }
+      ImageAction::ExtractIso {
+        output_file,
+        track_no,
+      } => {
+        let disc_sizes = disc_file_sizes(&cuesheet, &file_sizes, &[]);
+        let track = DataTrack::new(&cuesheet, &disc_sizes, *track_no)?;
+        check_outputs([output_file.as_path()], &sources, input_path)?;
+
+        let sector_size = track.data_type.sector_size() as usize;
+        let mut output = BufWriter::new(File::create(output_file)?);
+        let mut buffer = vec![0u8; sector_size * SECTOR_COUNT];
+
+        for copy in &track.copies {
+          let mut source = open_range(&sources[copy.file], copy)?;
+
+          loop {
+            let len = read_chunk(&mut source, &mut buffer)?;
+
+            if len == 0 {
+              break;
+            }
+
+            for sector in buffer[..len].chunks_exact(sector_size) {
+              if let Some(data) = image::user_data(track.data_type, sector) {
+                output.write_all(data)?;
+              }
+            }
+          }
+        }
+
+        output.flush()?;
+        Ok(())
+      }
+      ImageAction::Verify => match verify_tracks(
+        &cuesheet,
+        &sources,
+        &disc_file_sizes(&cuesheet, &file_sizes, &[]),
+      )? {
+        0 => Ok(()),
+        bad_sectors => Err(ImageError::Failed(bad_sectors)),
+      },
+    }
+  }
+}
+
+/// Returns the paths and byte sizes of the image files, resolved relative to the cuesheet.
+fn image_files(
+  cuesheet: &CueSheet,
+  input_path: &Path,
+) -> Result<(Vec<PathBuf>, Vec<u64>), std::io::Error> {
+  let base_dir = input_path.parent().unwrap_or(Path::new(""));
+  let sources: Vec<PathBuf> = cuesheet
+    .files
+    .iter()
+    .map(|v| base_dir.join(&v.name))
+    .collect();
+
+  let file_sizes = sources
+    .iter()
+    .map(|v| Ok(std::fs::metadata(v)?.len()))
+    .collect::<Result<Vec<u64>, std::io::Error>>()?;
+
+  Ok((sources, file_sizes))
+}
+
+/// Returns the file sizes for locating data tracks. `BINARY` and `MOTOROLA` files use their byte
+/// size, audio files use `file_lengths` or, for `WAVE` and `AIFF` files, their byte size in
+/// 2352 bytes CD audio frames. Sizes stop at the first audio file without a known length.
+fn disc_file_sizes(
+  cuesheet: &CueSheet,
+  byte_sizes: &[u64],
+  file_lengths: &[CueTimeStamp],
+) -> Vec<FileSize> {
+  cuesheet
+    .files
+    .iter()
+    .zip(byte_sizes)
+    .enumerate()
+    .map_while(|(position, (file, bytes))| match file.file_type {
+      KnownFileType::Binary | KnownFileType::Motorola => Some(FileSize::Bytes(*bytes)),
+      _ if position < file_lengths.len() => Some(FileSize::Length(file_lengths[position])),
+      // 16-bit stereo PCM at 44.1 kHz, the header is smaller than a frame.
+      KnownFileType::WAVE | KnownFileType::AIFF => Some(FileSize::Length(
+        CueTimeStamp::from_frames(bytes / RAW_SECTOR_SIZE as u64),
+      )),
+      _ => None,
+    })
+    .collect()
+}
+
+/// Reads the ISO 9660 volume of each track in [CueSheet::tracks] order. Audio tracks, non-ISO
+/// data tracks and unreadable images have no volume.
+///
+/// `file_lengths` are the lengths of the audio files in [CueSheet::files] order.
+pub fn read_volumes(
+  cuesheet: &CueSheet,
+  input_path: &Path,
+  file_lengths: &[CueTimeStamp],
+) -> Vec<Option<PrimaryVolume>> {
+  let mut volumes = vec![None; cuesheet.tracks.len()];
+  let Ok((sources, file_sizes)) = image_files(cuesheet, input_path) else {
+    return volumes;
+  };
+
+  let file_sizes = disc_file_sizes(cuesheet, &file_sizes, file_lengths);
+  let Ok(data_tracks) = DataTrack::all(cuesheet, &file_sizes) else {
+    return volumes;
+  };
+
+  for track in data_tracks {
+    let Some((file, offset)) = track.sector_offset(iso9660::PRIMARY_VOLUME_SECTOR) else {
+      continue;
+    };
+
+    let mut sector = vec![0u8; track.data_type.sector_size() as usize];
+    let read = File::open(&sources[file]).and_then(|mut v| {
+      v.seek(SeekFrom::Start(offset))
+        .and(v.read_exact(&mut sector))
+    });
+
+    let volume = read
+      .ok()
+      .and_then(|_| image::user_data(track.data_type, &sector))
+      .and_then(PrimaryVolume::parse);
+
+    if let Some(position) = cuesheet
+      .tracks
+      .iter()
+      .position(|v| v.track_no == track.track_no)
+    {
+      volumes[position] = volume;
+    }
+  }
+
+  volumes
+}
+
+/// Prints every bad sector and a summary per data track, returns the number of bad sectors.
+fn verify_tracks(
+  cuesheet: &CueSheet,
+  sources: &[PathBuf],
+  file_sizes: &[FileSize],
+) -> Result<usize, ImageError> {
+  let mut total = 0;
+
+  for track in DataTrack::all(cuesheet, file_sizes)? {
+    if !sector::is_raw(track.data_type) {
+      println!(
+        "Track {track_no} {data_type}: skipped, no EDC/ECC",
+        track_no = track.track_no,
+        data_type = track.data_type
+      );
+
+      continue;
+    }
+
+    let mut buffer = vec![0u8; RAW_SECTOR_SIZE * SECTOR_COUNT];
+    let mut lba = track.lba;
+    let mut bad_sectors = 0;
+
+    for copy in &track.copies {
+      let mut source = open_range(&sources[copy.file], copy)?;
+
+      loop {
+        let len = read_chunk(&mut source, &mut buffer)?;
+
+        if len == 0 {
+          break;
+        }
+
+        for sector in buffer[..len].chunks(RAW_SECTOR_SIZE) {
+          if let Err(error) = sector::verify_sector(track.data_type, lba, sector) {
+            println!(
+              "Track {track_no} LBA {lba} ({msf}): {error}",
+              track_no = track.track_no,
+              msf = Msf::from_lba(lba)
+            );
+
+            bad_sectors += 1;
+          }
+
+          lba += 1;
+        }
+      }
+    }
+
+    println!(
+      "Track {track_no} {data_type}: {sectors} sectors, {bad_sectors} bad",
+      track_no = track.track_no,
+      data_type = track.data_type,
+      sectors = lba - track.lba
+    );
+
+    total += bad_sectors;
+  }
+
+  Ok(total)
+}
+
+/// Writes the image files and the regenerated cuesheet.
+fn write_plan(
+  plan: &ImagePlan,
+  sources: &[PathBuf],
+  input_path: &Path,
+  output_dir: &Path,
+  cue_path: &Path,
+) -> Result<(), ImageError> {
+  let outputs: Vec<PathBuf> = plan
+    .cuesheet
+    .files
+    .iter()
+    .map(|v| output_dir.join(&v.name))
+    .collect();
+
+  check_outputs(
+    outputs.iter().map(PathBuf::as_path).chain([cue_path]),
+    sources,
+    input_path,
+  )?;
+
+  let mut buffer = vec![0u8; BUFFER_SIZE];
+  std::fs::create_dir_all(output_dir)?;
+
+  for (file, copies) in plan.cuesheet.files.iter().zip(&plan.outputs) {
+    let mut output = BufWriter::new(File::create(output_dir.join(&file.name))?);
+
+    for copy in copies {
+      let mut source = open_range(&sources[copy.file], copy)?;
+
+      loop {
+        let len = read_chunk(&mut source, &mut buffer)?;
+
+        if len == 0 {
+          break;
+        }
+
+        if copy.swap_bytes {
+          image::swap_bytes(&mut buffer[..len]);
+        }
+
+        output.write_all(&buffer[..len])?;
+      }
+    }
+
+    output.flush()?;
+  }
+
+  std::fs::write(cue_path, CueWriter::new().to_string(&plan.cuesheet))?;
+  Ok(())
+}
+
+/// Fails when any output path points to the input cuesheet or an image file, since creating the
+/// output would truncate it before it is read.
+fn check_outputs<'p>(
+  outputs: impl IntoIterator<Item = &'p Path>,
+  sources: &[PathBuf],
+  input_path: &Path,
+) -> Result<(), ImageError> {
+  let inputs: Vec<PathBuf> = sources
+    .iter()
+    .map(PathBuf::as_path)
+    .chain([input_path])
+    .map(resolve_path)
+    .collect();
+
+  for output in outputs {
+    if inputs.contains(&resolve_path(output)) {
+      return Err(ImageError::OutputOverwritesInput(output.to_path_buf()));
+    }
+  }
+
+  Ok(())
+}
+
+/// Canonicalizes the path component by component, so missing files and directories are still
+/// resolved against their existing parents.
+fn resolve_path(path: &Path) -> PathBuf {
+  let mut resolved = PathBuf::new();
+
+  for component in std::path::absolute(path)
+    .unwrap_or_else(|_| path.to_path_buf())
+    .components()
+  {
+    match component {
+      Component::CurDir => continue,
+      Component::ParentDir => _ = resolved.pop(),
+      component => resolved.push(component),
+    }
+
+    if let Ok(path) = resolved.canonicalize() {
+      resolved = path;
+    }
+  }
+
+  resolved
+}
+
+/// Opens the source file limited to the byte range.
+fn open_range(path: &Path, copy: &ImageCopy) -> Result<std::io::Take<File>, std::io::Error> {
+  let mut file = File::open(path)?;
+  file.seek(SeekFrom::Start(copy.start))?;
+
+  Ok(file.take(copy.end - copy.start))
+}
+
+/// Fills the buffer as much as possible, so chunks always end at a sector boundary.
+fn read_chunk<R: Read>(source: &mut R, buffer: &mut [u8]) -> Result<usize, std::io::Error> {
+  let mut len = 0;
+
+  while len < buffer.len() {
+    match source.read(&mut buffer[len..])? {
+      0 => break,
+      read => len += read,
+    }
+  }
+
+  Ok(len)
+}
+
+impl ErrorFormat for ImageError {
+  fn fmt(
+    &self,
+    f: &mut std::fmt::Formatter<'_>,
+    input_buffer: &str,
+    verbose_level: VerboseLevel,
+  ) -> std::fmt::Result {
+    if verbose_level == VerboseLevel::Quiet {
+      Ok(())
+    } else {
+      match self {
+        ImageError::CueLibError(error) => ErrorFormat::fmt(error, f, input_buffer, verbose_level),
+        ImageError::ImageError(error) => std::fmt::Display::fmt(&error, f),
+        ImageError::IOError(error) => std::fmt::Display::fmt(&error, f),
+        ImageError::MissingInputPath => {
+          f.write_str("image commands require the cuesheet path (--input)")
+        }
+        ImageError::OutputOverwritesInput(path) => write!(
+          f,
+          "output {path} would overwrite the input cuesheet or an image file",
+          path = path.display()
+        ),
+        ImageError::Failed(bad_sectors) => {
+          write!(f, "verification failed with {bad_sectors} bad sector(s)")
+        }
+      }
+    }
+  }
+}
+
+impl From<CueLibError> for ImageError {
+  #[inline]
+  fn from(value: CueLibError) -> Self {
+    Self::CueLibError(value)
+  }
+}
+
+impl From<image::ImageError> for ImageError {
+  #[inline]
+  fn from(value: image::ImageError) -> Self {
+    Self::ImageError(value)
+  }
+}
+
+impl From<std::io::Error> for ImageError {
+  #[inline]
+  fn from(value: std::io::Error) -> Self {
+    Self::IOError(value)
+  }
+}
+
+#[cfg(test)]
+mod tests {
+  use super::*;
+
+  const CUESHEET: &str = "FILE \"game.bin\" BINARY\n  TRACK 01 MODE1/2352\n    INDEX 01 00:00:00\n";
+
+  #[test]
+  fn refuses_to_overwrite_inputs() {
+    let dir = std::env::temp_dir().join(format!("cue_parse_image_{}", std::process::id()));
+    std::fs::create_dir_all(dir.join("out")).unwrap();
+
+    let input_path = dir.join("game.cue");
+    let image = vec![0xAA; 2352 * 4];
+    std::fs::write(&input_path, CUESHEET).unwrap();
+    std::fs::write(dir.join("game.bin"), &image).unwrap();
+
+    let actions = [
+      ImageAction::Merge {
+        output_file: dir.join("game.bin"),
+      },
+      ImageAction::Merge {
+        output_file: dir.join("missing/../game.bin"),
+      },
+      ImageAction::Merge {
+        output_file: dir.join("out/../game.wav"),
+      },
+      ImageAction::Split {
+        output_dir: dir.clone(),
+      },
+      ImageAction::ExtractIso {
+        output_file: dir.join("out/../game.bin"),
+        track_no: None,
+      },
+    ];
+
+    for action in actions {
+      let command = ImageCommand::new(CUESHEET, action).set_input_path(Some(input_path.clone()));
+
+      assert!(matches!(
+        command.run(),
+        Err(ImageError::OutputOverwritesInput(_))
+      ));
+    }
+
+    assert_eq!(std::fs::read(dir.join("game.bin")).unwrap(), image);
+    assert_eq!(std::fs::read_to_string(&input_path).unwrap(), CUESHEET);
+
+    let command = ImageCommand::new(
+      CUESHEET,
+      ImageAction::Merge {
+        output_file: dir.join("out/game.bin"),
+      },
+    )
+    .set_input_path(Some(input_path));
+
+    assert!(command.run().is_ok());
+    assert_eq!(std::fs::read(dir.join("out/game.bin")).unwrap(), image);
+
+    std::fs::remove_dir_all(dir).unwrap();
+  }
+}
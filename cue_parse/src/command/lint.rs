@@ -0,0 +1,93 @@
+use super::Command;
+use crate::{args::VerboseLevel, cli_error::ErrorFormat};
+use cue_lib::{
+  core::timestamp::CueTimeStamp,
+  error::{CueLibError, Severity},
+  lint::LintConfig,
+};
+
+pub struct LintCommand<'a> {
+  cuesheet: &'a str,
+  config: LintConfig,
+  file_lengths: Vec<CueTimeStamp>,
+}
+
+pub enum LintError {
+  CueLibError(CueLibError),
+
+  /// Number of findings with error severity.
+  Failed(usize),
+}
+
+impl<'a> LintCommand<'a> {
+  #[inline]
+  pub const fn new(cuesheet: &'a str) -> Self {
+    Self {
+      cuesheet,
+      config: LintConfig::new(),
+      file_lengths: Vec::new(),
+    }
+  }
+
+  #[inline]
+  pub const fn set_config(mut self, value: LintConfig) -> Self {
+    self.config = value;
+    self
+  }
+
+  /// Sets the file lengths in FILE order, used for the length of each file's last track.
+  #[inline]
+  pub fn set_file_lengths(mut self, value: Vec<CueTimeStamp>) -> Self {
+    self.file_lengths = value;
+    self
+  }
+}
+
+impl<'a> Command for &'a LintCommand<'a> {
+  type Error = LintError;
+
+  /// Prints every finding and fails when any of them is an error.
+  fn run(self) -> Result<(), LintError> {
+    let findings = self
+      .config
+      .lint_with_file_lengths(self.cuesheet, &self.file_lengths)?;
+
+    for finding in findings.iter() {
+      println!("{finding}");
+    }
+
+    match findings
+      .iter()
+      .filter(|v| v.severity() == Severity::Error)
+      .count()
+    {
+      0 => Ok(()),
+      errors => Err(LintError::Failed(errors)),
+    }
+  }
+}
+
+impl ErrorFormat for LintError {
+  fn fmt(
+    &self,
+    f: &mut std::fmt::Formatter<'_>,
+    input_buffer: &str,
+    verbose_level: VerboseLevel,
+  ) -> std::fmt::Result {
+    if verbose_level == VerboseLevel::Quiet {
+      Ok(())
+    } else {
+      match self {
+        LintError::CueLibError(error) => ErrorFormat::fmt(error, f, input_buffer, verbose_level),
+        LintError::Failed(errors) => write!(f, "lint failed with {errors} error(s)"),
+      }
+    }
+  }
+}
+
+impl From<CueLibError> for LintError {
+  #[inline]
+  fn from(value: CueLibError) -> Self {
+    Self::CueLibError(value)
+  }
+}
@@ -1,4 +1,5 @@
 use super::Command;
+use crate::cli_error::Diagnostics;
 use cue_lib::probe::CueSheetProbe;
 
 pub struct CommandVerify<'a> {
@@ -13,10 +14,11 @@ impl<'a> CommandVerify<'a> {
 }
 
 impl<'a> Command for &'a CommandVerify<'a> {
-  type Error = cue_lib::error::CueLibError;
+  type Error = Diagnostics;
 
+  /// Reports every problem in the cuesheet at once.
   #[inline]
-  fn run(self) -> Result<(), cue_lib::error::CueLibError> {
-    CueSheetProbe::verify(self.cuesheet)
+  fn run(self) -> Result<(), Diagnostics> {
+    CueSheetProbe::verify_all(self.cuesheet).map_err(Diagnostics)
   }
 }
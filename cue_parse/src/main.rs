@@ -1,8 +1,15 @@
 use self::{
-  args::Args,
+  args::{Args, VerboseLevel},
   cli_error::cli_stderr,
-  command::{Command, convert::ConvertCommand, verify::CommandVerify},
+  command::{
+    Command,
+    convert::ConvertCommand,
+    image::{ImageAction, ImageCommand},
+    lint::LintCommand,
+    verify::CommandVerify,
+  },
 };
+use cue_lib::{core::track::TrackNo, encoding::DecodedCueSheet, error::Severity, lint::LintConfig};
 use std::{io::Read as _, path::Path, process::ExitCode};
 
 pub mod args;
@@ -10,17 +17,17 @@ pub mod cli_error;
 pub mod command;
 
 #[inline]
-fn read_cuesheet<T>(path: Option<T>) -> Result<String, std::io::Error>
+fn read_input<T>(path: Option<T>) -> Result<Vec<u8>, std::io::Error>
 where
   T: AsRef<Path>,
 {
   match path {
-    Some(path) => std::fs::read_to_string(path),
+    Some(path) => std::fs::read(path),
     None => {
-      let mut buffer = String::new();
+      let mut buffer = Vec::new();
       let mut stdin = std::io::stdin();
 
-      match stdin.read_to_string(&mut buffer) {
+      match stdin.read_to_end(&mut buffer) {
         Ok(_) => Ok(buffer),
         Err(err) => Err(err),
       }
@@ -31,7 +38,7 @@ where
 fn main() -> ExitCode {
   let args = Args::init();
   let verbosity = args.verbose.unwrap_or_default();
-  let cuesheet = match read_cuesheet(args.input.as_ref()) {
+  let input = match read_input(args.input.as_ref()) {
     Ok(buffer) => buffer,
     Err(err) => {
       cli_stderr!(err, input = "", verbosity = verbosity);
@@ -39,6 +46,28 @@ fn main() -> ExitCode {
     }
   };
 
+  let decoded = match args.encoding {
+    Some(encoding) => DecodedCueSheet::decode_with(&input, encoding),
+    None => DecodedCueSheet::decode(&input),
+  };
+
+  if verbosity == VerboseLevel::Full {
+    eprintln!(
+      "Input encoding: {name} ({source})",
+      name = decoded.encoding_name(),
+      source = decoded.source()
+    );
+  }
+
+  if decoded.had_errors() && verbosity != VerboseLevel::Quiet {
+    eprintln!(
+      "warning: input contains invalid {name} sequences",
+      name = decoded.encoding_name()
+    );
+  }
+
+  let cuesheet = decoded.into_string();
+
   macro_rules! run {
     ($cmd:expr) => {
       match $cmd.run() {
@@ -60,12 +89,59 @@ fn main() -> ExitCode {
       output_file,
       metadata,
       pretty_print,
+      file_length,
     } => {
       let cmd = ConvertCommand::new(cuesheet.as_str())
         .set_metadata_remarks(metadata)
         .set_output_file(output_file)
-        .set_pretty_print(pretty_print);
+        .set_input_path(args.input)
+        .set_pretty_print(pretty_print)
+        .set_file_lengths(file_length);
+
+      run!(cmd)
+    }
+    args::Commands::Lint {
+      red_book,
+      allow,
+      warn,
+      deny,
+      file_length,
+    } => {
+      let mut config = if red_book {
+        LintConfig::red_book()
+      } else {
+        LintConfig::new()
+      };
+
+      for rule in allow {
+        config = config.disable(rule);
+      }
+
+      for rule in warn {
+        config = config.set_severity(rule, Some(Severity::Warning));
+      }
+
+      for rule in deny {
+        config = config.set_severity(rule, Some(Severity::Error));
+      }
+
+      let cmd = LintCommand::new(cuesheet.as_str())
+        .set_config(config)
+        .set_file_lengths(file_length);
+      run!(cmd)
+    }
+    args::Commands::Image { command } => {
+      let action = match command {
+        args::ImageCommands::Split { output_dir } => ImageAction::Split { output_dir },
+        args::ImageCommands::Merge { output_file } => ImageAction::Merge { output_file },
+        args::ImageCommands::ExtractIso { output_file, track } => ImageAction::ExtractIso {
+          output_file,
+          track_no: track.and_then(TrackNo::new),
+        },
+        args::ImageCommands::Verify => ImageAction::Verify,
+      };
 
+      let cmd = ImageCommand::new(cuesheet.as_str(), action).set_input_path(args.input);
       run!(cmd)
     }
     args::Commands::Query { input } => {